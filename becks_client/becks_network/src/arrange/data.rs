@@ -0,0 +1,94 @@
+use crate::prelude::*;
+use becks_arrange::*;
+use becks_convey::arrange::*;
+
+#[derive(Debug, Clone)]
+pub struct ArrangementInfo {
+    pub id: Id,
+    pub data: Option<Arrangement>,
+}
+
+impl ArrangementInfo {
+    pub fn new(id: Id) -> Self {
+        Self { id, data: None }
+    }
+
+    /// Uploads the scheduled groups to the server, returning the created arrangement info
+    pub async fn create(login: &Login, groups: Vec<Group>) -> Result<Self> {
+        let response = login
+            .post(server_url!("arrange/create"))
+            .json(&create::CreateRequest {
+                token: None,
+                groups,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: create::CreateResponse = response.json().await?;
+        Ok(Self::new(response.arrangement))
+    }
+
+    /// Lists the open arrangements from the latest one
+    pub async fn list_open(login: &Login) -> Result<Vec<Self>> {
+        let response = login
+            .get(server_url!("arrange/list"))
+            .json(&list::ListRequest {
+                token: None,
+                closed: false,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: list::ListResponse = response.json().await?;
+        Ok(response.arrangements.into_iter().map(Self::new).collect())
+    }
+
+    /// Forces to reload arrangement data from the server, where the played pairings are linked
+    pub async fn reload(&mut self, login: &Login) -> Result<&mut Arrangement> {
+        let response = login
+            .get(server_url!("arrange/acquire"))
+            .json(&acquire::AcquireRequest {
+                token: None,
+                arrangement: self.id(),
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: acquire::AcquireResponse = response.json().await?;
+        Ok(self.data.insert(response.arrangement))
+    }
+
+    /// Closes the arrangement on the server, keeping its final state
+    pub async fn close(&mut self, login: &Login) -> Result<&mut Arrangement> {
+        let response = login
+            .post(server_url!("arrange/close"))
+            .json(&close::CloseRequest {
+                token: None,
+                arrangement: self.id(),
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: close::CloseResponse = response.json().await?;
+        Ok(self.data.insert(response.arrangement))
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Loads arrangement data if not previously loaded, then returns it
+    pub async fn load(&mut self, login: &Login) -> Result<&mut Arrangement> {
+        if self.data.is_none() {
+            self.reload(login).await?;
+        }
+        Ok(self
+            .data
+            .as_mut()
+            .expect("arrangement data should be loaded after check"))
+    }
+}
@@ -5,7 +5,7 @@ use becks_crew::*;
 #[derive(Debug, Clone)]
 pub struct CrewInfo {
     id: Id,
-    data: Option<CrewData>,
+    pub data: Option<CrewData>,
 }
 
 impl CrewInfo {
@@ -16,16 +16,16 @@ impl CrewInfo {
     /// Uploads a new crew to the server, returning the created crew
     pub async fn create(login: &Login, name: String, social: Social) -> Result<Self> {
         let response = login
-            .client()
             .post(server_url!("crew/create"))
             .json(&create::CreateRequest {
-                token: login.token(),
+                token: None,
                 name,
                 social,
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         let response: create::CreateResponse = response.json().await?;
         Ok(Self {
             id: response.crew,
@@ -36,15 +36,15 @@ impl CrewInfo {
     /// Deletes the crew, invalidating the crew info
     pub async fn delete(&mut self, login: &Login) -> Result<()> {
         let _response = login
-            .client()
             .post(server_url!("crew/delete"))
             .json(&delete::DeleteRequest {
-                token: login.token(),
+                token: None,
                 crew: self.id,
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         self.data = None;
         Ok(())
     }
@@ -52,15 +52,15 @@ impl CrewInfo {
     /// Forces to reload user data from server
     pub async fn reload(&mut self, login: &Login) -> Result<&mut CrewData> {
         let response = login
-            .client()
             .get(server_url!("crew/acquire"))
             .json(&modify::AcquireRequest {
-                token: login.token(),
+                token: None,
                 crew: self.id,
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         let response: modify::AcquireResponse = response.json().await?;
         Ok(self.data.insert(response.crew))
     }
@@ -85,18 +85,55 @@ impl CrewInfo {
         self.data = None;
     }
 
+    /// Acquires the initial score of the crew and its score after each match, from the earliest
+    pub async fn history(&self, login: &Login) -> Result<(i32, Vec<becks_match::ScorePoint>)> {
+        let response = login
+            .get(server_url!("crew/history"))
+            .json(&history::HistoryRequest {
+                token: None,
+                crew: self.id,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: history::HistoryResponse = response.json().await?;
+        Ok((response.initial, response.history))
+    }
+
+    /// Acquires the stats of the crew, with the head-to-head record against the opponent if given
+    pub async fn stats(
+        &self,
+        login: &Login,
+        opponent: Option<Id>,
+    ) -> Result<becks_match::CrewStats> {
+        let response = login
+            .get(server_url!("crew/stats"))
+            .json(&stats::StatsRequest {
+                token: None,
+                crew: self.id,
+                opponent,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: stats::StatsResponse = response.json().await?;
+        Ok(response.stats)
+    }
+
     pub async fn modify(&mut self, login: &Login, loc: CrewLocation) -> Result<()> {
         let _response = login
-            .client()
             .post(server_url!("crew/modify"))
             .json(&modify::ModifyRequest {
-                token: login.token(),
+                token: None,
                 crew: self.id,
                 loc,
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         self.unload();
         Ok(())
     }
@@ -1,7 +1,10 @@
 use super::data::*;
+use crate::error::{ApiError, ErrorCode};
 use crate::prelude::*;
 use becks_convey::crew::*;
+use becks_convey::page::{PageInfo, PageRequest};
 use becks_crew::*;
+use std::collections::HashSet;
 use std::future::Future;
 use std::ops::Deref;
 use tokio::sync::RwLock;
@@ -15,6 +18,9 @@ impl crate::util::GetId for RwLock<CrewInfo> {
 #[derive(Debug, Default)]
 pub struct CrewList {
     list: Vec<RwLock<CrewInfo>>,
+    /// The conditions of the query, or None if the list is not queried
+    by: Option<Vec<CrewLocation>>,
+    page: PageInfo,
 }
 
 impl Deref for CrewList {
@@ -25,19 +31,27 @@ impl Deref for CrewList {
 }
 
 impl CrewList {
-    /// Queries the crew list and returns the stored form
+    /// Queries the first page of the crew list and returns it
     pub async fn query(login: &Login, by: Vec<CrewLocation>) -> Result<Self> {
+        Self::query_page(login, by, 0).await
+    }
+
+    async fn query_page(login: &Login, by: Vec<CrewLocation>, offset: usize) -> Result<Self> {
         let response = login
-            .client()
             .get(server_url!("crew/query"))
             .json(&query::QueryByRequest {
-                token: login.token(),
-                by,
+                token: None,
+                by: by.clone(),
                 fuzzy: true,
+                page: PageRequest {
+                    offset,
+                    limit: None,
+                },
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         let response: query::QueryByResponse = response.json().await?;
         let result = Self {
             list: response
@@ -45,6 +59,8 @@ impl CrewList {
                 .into_iter()
                 .map(|id| RwLock::new(CrewInfo::new(id)))
                 .collect(),
+            by: Some(by),
+            page: response.page,
         };
         Ok(result)
     }
@@ -55,6 +71,43 @@ impl CrewList {
                 .into_iter()
                 .map(|id| RwLock::new(CrewInfo::new(id)))
                 .collect(),
+            by: None,
+            page: Default::default(),
+        }
+    }
+
+    /// Returns whether more crews of the query are left on the server
+    pub fn has_more(&self) -> bool {
+        self.by.is_some() && self.page.next.is_some()
+    }
+
+    /// Returns the number of all crews found by the query, including those not yet in the list
+    pub fn total(&self) -> usize {
+        self.page.total.max(self.list.len())
+    }
+
+    /// Queries the page after this list, which can be appended to it
+    pub async fn next_page(&self, login: &Login) -> Result<Option<Self>> {
+        match (&self.by, self.page.next) {
+            (Some(by), Some(next)) => Ok(Some(Self::query_page(login, by.clone(), next).await?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Appends a page given by [`Self::next_page`]
+    pub fn append(&mut self, page: Self) {
+        self.list.extend(page.list);
+        self.page = page.page;
+    }
+
+    /// Loads the next page of the query into this list, returns false if no more are left
+    pub async fn load_more(&mut self, login: &Login) -> Result<bool> {
+        match self.next_page(login).await? {
+            Some(page) => {
+                self.append(page);
+                Ok(true)
+            }
+            None => Ok(false),
         }
     }
 
@@ -68,15 +121,80 @@ impl CrewList {
         crate::util::sort_by_value(&mut self.list, f).await
     }
 
-    /// Forces reload all crew from the server
+    /// Forces reload all crew from the server in one request
     pub async fn reload(&self, login: &Login) -> Result<()> {
-        let mut futures = Vec::new();
-        for crew in self.list.iter() {
-            futures.push(async { crew.write().await.reload(login).await.map(|_| ()) });
+        let mut ids = Vec::new();
+        for item in self.list.iter() {
+            ids.push(item.read().await.id());
         }
-        for future in futures.into_iter() {
-            future.await?;
+        self.acquire(login, ids).await
+    }
+
+    /// Loads the crew that are not previously loaded in one request
+    pub async fn load(&self, login: &Login) -> Result<()> {
+        let mut ids = Vec::new();
+        for item in self.list.iter() {
+            let item = item.read().await;
+            if item.data.is_none() {
+                ids.push(item.id());
+            }
+        }
+        self.acquire(login, ids).await
+    }
+
+    /// Acquires the data of given ids, and stores it to every crew of these ids
+    async fn acquire(&self, login: &Login, ids: Vec<Id>) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let requested = ids.iter().copied().collect::<HashSet<_>>();
+        let response = login
+            .get(server_url!("crew/acquire_many"))
+            .json(&modify::AcquireManyRequest {
+                token: None,
+                crews: ids,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: modify::AcquireManyResponse = response.json().await?;
+        let mut missing = 0;
+        for item in self.list.iter() {
+            let mut item = item.write().await;
+            if requested.contains(&item.id()) {
+                item.data = response.crews.get(&item.id()).cloned();
+                if item.data.is_none() {
+                    missing += 1;
+                }
+            }
+        }
+        if missing > 0 {
+            return Err(ApiError::new(
+                ErrorCode::CrewNotFound,
+                format!("{} of the crew are not found", missing),
+            )
+            .into());
         }
         Ok(())
     }
 }
+
+/// Acquires the ranked crews that pass the filter, from the highest score
+pub async fn leaderboard(
+    login: &Login,
+    filter: leaderboard::LeaderboardFilter,
+) -> Result<Vec<leaderboard::LeaderboardRow>> {
+    let response = login
+        .get(server_url!("crew/leaderboard"))
+        .json(&leaderboard::LeaderboardRequest {
+            token: None,
+            filter,
+        })
+        .send()
+        .await?
+        .check()
+        .await?;
+    let response: leaderboard::LeaderboardResponse = response.json().await?;
+    Ok(response.rows)
+}
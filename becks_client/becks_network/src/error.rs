@@ -0,0 +1,33 @@
+use crate::prelude::*;
+pub use becks_convey::error::{ApiError, ErrorCode};
+
+pub(crate) trait CheckResponse: Sized {
+    /// Returns the response if it is successful, or the [`ApiError`] given by the server
+    async fn check(self) -> Result<Self>;
+}
+
+impl CheckResponse for reqwest::Response {
+    async fn check(self) -> Result<Self> {
+        let status = self.status();
+        if status.is_success() {
+            return Ok(self);
+        }
+        let url = self.url().clone();
+        let err = self.json::<ApiError>().await.unwrap_or_else(|err| {
+            warn!("Error response of {} cannot be decoded, {}", url, err);
+            ApiError::new(ErrorCode::Unknown, status.to_string())
+        });
+        debug!("Server returns {} for {}, {}", status, url, err);
+        Err(err.into())
+    }
+}
+
+/// Returns the error given by the server, if the error comes from the server
+pub fn api_error(err: &anyhow::Error) -> Option<&ApiError> {
+    err.downcast_ref()
+}
+
+/// Returns the code of the error given by the server, if the error comes from the server
+pub fn error_code(err: &anyhow::Error) -> Option<ErrorCode> {
+    api_error(err).map(|err| err.code)
+}
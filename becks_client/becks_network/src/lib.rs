@@ -1,9 +1,12 @@
+pub mod arrange;
 pub mod config;
 pub mod crew;
+pub mod error;
 mod login;
 pub mod mat;
 pub mod poster;
 mod prelude;
+pub mod swiss;
 pub mod util;
 
 pub use login::*;
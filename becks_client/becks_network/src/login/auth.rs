@@ -1,20 +1,12 @@
 use crate::prelude::*;
 use becks_convey::user::auth::*;
+use becks_convey::user::member::Role;
 use std::sync::{LazyLock, Mutex};
 
-#[derive(Error, Debug)]
-enum LoginError {
-    #[error("log-in information is wrong")]
-    Wrong,
-    #[error("username or password is illegal")]
-    Illegal,
-    #[error("server returns an unexpected status code")]
-    Unexpected,
-}
-
 #[derive(Debug)]
 pub struct Login {
     token: Token,
+    role: Role,
     pub end: Mutex<bool>,
 }
 
@@ -24,7 +16,7 @@ impl Login {
     /// The first layer of Result is errors generated by creation,
     /// the second layer is errors generated by log-in
     pub async fn create(name: String, pass: String) -> Result<Result<Self>> {
-        debug!("Attempt to create user name {}, pass {}", name, pass);
+        debug!("Attempt to create user name {}", name);
         let _response = CLIENT
             .post(server_url!("user/create"))
             .json(&CreateRequest {
@@ -33,47 +25,45 @@ impl Login {
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         Ok(Self::log_in(name, pass).await)
     }
 
     /// Attempts to log in with given credentials
     pub async fn log_in(name: String, pass: String) -> Result<Self> {
-        debug!("Attempt to log-in with name {}, pass {}", name, pass);
+        Self::log_in_to(name, pass, None).await
+    }
+
+    /// Attempts to log in with given credentials to the database of a club,
+    /// or the own database if no club is given
+    pub async fn log_in_to(name: String, pass: String, club: Option<String>) -> Result<Self> {
+        debug!("Attempt to log-in with name {}", name);
         let response = CLIENT
             .post(server_url!("user/login"))
-            .json(&LoginRequest { name, pass })
+            .json(&LoginRequest { name, pass, club })
             .send()
+            .await?
+            .check()
             .await?;
-        match response.status() {
-            StatusCode::OK => {
-                let response: LoginResponse = response.json().await?;
-                Ok(Login {
-                    token: response.token,
-                    end: Mutex::new(false),
-                })
-            }
-            StatusCode::BAD_REQUEST => Err(LoginError::Illegal.into()),
-            StatusCode::UNAUTHORIZED => Err(LoginError::Wrong.into()),
-            status => {
-                error!("Server returns unexpected status code: {}", status);
-                Err(LoginError::Unexpected.into())
-            }
-        }
+        let response: LoginResponse = response.json().await?;
+        Ok(Login {
+            token: response.token,
+            role: response.role,
+            end: Mutex::new(false),
+        })
     }
 
     /// Logs out of the server
     pub async fn log_out(&self) -> Result<()> {
-        debug!("Attempt to log-out token {:?}", self.token());
+        debug!("Attempt to log-out");
         let _response = self
-            .client()
             .post(server_url!("user/logout"))
-            .json(&LogoutRequest {
-                token: self.token(),
-            })
+            .json(&LogoutRequest { token: None })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         Ok(())
     }
 
@@ -81,12 +71,27 @@ impl Login {
         &CLIENT
     }
 
-    pub fn token(&self) -> Token {
-        self.token
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Returns the role of the user on the logged-in database
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Starts a POST request authorized with the token of this login
+    pub fn post(&self, url: impl reqwest::IntoUrl) -> reqwest::RequestBuilder {
+        self.client().post(url).bearer_auth(&self.token)
+    }
+
+    /// Starts a GET request authorized with the token of this login
+    pub fn get(&self, url: impl reqwest::IntoUrl) -> reqwest::RequestBuilder {
+        self.client().get(url).bearer_auth(&self.token)
     }
 }
 
-static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+pub(super) static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
     reqwest::Client::builder()
         .timeout(std::time::Duration::new(1, 0))
         .build()
@@ -0,0 +1,63 @@
+use super::auth::CLIENT;
+use crate::prelude::*;
+use becks_convey::user::manage::*;
+
+impl Login {
+    /// Changes the password of the logged-in user
+    pub async fn change_password(&self, old: String, new: String) -> Result<()> {
+        debug!("Attempt to change password");
+        let _response = self
+            .post(server_url!("user/password"))
+            .json(&PasswordRequest {
+                token: None,
+                old,
+                new,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes the logged-in user along with its database, invalidating this login
+    pub async fn delete(&self, pass: String) -> Result<()> {
+        debug!("Attempt to delete user");
+        let _response = self
+            .post(server_url!("user/delete"))
+            .json(&DeleteRequest { token: None, pass })
+            .send()
+            .await?
+            .check()
+            .await?;
+        *self.end.lock().unwrap() = true;
+        Ok(())
+    }
+
+    /// Issues a one-time reset code for the named user, requiring administrator rights
+    pub async fn issue_reset(&self, name: String) -> Result<String> {
+        debug!("Attempt to issue reset code for user {}", name);
+        let response = self
+            .post(server_url!("user/reset/issue"))
+            .json(&IssueResetRequest { token: None, name })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: IssueResetResponse = response.json().await?;
+        Ok(response.code)
+    }
+
+    /// Resets the password of the named user with a code issued by an administrator
+    pub async fn reset_password(name: String, code: String, pass: String) -> Result<()> {
+        debug!("Attempt to reset password of user {}", name);
+        let _response = CLIENT
+            .post(server_url!("user/reset"))
+            .json(&ResetRequest { name, code, pass })
+            .send()
+            .await?
+            .check()
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::prelude::*;
+use becks_convey::user::member::*;
+
+impl Login {
+    /// Lists the members of the club owned by the logged-in user
+    pub async fn members(&self) -> Result<Vec<MemberInfo>> {
+        let response = self
+            .get(server_url!("user/members"))
+            .json(&MembersRequest { token: None })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: MembersResponse = response.json().await?;
+        Ok(response.members)
+    }
+
+    /// Lists the clubs the logged-in user is a member of
+    pub async fn clubs(&self) -> Result<Vec<ClubInfo>> {
+        let response = self
+            .get(server_url!("user/clubs"))
+            .json(&ClubsRequest { token: None })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: ClubsResponse = response.json().await?;
+        Ok(response.clubs)
+    }
+
+    /// Grants a role on the club of the logged-in user to another user
+    pub async fn grant(&self, member: String, role: Role) -> Result<()> {
+        debug!("Attempt to grant {:?} to user {}", role, member);
+        let _response = self
+            .post(server_url!("user/members/grant"))
+            .json(&GrantRequest {
+                token: None,
+                member,
+                role,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        Ok(())
+    }
+
+    /// Removes a member from the club of the logged-in user
+    pub async fn revoke_member(&self, member: String) -> Result<()> {
+        debug!("Attempt to revoke membership of user {}", member);
+        let _response = self
+            .post(server_url!("user/members/revoke"))
+            .json(&RevokeMemberRequest {
+                token: None,
+                member,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
 mod auth;
+mod manage;
+mod member;
+mod session;
 mod update;
 
 pub use auth::*;
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+use becks_convey::user::session::*;
+
+impl Login {
+    /// Lists the sessions of the logged-in user on all devices
+    pub async fn sessions(&self) -> Result<Vec<SessionInfo>> {
+        debug!("Attempt to list sessions");
+        let response = self
+            .get(server_url!("user/sessions"))
+            .json(&SessionsRequest { token: None })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: SessionsResponse = response.json().await?;
+        Ok(response.sessions)
+    }
+
+    /// Revokes a session of the logged-in user without affecting the others
+    pub async fn revoke_session(&self, session: String) -> Result<()> {
+        debug!("Attempt to revoke session {}", session);
+        let _response = self
+            .post(server_url!("user/sessions/revoke"))
+            .json(&RevokeSessionRequest {
+                token: None,
+                session,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        Ok(())
+    }
+}
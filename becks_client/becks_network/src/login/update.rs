@@ -4,15 +4,12 @@ use std::sync::Arc;
 
 impl Login {
     pub async fn update(&self) {
-        self.client()
-            .post(server_url!("user/update"))
-            .json(&UpdateRequest {
-                token: self.token(),
-            })
+        self.post(server_url!("user/update"))
+            .json(&UpdateRequest { token: None })
             .send()
             .await
             .inspect_err(|err| {
-                error!("When updating login for {:?}, {}", self.token(), err);
+                error!("When updating login, {}", err);
             })
             .ok();
     }
@@ -16,15 +16,12 @@ impl MatchInfo {
     /// Uploads a new match to the server, returning the created match info
     pub async fn create(login: &Login, mat: Match) -> Result<Self> {
         let response = login
-            .client()
             .post(server_url!("mat/create"))
-            .json(&create::CreateRequest {
-                token: login.token(),
-                mat,
-            })
+            .json(&create::CreateRequest { token: None, mat })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         let response: create::CreateResponse = response.json().await?;
         Ok(Self {
             id: response.mat,
@@ -35,15 +32,14 @@ impl MatchInfo {
     /// Forces to reload match data from the server
     pub async fn reload(&mut self, login: &Login) -> Result<&mut Match> {
         let response = login
-            .client()
             .get(server_url!("mat/acquire"))
             .json(&acquire::AcquireRequest {
-                token: login.token(),
+                token: None,
                 mat: self.id,
             })
             .send()
             .await?;
-        let response = response.error_for_status()?;
+        let response = response.check().await?;
         let response: acquire::AcquireResponse = response.json().await?;
         Ok(self.data.insert(response.mat))
     }
@@ -68,19 +64,42 @@ impl MatchInfo {
         self.data = None;
     }
 
-    pub async fn modify(&mut self, login: &Login, notes: String) -> Result<()> {
-        let _response = login
-            .client()
+    /// Modifies the given fields of the match, returning the crews whose score changes
+    pub async fn modify(
+        &mut self,
+        login: &Login,
+        modify: modify::MatchModification,
+    ) -> Result<Vec<modify::ScoreDiff>> {
+        let response = login
             .post(server_url!("mat/modify"))
             .json(&modify::ModifyRequest {
-                token: login.token(),
+                token: None,
                 mat: self.id(),
-                notes,
+                modify,
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
+        let response: modify::ModifyResponse = response.json().await?;
+        self.unload();
+        Ok(response.diffs)
+    }
+
+    /// Voids the match, returning the crews whose score changes as a result
+    pub async fn void(&mut self, login: &Login) -> Result<Vec<void::ScoreDiff>> {
+        let response = login
+            .post(server_url!("mat/void"))
+            .json(&void::VoidRequest {
+                token: None,
+                mat: self.id(),
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: void::VoidResponse = response.json().await?;
         self.unload();
-        Ok(())
+        Ok(response.diffs)
     }
 }
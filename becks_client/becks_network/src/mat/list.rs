@@ -1,7 +1,10 @@
 use super::data::*;
+use crate::error::{ApiError, ErrorCode};
 use crate::prelude::*;
 use becks_convey::mat::*;
+use becks_convey::page::{PageInfo, PageRequest};
 use becks_match::*;
+use std::collections::HashSet;
 use std::future::Future;
 use std::ops::Deref;
 use tokio::sync::RwLock;
@@ -15,6 +18,9 @@ impl crate::util::GetId for RwLock<MatchInfo> {
 #[derive(Debug, Default)]
 pub struct MatchList {
     list: Vec<RwLock<MatchInfo>>,
+    /// The conditions of the query, or None if the list is not queried
+    by: Option<Vec<query::QueryMatchBy>>,
+    page: PageInfo,
 }
 
 impl Deref for MatchList {
@@ -25,18 +31,31 @@ impl Deref for MatchList {
 }
 
 impl MatchList {
-    /// Queries the match list and returns it
+    /// Queries the first page of the match list and returns it
     pub async fn query(login: &Login, by: Vec<query::QueryMatchBy>) -> Result<Self> {
+        Self::query_page(login, by, 0).await
+    }
+
+    async fn query_page(
+        login: &Login,
+        by: Vec<query::QueryMatchBy>,
+        offset: usize,
+    ) -> Result<Self> {
         let response = login
-            .client()
             .get(server_url!("mat/query"))
             .json(&query::QueryRequest {
-                token: login.token(),
-                by,
+                token: None,
+                by: by.clone(),
+                include_voided: false,
+                page: PageRequest {
+                    offset,
+                    limit: None,
+                },
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         let response: query::QueryResponse = response.json().await?;
         let result = Self {
             list: response
@@ -44,10 +63,47 @@ impl MatchList {
                 .into_iter()
                 .map(|id| RwLock::new(MatchInfo::new(id)))
                 .collect(),
+            by: Some(by),
+            page: response.page,
         };
         Ok(result)
     }
 
+    /// Returns whether more matches of the query are left on the server
+    pub fn has_more(&self) -> bool {
+        self.by.is_some() && self.page.next.is_some()
+    }
+
+    /// Returns the number of all matches found by the query, including those not yet in the list
+    pub fn total(&self) -> usize {
+        self.page.total.max(self.list.len())
+    }
+
+    /// Queries the page after this list, which can be appended to it
+    pub async fn next_page(&self, login: &Login) -> Result<Option<Self>> {
+        match (&self.by, self.page.next) {
+            (Some(by), Some(next)) => Ok(Some(Self::query_page(login, by.clone(), next).await?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Appends a page given by [`Self::next_page`]
+    pub fn append(&mut self, page: Self) {
+        self.list.extend(page.list);
+        self.page = page.page;
+    }
+
+    /// Loads the next page of the query into this list, returns false if no more are left
+    pub async fn load_more(&mut self, login: &Login) -> Result<bool> {
+        match self.next_page(login).await? {
+            Some(page) => {
+                self.append(page);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
     /// Asynchronously sorts the list by the value provided by the function
     pub async fn sort_by_value<F, R, T>(&mut self, f: F) -> Result<()>
     where
@@ -58,14 +114,60 @@ impl MatchList {
         crate::util::sort_by_value(&mut self.list, f).await
     }
 
-    /// Forces reload all matches from the server
+    /// Forces reload all matches from the server in one request
     pub async fn reload(&self, login: &Login) -> Result<()> {
-        let mut futures = Vec::new();
-        for mat in self.list.iter() {
-            futures.push(async { mat.write().await.reload(login).await.map(|_| ()) });
+        let mut ids = Vec::new();
+        for item in self.list.iter() {
+            ids.push(item.read().await.id());
+        }
+        self.acquire(login, ids).await
+    }
+
+    /// Loads the matches that are not previously loaded in one request
+    pub async fn load(&self, login: &Login) -> Result<()> {
+        let mut ids = Vec::new();
+        for item in self.list.iter() {
+            let item = item.read().await;
+            if item.data.is_none() {
+                ids.push(item.id());
+            }
+        }
+        self.acquire(login, ids).await
+    }
+
+    /// Acquires the data of given ids, and stores it to every match of these ids
+    async fn acquire(&self, login: &Login, ids: Vec<Id>) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let requested = ids.iter().copied().collect::<HashSet<_>>();
+        let response = login
+            .get(server_url!("mat/acquire_many"))
+            .json(&acquire::AcquireManyRequest {
+                token: None,
+                mats: ids,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: acquire::AcquireManyResponse = response.json().await?;
+        let mut missing = 0;
+        for item in self.list.iter() {
+            let mut item = item.write().await;
+            if requested.contains(&item.id()) {
+                item.data = response.mats.get(&item.id()).cloned();
+                if item.data.is_none() {
+                    missing += 1;
+                }
+            }
         }
-        for future in futures.into_iter() {
-            future.await?;
+        if missing > 0 {
+            return Err(ApiError::new(
+                ErrorCode::MatchNotFound,
+                format!("{} of the matches are not found", missing),
+            )
+            .into());
         }
         Ok(())
     }
@@ -18,16 +18,16 @@ impl PosterInfo {
     /// Only the value data is required, and compilation will be done at the server-side
     pub async fn create(login: &Login, value: String, images: Vec<String>) -> Result<Self> {
         let response = login
-            .client()
             .post(server_url!("poster/create"))
             .json(&create::CreateRequest {
-                token: login.token(),
+                token: None,
                 value,
                 images,
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         let response: create::CreateResponse = response.json().await?;
         Ok(Self {
             id: response.poster,
@@ -38,15 +38,15 @@ impl PosterInfo {
     /// Forces to reload poster data from the server
     pub async fn reload(&mut self, login: &Login) -> Result<&mut Poster> {
         let response = login
-            .client()
             .get(server_url!("poster/acquire"))
             .json(&acquire::AcquireRequest {
-                token: login.token(),
+                token: None,
                 poster: self.id(),
             })
             .send()
             .await?
-            .error_for_status()?;
+            .check()
+            .await?;
         let response: acquire::AcquireResponse = response.json().await?;
         Ok(self.data.insert(response.poster))
     }
@@ -1,7 +1,6 @@
 pub(crate) use crate::config::*;
+pub(crate) use crate::error::CheckResponse;
 pub(crate) use crate::login::*;
 pub(crate) use crate::server_url;
 pub(crate) use anyhow::Result;
-pub(crate) use log::{debug, error, info, trace, warn};
-pub(crate) use reqwest::StatusCode;
-pub(crate) use thiserror::Error;
+pub(crate) use log::{debug, error, trace, warn};
@@ -0,0 +1,112 @@
+use crate::prelude::*;
+use becks_arrange::*;
+use becks_convey::swiss::*;
+
+#[derive(Debug, Clone)]
+pub struct SwissInfo {
+    pub id: Id,
+    pub data: Option<Swiss>,
+}
+
+impl SwissInfo {
+    pub fn new(id: Id) -> Self {
+        Self { id, data: None }
+    }
+
+    /// Creates a Swiss event of the crews on the server, which pairs its first round
+    /// avoiding crews that have met since the timestamp, returning the created event info
+    pub async fn create(login: &Login, crews: Vec<Id>, since: u64) -> Result<Self> {
+        let response = login
+            .post(server_url!("swiss/create"))
+            .json(&create::CreateRequest {
+                token: None,
+                crews,
+                since,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: create::CreateResponse = response.json().await?;
+        Ok(Self::new(response.swiss))
+    }
+
+    /// Lists the open Swiss events from the latest one
+    pub async fn list_open(login: &Login) -> Result<Vec<Self>> {
+        let response = login
+            .get(server_url!("swiss/list"))
+            .json(&list::ListRequest {
+                token: None,
+                closed: false,
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: list::ListResponse = response.json().await?;
+        Ok(response.swiss.into_iter().map(Self::new).collect())
+    }
+
+    /// Forces to reload Swiss data from the server, where the played pairings are linked
+    pub async fn reload(&mut self, login: &Login) -> Result<&mut Swiss> {
+        let response = login
+            .get(server_url!("swiss/acquire"))
+            .json(&acquire::AcquireRequest {
+                token: None,
+                swiss: self.id(),
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: acquire::AcquireResponse = response.json().await?;
+        Ok(self.data.insert(response.swiss))
+    }
+
+    /// Pairs the next round on the server once the latest round is done
+    pub async fn next_round(&mut self, login: &Login) -> Result<&mut Swiss> {
+        let response = login
+            .post(server_url!("swiss/next"))
+            .json(&next::NextRequest {
+                token: None,
+                swiss: self.id(),
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: next::NextResponse = response.json().await?;
+        Ok(self.data.insert(response.swiss))
+    }
+
+    /// Closes the Swiss event on the server, keeping its final state
+    pub async fn close(&mut self, login: &Login) -> Result<&mut Swiss> {
+        let response = login
+            .post(server_url!("swiss/close"))
+            .json(&close::CloseRequest {
+                token: None,
+                swiss: self.id(),
+            })
+            .send()
+            .await?
+            .check()
+            .await?;
+        let response: close::CloseResponse = response.json().await?;
+        Ok(self.data.insert(response.swiss))
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Loads Swiss data if not previously loaded, then returns it
+    pub async fn load(&mut self, login: &Login) -> Result<&mut Swiss> {
+        if self.data.is_none() {
+            self.reload(login).await?;
+        }
+        Ok(self
+            .data
+            .as_mut()
+            .expect("Swiss data should be loaded after check"))
+    }
+}
@@ -0,0 +1,4 @@
+mod data;
+
+pub use becks_convey::swiss as methods;
+pub use data::*;
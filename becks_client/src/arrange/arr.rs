@@ -31,6 +31,8 @@ impl PartialOrd for ArrangeItem {
 pub struct Arranger {
     ids: Vec<ArrangeItem>,
     group_size: usize,
+    /// The number of tables each group plays on, where 0 is not limited
+    tables: usize,
     pub groups: Vec<super::Group>,
 }
 
@@ -51,12 +53,17 @@ where
 }
 
 impl Arranger {
-    pub fn new(ids: impl IntoIterator<Item = ArrangeItem>, group_size: usize) -> Self {
+    pub fn new(
+        ids: impl IntoIterator<Item = ArrangeItem>,
+        group_size: usize,
+        tables: usize,
+    ) -> Self {
         let ids = ids.into_iter().collect::<Vec<_>>();
         let group_size = group_size.clamp(1, ids.len().max(1));
         Self {
             ids,
             group_size,
+            tables,
             groups: Default::default(),
         }
     }
@@ -91,6 +98,7 @@ impl Arranger {
         }
 
         for group in self.groups.iter_mut() {
+            group.tables = self.tables;
             group.arrange();
         }
     }
@@ -1,4 +1,3 @@
 mod arr;
-mod group;
 pub use arr::*;
-pub use group::*;
+pub use becks_arrange::{Arrangement, Group, Swiss};
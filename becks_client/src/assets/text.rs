@@ -1,3 +1,4 @@
+use becks_network::error::ErrorCode;
 use log::error;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -46,6 +47,26 @@ impl Text {
             .map(|s| s.as_ref())
             .unwrap_or_else(|| "<MISSING-TEXT>")
     }
+
+    /// Returns the text of an error code given by the server, such as `error_invalid_token`,
+    /// or the fallback text if no code is given or the code has no text
+    pub fn get_error(&self, code: Option<ErrorCode>, fallback: &str) -> &str {
+        code.and_then(|code| self.map.get(&error_key(code)))
+            .map(|s| s.as_ref())
+            .unwrap_or_else(|| self.get(fallback))
+    }
+}
+
+/// Converts the name of the code to the snake-cased key of its text
+fn error_key(code: ErrorCode) -> String {
+    let mut key = String::from("error");
+    for c in format!("{:?}", code).chars() {
+        if c.is_ascii_uppercase() {
+            key.push('_');
+        }
+        key.push(c.to_ascii_lowercase());
+    }
+    key
 }
 
 pub static TEXT: LazyLock<Text> =
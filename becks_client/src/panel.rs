@@ -60,6 +60,7 @@ pub enum MainMessage {
     CrewQueryMessage(crew_query::CrewQueryMessage),
     CrewInfoMessage(crew_info::CrewInfoMessage),
     CrewCreateMessage(crew_create::CrewCreateMessage),
+    CrewLeaderboardMessage(crew_leaderboard::CrewLeaderboardMessage),
     MatMessage(mat_panel::MatMessage),
     MatCreateMessage(mat_create::MatCreateMessage),
     MatArrangeMessage(mat_arrange::MatArrangeMessage),
@@ -6,6 +6,7 @@ pub struct CrewCreatePanel {
     name: String,
     social: Option<Social>,
     error: bool,
+    error_code: Option<error::ErrorCode>,
     // Errors cause by mistakes in input
     local_error: bool,
 }
@@ -13,7 +14,7 @@ pub struct CrewCreatePanel {
 #[derive(Debug, Clone)]
 pub enum CrewCreateMessage {
     Start,
-    Error,
+    Error(Option<error::ErrorCode>),
     LocalError,
     UpdateName(String),
     UpdateSocial(Social),
@@ -40,13 +41,16 @@ impl Panel for CrewCreatePanel {
                             ))),
                             Err(err) => {
                                 warn!("When creating crew, {}", err);
-                                MainMessage::CrewCreateMessage(CrewCreateMessage::Error)
+                                MainMessage::CrewCreateMessage(CrewCreateMessage::Error(
+                                    error::error_code(&err),
+                                ))
                             }
                         },
                     )
                 }
-                CrewCreateMessage::Error => {
+                CrewCreateMessage::Error(code) => {
                     self.error = true;
+                    self.error_code = code;
                     Task::none()
                 }
                 CrewCreateMessage::LocalError => {
@@ -94,7 +98,10 @@ impl Panel for CrewCreatePanel {
             ]
         ]
         .push_maybe(if self.error {
-            Some(widget::text(assets::TEXT.get("crew_create_error")).style(widget::text::danger))
+            Some(
+                widget::text(assets::TEXT.get_error(self.error_code, "crew_create_error"))
+                    .style(widget::text::danger),
+            )
         } else {
             None
         })
@@ -1,5 +1,6 @@
 use crate::prelude::*;
 use becks_crew::*;
+use becks_match::{CrewStats, Record};
 use crew_repr::Brand;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -10,6 +11,11 @@ pub struct CrewInfoPanel {
     id: Option<Id>,
     crew_data: Option<CrewData>,
     mat: Option<mat_panel::MatPanel>,
+    stats: Option<CrewStats>,
+    history: Option<score_chart::ScoreChart>,
+    /// The opponent of the head-to-head record, with its name once loaded
+    opponent: Option<(Id, Option<String>)>,
+    opponent_selection: Option<crew_query::CrewQueryPanel>,
     error: bool,
     score_apply_confirm: bool,
     delete_confirm: bool,
@@ -20,6 +26,11 @@ pub enum CrewInfoMessage {
     Load,
     Loaded(Acquire<(Id, CrewData, Option<mat::MatchList>)>),
     LoadError,
+    LoadStats,
+    StatsLoaded(Acquire<(CrewStats, Option<String>)>),
+    StartSelectOpponent,
+    LoadHistory,
+    HistoryLoaded(Acquire<score_chart::ScoreChart>),
     Update(CrewLocation),
     ScoreApplyConfirm,
     DeleteConfirm,
@@ -33,6 +44,10 @@ impl CrewInfoPanel {
             id: None,
             crew_data: None,
             mat: None,
+            stats: None,
+            history: None,
+            opponent: None,
+            opponent_selection: None,
             error: false,
             score_apply_confirm: false,
             delete_confirm: false,
@@ -146,11 +161,82 @@ impl Panel for CrewInfoPanel {
                             self.mat = Some(mat_panel::MatPanel::new(mat, Some(id)));
                         }
                         self.error = false;
-                        Task::done(MainMessage::MatMessage(mat_panel::MatMessage::Load))
+                        Task::batch([
+                            Task::done(MainMessage::MatMessage(mat_panel::MatMessage::Load)),
+                            Task::done(MainMessage::CrewInfoMessage(CrewInfoMessage::LoadStats)),
+                            Task::done(MainMessage::CrewInfoMessage(CrewInfoMessage::LoadHistory)),
+                        ])
                     } else {
                         Task::none()
                     }
                 }
+                CrewInfoMessage::LoadStats => {
+                    let crew = self.crew.clone();
+                    let opponent = self.opponent.as_ref().map(|(id, _)| *id);
+                    Task::perform(
+                        async move {
+                            let stats = crew.lock().await.stats(login.as_ref(), opponent).await?;
+                            let name = if let Some(opponent) = opponent {
+                                Some(
+                                    crew::CrewInfo::new(opponent)
+                                        .load(login.as_ref())
+                                        .await?
+                                        .name
+                                        .clone(),
+                                )
+                            } else {
+                                None
+                            };
+                            Result::<_>::Ok((stats, name))
+                        },
+                        |result| match result {
+                            Ok(stats) => MainMessage::CrewInfoMessage(
+                                CrewInfoMessage::StatsLoaded(Acquire::new(stats)),
+                            ),
+                            Err(err) => {
+                                warn!("When loading crew stats, {}", err);
+                                MainMessage::CrewInfoMessage(CrewInfoMessage::LoadError)
+                            }
+                        },
+                    )
+                }
+                CrewInfoMessage::StatsLoaded(stats) => {
+                    if let Some((stats, name)) = stats.try_acquire() {
+                        self.stats = Some(stats);
+                        if let Some((_, opponent_name)) = self.opponent.as_mut() {
+                            *opponent_name = name;
+                        }
+                    }
+                    Task::none()
+                }
+                CrewInfoMessage::LoadHistory => {
+                    let crew = self.crew.clone();
+                    Task::perform(
+                        async move { crew.lock().await.history(login.as_ref()).await },
+                        |result| match result {
+                            Ok((initial, history)) => {
+                                MainMessage::CrewInfoMessage(CrewInfoMessage::HistoryLoaded(
+                                    Acquire::new(score_chart::ScoreChart::new(initial, history)),
+                                ))
+                            }
+                            Err(err) => {
+                                warn!("When loading crew history, {}", err);
+                                MainMessage::CrewInfoMessage(CrewInfoMessage::LoadError)
+                            }
+                        },
+                    )
+                }
+                CrewInfoMessage::HistoryLoaded(history) => {
+                    if let Some(history) = history.try_acquire() {
+                        self.history = Some(history);
+                    }
+                    Task::none()
+                }
+                CrewInfoMessage::StartSelectOpponent => {
+                    self.opponent_selection =
+                        Some(crew_query::CrewQueryPanel::default().select_only());
+                    Task::none()
+                }
                 CrewInfoMessage::Update(loc) => {
                     let crew = self.crew.clone();
                     Task::perform(
@@ -190,7 +276,19 @@ impl Panel for CrewInfoPanel {
                 }
             },
             _ => {
-                if let Some(mat) = self.mat.as_mut() {
+                if let Some(selection) = self.opponent_selection.as_mut() {
+                    let task = selection.update_with_login(login, message);
+                    let opponent = selection.selection().iter().next().copied();
+                    if let Some(opponent) = opponent {
+                        self.opponent_selection = None;
+                        self.opponent = Some((opponent, None));
+                        task.chain(Task::done(MainMessage::CrewInfoMessage(
+                            CrewInfoMessage::LoadStats,
+                        )))
+                    } else {
+                        task
+                    }
+                } else if let Some(mat) = self.mat.as_mut() {
                     mat.update_with_login(login, message)
                 } else {
                     Task::none()
@@ -360,6 +458,35 @@ impl Panel for CrewInfoPanel {
                 );
             }
 
+            if let Some(stats) = self.stats.as_ref() {
+                column.push(
+                    widget::container(view_stats(
+                        stats,
+                        self.opponent.as_ref().and_then(|(_, name)| name.as_deref()),
+                    ))
+                    .style(widget::container::rounded_box)
+                    .into(),
+                );
+            }
+            if let Some(history) = self.history.as_ref() {
+                column.push(
+                    widget::container(widget::column![
+                        widget::text(assets::TEXT.get("crew_info_history_title")),
+                        history.view(),
+                    ])
+                    .style(widget::container::rounded_box)
+                    .into(),
+                );
+            }
+            if let Some(selection) = self.opponent_selection.as_ref() {
+                column.push(
+                    widget::container(selection.view())
+                        .width(iced::Fill)
+                        .style(widget::container::rounded_box)
+                        .into(),
+                );
+            }
+
             if let Some(mat) = self.mat.as_ref() {
                 column.push(
                     widget::container(mat.view())
@@ -440,3 +567,66 @@ fn view_beat(beat: &Beat) -> Element<MainMessage> {
     }
     widget::Column::from_iter(column).spacing(5).into()
 }
+
+fn view_record(record: &Record) -> Element<MainMessage> {
+    widget::text(format!(
+        "{} {} / {} {} ({}: {} / {})",
+        record.wins,
+        assets::TEXT.get("crew_info_stats_wins"),
+        record.losses,
+        assets::TEXT.get("crew_info_stats_losses"),
+        assets::TEXT.get("crew_info_stats_rounds"),
+        record.rounds_won,
+        record.rounds_lost,
+    ))
+    .into()
+}
+
+fn view_stats<'a>(stats: &'a CrewStats, opponent: Option<&'a str>) -> Element<'a, MainMessage> {
+    let percent = |rate: Option<f64>| {
+        rate.map_or_else(|| "-".to_owned(), |rate| format!("{:.1}%", rate * 100.0))
+    };
+    widget::column![
+        widget::text(assets::TEXT.get("crew_info_stats_title")),
+        view_data("crew_info_stats_record", view_record(&stats.record)),
+        view_data(
+            "crew_info_stats_win_rate",
+            widget::text(percent(stats.record.win_rate())),
+        ),
+        view_data(
+            "crew_info_stats_streak",
+            widget::text(format!(
+                "{} / {}",
+                stats.current_streak, stats.longest_streak
+            )),
+        ),
+        view_data(
+            "crew_info_stats_opponent_score",
+            widget::text(
+                stats
+                    .average_opponent_score
+                    .map_or_else(|| "-".to_owned(), |score| format!("{:.0}", score)),
+            ),
+        ),
+        widget::row![
+            widget::button(assets::TEXT.get("crew_info_stats_head_to_head")).on_press(
+                MainMessage::CrewInfoMessage(CrewInfoMessage::StartSelectOpponent)
+            ),
+            widget::horizontal_space(),
+        ]
+        .push_maybe(
+            opponent
+                .zip(stats.head_to_head.as_ref())
+                .map(|(opponent, record)| {
+                    widget::row![
+                        widget::text(format!("{} {}", assets::TEXT.get("vs"), opponent)),
+                        view_record(record),
+                        widget::text(percent(record.win_rate())),
+                    ]
+                    .spacing(10)
+                })
+        ),
+    ]
+    .spacing(5)
+    .into()
+}
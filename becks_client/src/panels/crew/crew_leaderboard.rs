@@ -0,0 +1,199 @@
+use crate::prelude::*;
+use becks_crew::*;
+use crew::methods::leaderboard::{LeaderboardFilter, LeaderboardRow};
+
+#[derive(Debug, Default)]
+pub struct CrewLeaderboardPanel {
+    filter: LeaderboardFilter,
+    rows: Vec<LeaderboardRow>,
+    error: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum CrewLeaderboardMessage {
+    Load,
+    Loaded(Acquire<Vec<LeaderboardRow>>),
+    LoadError,
+    UpdateSocial(Option<Social>),
+    UpdateGender(Option<Gender>),
+    UpdateHand(Option<Hand>),
+    UpdateMinPlayed(usize),
+}
+
+impl Panel for CrewLeaderboardPanel {
+    fn update_with_login(&mut self, login: Arc<Login>, message: MainMessage) -> Task<MainMessage> {
+        if let MainMessage::CrewLeaderboardMessage(message) = message {
+            match message {
+                CrewLeaderboardMessage::Load => {
+                    let filter = self.filter.clone();
+                    Task::perform(
+                        async move { crew::leaderboard(login.as_ref(), filter).await },
+                        |result| match result {
+                            Ok(rows) => MainMessage::CrewLeaderboardMessage(
+                                CrewLeaderboardMessage::Loaded(Acquire::new(rows)),
+                            ),
+                            Err(err) => {
+                                error!("When loading leaderboard, {}", err);
+                                MainMessage::CrewLeaderboardMessage(
+                                    CrewLeaderboardMessage::LoadError,
+                                )
+                            }
+                        },
+                    )
+                }
+                CrewLeaderboardMessage::Loaded(rows) => {
+                    if let Some(rows) = rows.try_acquire() {
+                        self.rows = rows;
+                        self.error = false;
+                    }
+                    Task::none()
+                }
+                CrewLeaderboardMessage::LoadError => {
+                    self.error = true;
+                    Task::none()
+                }
+                CrewLeaderboardMessage::UpdateSocial(social) => {
+                    self.filter.social = social;
+                    reload()
+                }
+                CrewLeaderboardMessage::UpdateGender(gender) => {
+                    self.filter.gender = gender;
+                    reload()
+                }
+                CrewLeaderboardMessage::UpdateHand(hand) => {
+                    self.filter.hand = hand;
+                    reload()
+                }
+                CrewLeaderboardMessage::UpdateMinPlayed(min_played) => {
+                    self.filter.min_played = min_played;
+                    reload()
+                }
+            }
+        } else {
+            Task::none()
+        }
+    }
+
+    fn view(&self) -> Element<MainMessage> {
+        use iced_aw::{grid, grid_row};
+        let filters = widget::row![
+            pick_filter(self.filter.social, |social| {
+                MainMessage::CrewLeaderboardMessage(CrewLeaderboardMessage::UpdateSocial(social))
+            }),
+            pick_filter(self.filter.gender, |gender| {
+                MainMessage::CrewLeaderboardMessage(CrewLeaderboardMessage::UpdateGender(gender))
+            }),
+            pick_filter(self.filter.hand, |hand| {
+                MainMessage::CrewLeaderboardMessage(CrewLeaderboardMessage::UpdateHand(hand))
+            }),
+            widget::text_input(
+                assets::TEXT.get("crew_leaderboard_min_played"),
+                &if self.filter.min_played == 0 {
+                    "".to_owned()
+                } else {
+                    self.filter.min_played.to_string()
+                },
+            )
+            .width(150)
+            .on_input(|value| {
+                if value.is_empty() {
+                    MainMessage::CrewLeaderboardMessage(CrewLeaderboardMessage::UpdateMinPlayed(0))
+                } else {
+                    match value.parse() {
+                        Ok(value) => MainMessage::CrewLeaderboardMessage(
+                            CrewLeaderboardMessage::UpdateMinPlayed(value),
+                        ),
+                        Err(_) => MainMessage::None,
+                    }
+                }
+            }),
+        ]
+        .spacing(10);
+
+        let base_row: Vec<Element<MainMessage>> = vec![
+            widget::text(assets::TEXT.get("crew_leaderboard_rank")).into(),
+            widget::text(assets::TEXT.get("crew_leaderboard_name")).into(),
+            widget::text(assets::TEXT.get("crew_leaderboard_score")).into(),
+            widget::text(assets::TEXT.get("crew_leaderboard_played")).into(),
+        ];
+        let mut rows = vec![grid_row(base_row)];
+        for row in self.rows.iter() {
+            let crew = row.crew;
+            let row: Vec<Element<MainMessage>> = vec![
+                widget::text(row.rank.to_string()).into(),
+                widget::button(widget::text(&row.name))
+                    .style(widget::button::text)
+                    .on_press_with(move || {
+                        MainMessage::Open(Acquire::new(PanelHandle::new(
+                            crew_info::CrewInfoPanel::new(crew),
+                        )))
+                    })
+                    .into(),
+                widget::text(row.score.0.to_string()).into(),
+                widget::text(row.played.to_string()).into(),
+            ];
+            rows.push(grid_row(row));
+        }
+
+        let mut column: Vec<Element<MainMessage>> = vec![
+            widget::text(assets::TEXT.get("crew_leaderboard_title")).into(),
+            filters.into(),
+        ];
+        if self.error {
+            column.push(
+                widget::text(assets::TEXT.get("crew_leaderboard_error"))
+                    .style(widget::text::danger)
+                    .into(),
+            );
+        }
+        column.push(
+            widget::scrollable(grid(rows).row_spacing(5).column_spacing(20))
+                .height(iced::Fill)
+                .into(),
+        );
+        widget::Column::from_iter(column)
+            .spacing(10)
+            .padding(10)
+            .into()
+    }
+
+    fn on_start_up(&mut self) -> Task<MainMessage> {
+        reload()
+    }
+
+    fn on_rewind_to(&mut self) -> Task<MainMessage> {
+        // Scores may have changed in the panels opened from here
+        reload()
+    }
+}
+
+fn reload() -> Task<MainMessage> {
+    Task::done(MainMessage::CrewLeaderboardMessage(
+        CrewLeaderboardMessage::Load,
+    ))
+}
+
+/// A pick list of the values, with an extra choice that matches any value
+fn pick_filter<T>(
+    value: Option<T>,
+    on_pick: impl Fn(Option<T>) -> MainMessage + 'static,
+) -> Element<'static, MainMessage>
+where
+    T: Repr + Copy,
+{
+    let any = assets::TEXT.get("crew_leaderboard_any");
+    widget::pick_list(
+        std::iter::once(any)
+            .chain(T::all_repred())
+            .collect::<Vec<_>>(),
+        Some(value.map_or(any, |value| value.repr())),
+        move |choice| {
+            if choice == any {
+                on_pick(None)
+            } else {
+                on_pick(Some(*T::unrepr(choice)))
+            }
+        },
+    )
+    .into()
+}
@@ -20,6 +20,8 @@ pub enum CrewMessage {
     Loaded(Acquire<Vec<(Id, CrewData)>>),
     Select(Id),
     SelectAll,
+    LoadMore,
+    MoreLoaded(Acquire<crew::CrewList>),
 }
 
 impl CrewPanel {
@@ -58,6 +60,9 @@ impl Panel for CrewPanel {
                     let crew = self.crew.clone();
                     Task::perform(
                         async move {
+                            if let Err(err) = crew.load(login.as_ref()).await {
+                                warn!("When loading crew list at once, {}", err);
+                            }
                             let mut crew_list = Vec::new();
                             for crew in crew.iter() {
                                 let id = crew.read().await.id();
@@ -104,6 +109,36 @@ impl Panel for CrewPanel {
                     }
                     Task::none()
                 }
+                CrewMessage::LoadMore => {
+                    let crew = self.crew.clone();
+                    Task::perform(
+                        async move { crew.next_page(login.as_ref()).await },
+                        |result| match result {
+                            Ok(Some(page)) => MainMessage::CrewMessage(CrewMessage::MoreLoaded(
+                                Acquire::new(page),
+                            )),
+                            Ok(None) => MainMessage::None,
+                            Err(err) => {
+                                error!("When loading more crew, {}", err);
+                                MainMessage::None
+                            }
+                        },
+                    )
+                }
+                CrewMessage::MoreLoaded(page) => {
+                    if let Some(page) = page.try_acquire() {
+                        // Tasks holding the list are done by now, so it is rarely shared
+                        if let Some(crew) = Arc::get_mut(&mut self.crew) {
+                            crew.append(page);
+                            Task::done(MainMessage::CrewMessage(CrewMessage::Load))
+                        } else {
+                            warn!("The crew list is in use, so its next page is dropped");
+                            Task::none()
+                        }
+                    } else {
+                        Task::none()
+                    }
+                }
                 CrewMessage::Select(id) => {
                     let mut selected = self.selected.lock().unwrap();
                     if !selected.insert(id) {
@@ -149,6 +184,13 @@ impl Panel for CrewPanel {
                     ));
                     column.push(widget::Rule::horizontal(1).into());
                 }
+                if self.crew.has_more() {
+                    column.push(
+                        widget::button(assets::TEXT.get("crew_load_more"))
+                            .on_press(MainMessage::CrewMessage(CrewMessage::LoadMore))
+                            .into(),
+                    );
+                }
                 widget::Column::from_iter(column).into()
             }
         } else {
@@ -1,4 +1,6 @@
 pub mod crew_create;
 pub mod crew_info;
+pub mod crew_leaderboard;
 pub mod crew_panel;
 pub mod crew_query;
+pub mod score_chart;
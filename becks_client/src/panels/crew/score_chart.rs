@@ -0,0 +1,123 @@
+use crate::prelude::*;
+use becks_match::ScorePoint;
+use iced::widget::canvas::{self, Frame, Path, Stroke, Text};
+use iced::{mouse, Point, Rectangle, Renderer, Theme};
+
+/// Space around the plot, leaving room for the score labels
+const MARGIN: f32 = 30.0;
+
+/// A line chart of the score of a crew after each match it plays, over time
+#[derive(Debug, Clone)]
+pub struct ScoreChart {
+    initial: i32,
+    history: Vec<ScorePoint>,
+}
+
+impl ScoreChart {
+    pub fn new(initial: i32, history: Vec<ScorePoint>) -> Self {
+        Self { initial, history }
+    }
+
+    pub fn view(&self) -> Element<MainMessage> {
+        widget::canvas(self).width(iced::Fill).height(200).into()
+    }
+
+    /// Returns the points to plot from the initial score, where x is a ratio of the time span
+    /// and matches are spread evenly if they are all played at the same time
+    fn points(&self) -> Vec<(f32, i32)> {
+        let first = self.history.first().map_or(0, |point| point.timestamp);
+        let last = self.history.last().map_or(0, |point| point.timestamp);
+        let span = last.saturating_sub(first);
+        let count = self.history.len();
+        std::iter::once((0.0, self.initial))
+            .chain(self.history.iter().enumerate().map(|(index, point)| {
+                let x = if span == 0 {
+                    (index + 1) as f32 / count as f32
+                } else {
+                    (point.timestamp - first) as f32 / span as f32
+                };
+                (x, point.score)
+            }))
+            .collect()
+    }
+}
+
+impl canvas::Program<MainMessage> for ScoreChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = theme.palette();
+        let points = self.points();
+        let min = points
+            .iter()
+            .map(|(_, score)| *score)
+            .min()
+            .unwrap_or_default();
+        let max = points
+            .iter()
+            .map(|(_, score)| *score)
+            .max()
+            .unwrap_or_default();
+        // A flat history is drawn in the middle
+        let range = (max - min).max(1) as f32;
+        let width = (bounds.width - MARGIN * 2.0).max(1.0);
+        let height = (bounds.height - MARGIN * 2.0).max(1.0);
+        let to_point = |(x, score): (f32, i32)| {
+            let y = if max == min {
+                0.5
+            } else {
+                (score - min) as f32 / range
+            };
+            Point::new(MARGIN + x * width, MARGIN + (1.0 - y) * height)
+        };
+
+        let axes = Path::new(|builder| {
+            builder.move_to(Point::new(MARGIN, MARGIN));
+            builder.line_to(Point::new(MARGIN, MARGIN + height));
+            builder.line_to(Point::new(MARGIN + width, MARGIN + height));
+        });
+        frame.stroke(
+            &axes,
+            Stroke::default()
+                .with_color(palette.text.scale_alpha(0.5))
+                .with_width(1.0),
+        );
+        for (score, y) in [(max, MARGIN), (min, MARGIN + height)] {
+            frame.fill_text(Text {
+                content: score.to_string(),
+                position: Point::new(2.0, y - 6.0),
+                color: palette.text,
+                size: iced::Pixels(12.0),
+                ..Default::default()
+            });
+        }
+
+        let line = Path::new(|builder| {
+            for (index, point) in points.iter().copied().map(to_point).enumerate() {
+                if index == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+        frame.stroke(
+            &line,
+            Stroke::default()
+                .with_color(palette.primary)
+                .with_width(2.0),
+        );
+        for point in points.iter().copied().map(to_point) {
+            frame.fill(&Path::circle(point, 3.0), palette.primary);
+        }
+        vec![frame.into_geometry()]
+    }
+}
@@ -1,5 +1,4 @@
 use crate::prelude::*;
-use widget::{button, text};
 
 #[derive(Default, Debug)]
 pub(crate) struct LobbyPanel {
@@ -72,6 +71,11 @@ impl Panel for LobbyPanel {
                     .on_press(MainMessage::Open(Acquire::new(PanelHandle::new(
                         crew_query::CrewQueryPanel::default()
                     )))),
+                widget::button(assets::TEXT.get("lobby_leaderboard"))
+                    .style(widget::button::text)
+                    .on_press(MainMessage::Open(Acquire::new(PanelHandle::new(
+                        crew_leaderboard::CrewLeaderboardPanel::default()
+                    )))),
                 widget::button(assets::TEXT.get("lobby_poster"))
                     .style(widget::button::text)
                     .on_press(MainMessage::Open(Acquire::new(PanelHandle::new(
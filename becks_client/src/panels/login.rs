@@ -13,8 +13,8 @@ pub(crate) struct LoginPanel {
 enum ErrorStatus {
     #[default]
     None,
-    Login,
-    Create,
+    Login(Option<error::ErrorCode>),
+    Create(Option<error::ErrorCode>),
 }
 
 #[derive(Debug, Clone)]
@@ -56,7 +56,8 @@ impl Panel for LoginPanel {
                             }
                             Err(err) => {
                                 warn!("When logging in, {}", err);
-                                *error.lock().unwrap() = ErrorStatus::Login;
+                                *error.lock().unwrap() =
+                                    ErrorStatus::Login(error::error_code(&err));
                                 MainMessage::None
                             }
                         },
@@ -74,13 +75,15 @@ impl Panel for LoginPanel {
                                 }
                                 Err(err) => {
                                     warn!("When logging in after creation, {}", err);
-                                    *error.lock().unwrap() = ErrorStatus::Login;
+                                    *error.lock().unwrap() =
+                                        ErrorStatus::Login(error::error_code(&err));
                                     MainMessage::None
                                 }
                             },
                             Err(err) => {
                                 warn!("When creating user, {}", err);
-                                *error.lock().unwrap() = ErrorStatus::Create;
+                                *error.lock().unwrap() =
+                                    ErrorStatus::Create(error::error_code(&err));
                                 MainMessage::None
                             }
                         },
@@ -110,12 +113,13 @@ impl Panel for LoginPanel {
         ]
         .push_maybe(match *self.error.lock().unwrap() {
             ErrorStatus::None => None,
-            ErrorStatus::Login => {
-                Some(text(assets::TEXT.get("login_error_login")).style(widget::text::danger))
-            }
-            ErrorStatus::Create => {
-                Some(text(assets::TEXT.get("login_error_create")).style(widget::text::danger))
-            }
+            ErrorStatus::Login(code) => Some(
+                text(assets::TEXT.get_error(code, "login_error_login")).style(widget::text::danger),
+            ),
+            ErrorStatus::Create(code) => Some(
+                text(assets::TEXT.get_error(code, "login_error_create"))
+                    .style(widget::text::danger),
+            ),
         })
         .spacing(20)
         .into()
@@ -1,16 +1,30 @@
 use crate::arrange::*;
 use crate::prelude::*;
-use becks_match::*;
-use mat_panel::MatMessage;
-use std::sync::Mutex;
+use becks_crew::*;
+use std::collections::HashMap;
+
+/// How the selected crews are arranged
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ArrangeMode {
+    /// Fixed groups where everyone plays everyone else in the group
+    #[default]
+    Groups,
+    /// Rounds paired by records, where crews that have met recently are not paired again
+    Swiss,
+}
 
 #[derive(Default, Debug)]
 pub struct MatArrangePanel {
     selection: Option<crew_query::CrewQueryPanel>,
     selected: Option<Vec<Id>>,
-    arranger: Option<Mutex<Arranger>>,
-    names: Vec<Vec<String>>,
+    mode: ArrangeMode,
+    /// The open arrangement of groups, which is kept by the server
+    arrangement: Option<arrange::ArrangementInfo>,
+    /// The open Swiss event, which is kept by the server as well
+    swiss: Option<swiss::SwissInfo>,
+    names: HashMap<Id, String>,
     group_size: usize,
+    tables: usize,
     current_group: usize,
     error: bool,
     local_error: bool,
@@ -19,11 +33,21 @@ pub struct MatArrangePanel {
 #[derive(Debug, Clone)]
 pub enum MatArrangeMessage {
     StartArrange,
-    ArrangeAcquired(Acquire<Arranger>),
-    NamesAcquired(Acquire<Vec<Vec<String>>>),
+    ArrangementAcquired(Acquire<(arrange::ArrangementInfo, Arc<crew::CrewList>)>),
+    SwissAcquired(Acquire<(swiss::SwissInfo, Arc<crew::CrewList>)>),
+    NamesAcquired(Acquire<HashMap<Id, String>>),
     StartSelection,
     EndSelection,
     UpdateGroupSize(usize),
+    UpdateTables(usize),
+    UpdateMode(ArrangeMode),
+    NextRound,
+    Sync,
+    ArrangementSynced(Acquire<arrange::ArrangementInfo>),
+    SwissSynced(Acquire<swiss::SwissInfo>),
+    Reload,
+    Close,
+    Closed,
     NextGroup,
     PrevGroup,
     Error,
@@ -43,23 +67,58 @@ impl Panel for MatArrangePanel {
                         } else {
                             self.group_size
                         };
+                        let tables = self.tables;
+                        let mode = self.mode;
                         Task::perform(
                             async move {
+                                let crews = Arc::new(crew::CrewList::new(selected));
+                                crews.load(login.as_ref()).await?;
                                 let mut items = Vec::new();
-                                for id in selected.into_iter() {
-                                    let mut info = crew::CrewInfo::new(id);
-                                    let data = info.load(login.as_ref()).await?;
+                                for crew in crews.iter() {
+                                    let mut crew = crew.write().await;
+                                    let id = crew.id();
+                                    let data = crew.load(login.as_ref()).await?;
                                     items.push(ArrangeItem {
                                         id,
                                         score: data.score,
                                     });
                                 }
-                                Result::<Arranger>::Ok(Arranger::new(items, group_size))
+                                let message = match mode {
+                                    ArrangeMode::Groups => {
+                                        let mut arranger = Arranger::new(items, group_size, tables);
+                                        arranger.arrange();
+                                        let mut arrangement = arrange::ArrangementInfo::create(
+                                            login.as_ref(),
+                                            arranger.groups,
+                                        )
+                                        .await?;
+                                        arrangement.load(login.as_ref()).await?;
+                                        MatArrangeMessage::ArrangementAcquired(Acquire::new((
+                                            arrangement,
+                                            crews,
+                                        )))
+                                    }
+                                    ArrangeMode::Swiss => {
+                                        let since = current_timestamp().saturating_sub(
+                                            config::CONFIG.interact.recent.as_secs(),
+                                        );
+                                        // The server pairs the first round
+                                        let mut swiss = swiss::SwissInfo::create(
+                                            login.as_ref(),
+                                            items.iter().map(|item| item.id).collect(),
+                                            since,
+                                        )
+                                        .await?;
+                                        swiss.load(login.as_ref()).await?;
+                                        MatArrangeMessage::SwissAcquired(Acquire::new((
+                                            swiss, crews,
+                                        )))
+                                    }
+                                };
+                                Result::<_>::Ok(message)
                             },
                             |result| match result {
-                                Ok(arranger) => MainMessage::MatArrangeMessage(
-                                    MatArrangeMessage::ArrangeAcquired(Acquire::new(arranger)),
-                                ),
+                                Ok(message) => MainMessage::MatArrangeMessage(message),
                                 Err(err) => {
                                     error!("When acquiring arranger crew, {}", err);
                                     MainMessage::MatArrangeMessage(MatArrangeMessage::Error)
@@ -73,34 +132,83 @@ impl Panel for MatArrangePanel {
                         ))
                     }
                 }
-                MatArrangeMessage::ArrangeAcquired(arranger) => {
-                    if let Some(mut arranger) = arranger.try_acquire() {
-                        arranger.arrange();
-                        let groups = arranger.groups.clone();
-                        self.arranger = Some(Mutex::new(arranger));
+                MatArrangeMessage::ArrangementAcquired(arrangement) => {
+                    if let Some((arrangement, crews)) = arrangement.try_acquire() {
+                        self.arrangement = Some(arrangement);
+                        self.swiss = None;
+                        self.current_group = 0;
+                        load_names(login, crews)
+                    } else {
+                        Task::none()
+                    }
+                }
+                MatArrangeMessage::Reload => Task::perform(
+                    async move {
+                        // Only the latest open arrangement is shown, or else the latest open
+                        // Swiss event
+                        if let Some(mut arrangement) =
+                            arrange::ArrangementInfo::list_open(login.as_ref())
+                                .await?
+                                .into_iter()
+                                .next()
+                        {
+                            let crews = arrangement
+                                .load(login.as_ref())
+                                .await?
+                                .crews()
+                                .collect::<Vec<_>>();
+                            let crews = Arc::new(crew::CrewList::new(crews));
+                            crews.load(login.as_ref()).await?;
+                            return Result::<_>::Ok(Some(MatArrangeMessage::ArrangementAcquired(
+                                Acquire::new((arrangement, crews)),
+                            )));
+                        }
+                        let Some(mut swiss) = swiss::SwissInfo::list_open(login.as_ref())
+                            .await?
+                            .into_iter()
+                            .next()
+                        else {
+                            return Ok(None);
+                        };
+                        let crews = swiss
+                            .load(login.as_ref())
+                            .await?
+                            .crews()
+                            .collect::<Vec<_>>();
+                        let crews = Arc::new(crew::CrewList::new(crews));
+                        crews.load(login.as_ref()).await?;
+                        Ok(Some(MatArrangeMessage::SwissAcquired(Acquire::new((
+                            swiss, crews,
+                        )))))
+                    },
+                    |result| match result {
+                        Ok(Some(message)) => MainMessage::MatArrangeMessage(message),
+                        Ok(None) => MainMessage::None,
+                        Err(err) => {
+                            error!("When reloading arrangement, {}", err);
+                            MainMessage::MatArrangeMessage(MatArrangeMessage::Error)
+                        }
+                    },
+                ),
+                MatArrangeMessage::Close => {
+                    if let Some(mut arrangement) = self.arrangement.clone() {
                         Task::perform(
-                            async move {
-                                let mut result = Vec::new();
-                                for group in groups.iter() {
-                                    let mut group_names = Vec::new();
-                                    for id in group.all.iter().copied() {
-                                        group_names.push(std::mem::take(
-                                            &mut crew::CrewInfo::new(id)
-                                                .load(login.as_ref())
-                                                .await?
-                                                .name,
-                                        ));
-                                    }
-                                    result.push(group_names);
+                            async move { arrangement.close(login.as_ref()).await.map(|_| ()) },
+                            |result| match result {
+                                Ok(()) => MainMessage::MatArrangeMessage(MatArrangeMessage::Closed),
+                                Err(err) => {
+                                    error!("When closing arrangement, {}", err);
+                                    MainMessage::MatArrangeMessage(MatArrangeMessage::Error)
                                 }
-                                Result::<_>::Ok(result)
                             },
+                        )
+                    } else if let Some(mut swiss) = self.swiss.clone() {
+                        Task::perform(
+                            async move { swiss.close(login.as_ref()).await.map(|_| ()) },
                             |result| match result {
-                                Ok(names) => MainMessage::MatArrangeMessage(
-                                    MatArrangeMessage::NamesAcquired(Acquire::new(names)),
-                                ),
+                                Ok(()) => MainMessage::MatArrangeMessage(MatArrangeMessage::Closed),
                                 Err(err) => {
-                                    error!("When acquiring names, {}", err);
+                                    error!("When closing Swiss event, {}", err);
                                     MainMessage::MatArrangeMessage(MatArrangeMessage::Error)
                                 }
                             },
@@ -109,6 +217,41 @@ impl Panel for MatArrangePanel {
                         Task::none()
                     }
                 }
+                MatArrangeMessage::Closed => {
+                    self.arrangement = None;
+                    self.swiss = None;
+                    self.current_group = 0;
+                    Task::none()
+                }
+                MatArrangeMessage::SwissAcquired(swiss) => {
+                    if let Some((swiss, crews)) = swiss.try_acquire() {
+                        self.swiss = Some(swiss);
+                        self.arrangement = None;
+                        load_names(login, crews)
+                    } else {
+                        Task::none()
+                    }
+                }
+                MatArrangeMessage::NextRound => {
+                    let Some(mut swiss) = self.swiss.clone() else {
+                        return Task::none();
+                    };
+                    Task::perform(
+                        async move {
+                            swiss.next_round(login.as_ref()).await?;
+                            Result::<_>::Ok(swiss)
+                        },
+                        |result| match result {
+                            Ok(swiss) => MainMessage::MatArrangeMessage(
+                                MatArrangeMessage::SwissSynced(Acquire::new(swiss)),
+                            ),
+                            Err(err) => {
+                                error!("When pairing next round, {}", err);
+                                MainMessage::MatArrangeMessage(MatArrangeMessage::Error)
+                            }
+                        },
+                    )
+                }
                 MatArrangeMessage::NamesAcquired(names) => {
                     if let Some(names) = names.try_acquire() {
                         self.names = names;
@@ -133,11 +276,70 @@ impl Panel for MatArrangePanel {
                     self.group_size = group_size;
                     Task::none()
                 }
+                MatArrangeMessage::UpdateTables(tables) => {
+                    self.tables = tables;
+                    Task::none()
+                }
+                MatArrangeMessage::UpdateMode(mode) => {
+                    self.mode = mode;
+                    Task::none()
+                }
+                MatArrangeMessage::Sync => {
+                    if let Some(mut arrangement) = self.arrangement.clone() {
+                        // The server links recorded matches to the arrangement
+                        return Task::perform(
+                            async move {
+                                arrangement.reload(login.as_ref()).await?;
+                                Result::<_>::Ok(arrangement)
+                            },
+                            |result| match result {
+                                Ok(arrangement) => MainMessage::MatArrangeMessage(
+                                    MatArrangeMessage::ArrangementSynced(Acquire::new(arrangement)),
+                                ),
+                                Err(err) => {
+                                    error!("When syncing arrangement, {}", err);
+                                    MainMessage::MatArrangeMessage(MatArrangeMessage::Error)
+                                }
+                            },
+                        );
+                    }
+                    let Some(mut swiss) = self.swiss.clone() else {
+                        return Task::none();
+                    };
+                    Task::perform(
+                        async move {
+                            swiss.reload(login.as_ref()).await?;
+                            Result::<_>::Ok(swiss)
+                        },
+                        |result| match result {
+                            Ok(swiss) => MainMessage::MatArrangeMessage(
+                                MatArrangeMessage::SwissSynced(Acquire::new(swiss)),
+                            ),
+                            Err(err) => {
+                                error!("When syncing Swiss event, {}", err);
+                                MainMessage::MatArrangeMessage(MatArrangeMessage::Error)
+                            }
+                        },
+                    )
+                }
+                MatArrangeMessage::ArrangementSynced(arrangement) => {
+                    if let Some(arrangement) = arrangement.try_acquire() {
+                        self.arrangement = Some(arrangement);
+                    }
+                    Task::none()
+                }
+                MatArrangeMessage::SwissSynced(swiss) => {
+                    if let Some(swiss) = swiss.try_acquire() {
+                        self.swiss = Some(swiss);
+                    }
+                    Task::none()
+                }
                 MatArrangeMessage::NextGroup => {
                     self.current_group = (self.current_group + 1).min(
-                        self.arranger
+                        self.arrangement
                             .as_ref()
-                            .map(|arranger| arranger.lock().unwrap().groups.len().saturating_sub(1))
+                            .and_then(|arrangement| arrangement.data.as_ref())
+                            .map(|arrangement| arrangement.groups.len().saturating_sub(1))
                             .unwrap_or_default(),
                     );
                     Task::none()
@@ -166,13 +368,18 @@ impl Panel for MatArrangePanel {
     }
     fn view(&self) -> Element<MainMessage> {
         let mut sub_column: Vec<Element<MainMessage>> = Vec::new();
-        if let Some(arranger) = self.arranger.as_ref() {
-            let arranger = arranger.lock().unwrap();
-            sub_column.push(view_arranger(
-                &arranger,
-                self.names.as_slice(),
+        if let Some(arrangement) = self
+            .arrangement
+            .as_ref()
+            .and_then(|arrangement| arrangement.data.as_ref())
+        {
+            sub_column.push(view_arrangement(
+                arrangement,
+                &self.names,
                 self.current_group,
             ));
+        } else if let Some(swiss) = self.swiss.as_ref().and_then(|swiss| swiss.data.as_ref()) {
+            sub_column.push(view_swiss(swiss, &self.names));
         } else if let Some(selection) = self.selection.as_ref() {
             sub_column.push(
                 widget::button(assets::TEXT.get("mat_arrange_select_end"))
@@ -190,33 +397,74 @@ impl Panel for MatArrangePanel {
             );
         } else {
             sub_column.push(
-                widget::row![widget::text_input(
-                    assets::TEXT.get("mat_arrange_groupsize"),
-                    &if self.group_size == 0 {
-                        "".to_owned()
-                    } else {
-                        self.group_size.to_string()
-                    },
-                )
-                .on_input(|value| {
-                    MainMessage::MatArrangeMessage(MatArrangeMessage::UpdateGroupSize(
-                        if value.is_empty() {
-                            0
-                        } else {
-                            value.parse().unwrap_or(self.group_size)
-                        },
-                    ))
-                }),]
-                .push_maybe(self.selected.as_ref().map(|selection| {
-                    widget::text(format!(
-                        "{}: {}",
-                        assets::TEXT.get("mat_arrange_total"),
-                        selection.len(),
-                    ))
-                }))
+                widget::row![
+                    widget::radio(
+                        assets::TEXT.get("mat_arrange_mode_groups"),
+                        ArrangeMode::Groups,
+                        Some(self.mode),
+                        |mode| MainMessage::MatArrangeMessage(MatArrangeMessage::UpdateMode(mode)),
+                    ),
+                    widget::radio(
+                        assets::TEXT.get("mat_arrange_mode_swiss"),
+                        ArrangeMode::Swiss,
+                        Some(self.mode),
+                        |mode| MainMessage::MatArrangeMessage(MatArrangeMessage::UpdateMode(mode)),
+                    ),
+                ]
                 .spacing(20)
                 .into(),
             );
+            sub_column.push(
+                widget::Row::new()
+                    .push_maybe((self.mode == ArrangeMode::Groups).then(|| {
+                        widget::row![
+                            widget::text_input(
+                                assets::TEXT.get("mat_arrange_groupsize"),
+                                &if self.group_size == 0 {
+                                    "".to_owned()
+                                } else {
+                                    self.group_size.to_string()
+                                },
+                            )
+                            .on_input(|value| {
+                                MainMessage::MatArrangeMessage(MatArrangeMessage::UpdateGroupSize(
+                                    if value.is_empty() {
+                                        0
+                                    } else {
+                                        value.parse().unwrap_or(self.group_size)
+                                    },
+                                ))
+                            }),
+                            widget::text_input(
+                                assets::TEXT.get("mat_arrange_tables"),
+                                &if self.tables == 0 {
+                                    "".to_owned()
+                                } else {
+                                    self.tables.to_string()
+                                },
+                            )
+                            .on_input(|value| {
+                                MainMessage::MatArrangeMessage(MatArrangeMessage::UpdateTables(
+                                    if value.is_empty() {
+                                        0
+                                    } else {
+                                        value.parse().unwrap_or(self.tables)
+                                    },
+                                ))
+                            }),
+                        ]
+                        .spacing(20)
+                    }))
+                    .push_maybe(self.selected.as_ref().map(|selection| {
+                        widget::text(format!(
+                            "{}: {}",
+                            assets::TEXT.get("mat_arrange_total"),
+                            selection.len(),
+                        ))
+                    }))
+                    .spacing(20)
+                    .into(),
+            );
             sub_column.push(
                 widget::button(assets::TEXT.get("mat_arrange_select"))
                     .style(widget::button::primary)
@@ -233,7 +481,7 @@ impl Panel for MatArrangePanel {
                             .selected
                             .as_ref()
                             .is_some_and(|selected| !selected.is_empty())
-                            && self.group_size != 0
+                            && (self.mode == ArrangeMode::Swiss || self.group_size != 0)
                         {
                             Some(MainMessage::MatArrangeMessage(
                                 MatArrangeMessage::StartArrange,
@@ -268,72 +516,200 @@ impl Panel for MatArrangePanel {
         ]
         .into()
     }
+
+    fn on_start_up(&mut self) -> Task<MainMessage> {
+        // The open arrangement may have been made before the client is restarted
+        Task::done(MainMessage::MatArrangeMessage(MatArrangeMessage::Reload))
+    }
+
+    fn on_rewind_to(&mut self) -> Task<MainMessage> {
+        // A match may have been recorded from the schedule
+        Task::done(MainMessage::MatArrangeMessage(MatArrangeMessage::Sync))
+    }
 }
 
-fn view_group<'n>(group: &Group, names: &'n [String]) -> Element<'n, MainMessage> {
-    use iced_aw::{grid, grid_row, Grid};
+fn view_group<'n>(group: &Group, names: &'n HashMap<Id, String>) -> Element<'n, MainMessage> {
+    use iced_aw::{grid, grid_row};
+    let name = |id: &Id| -> Element<'n, MainMessage> {
+        widget::text(names.get(id).map(String::as_str).unwrap_or_default()).into()
+    };
     let mut rows = Vec::new();
-    let mut base_row: Vec<Element<MainMessage>> = Vec::new();
-    base_row.push(widget::horizontal_space().into());
-    for (_id, name) in group.all.iter().copied().zip(names.iter()) {
-        base_row.push(widget::text(name).into());
-    }
+    let base_row: Vec<Element<MainMessage>> = vec![
+        widget::text(assets::TEXT.get("mat_arrange_turn")).into(),
+        widget::text(assets::TEXT.get("mat_arrange_table")).into(),
+        widget::horizontal_space().into(),
+        widget::horizontal_space().into(),
+        widget::horizontal_space().into(),
+    ];
     rows.push(grid_row(base_row));
-    for (left_id, left_name) in group.all.iter().copied().zip(names.iter()) {
-        let mut row: Vec<Element<MainMessage>> = Vec::new();
-        row.push(widget::text(left_name).into());
-        for (right_id, _right_name) in group.all.iter().copied().zip(names.iter()) {
-            if right_id == left_id {
-                row.push(widget::horizontal_space().into());
+    for short in group.schedule.iter().copied() {
+        let row: Vec<Element<MainMessage>> = vec![
+            widget::text((short.turn + 1).to_string()).into(),
+            widget::text((short.table + 1).to_string()).into(),
+            name(&short.left),
+            name(&short.right),
+            if short.is_done() {
+                widget::text(assets::TEXT.get("mat_arrange_done"))
+                    .style(widget::text::success)
+                    .into()
             } else {
-                row.push(
-                    widget::button(assets::TEXT.get("mat_arrange_mat_create"))
-                        .width(iced::Fill)
-                        .on_press_with(move || {
-                            MainMessage::Open(Acquire::new(PanelHandle::new(
-                                mat_create::MatCreatePanel::default()
-                                    .with_left(left_id)
-                                    .with_right(right_id),
-                            )))
-                        })
-                        .into(),
-                );
-            }
-        }
-        let row = grid_row(row);
-        rows.push(row);
+                widget::button(assets::TEXT.get("mat_arrange_mat_create"))
+                    .width(iced::Fill)
+                    .on_press_with(move || {
+                        MainMessage::Open(Acquire::new(PanelHandle::new(
+                            mat_create::MatCreatePanel::default()
+                                .with_left(short.left)
+                                .with_right(short.right),
+                        )))
+                    })
+                    .into()
+            },
+        ];
+        rows.push(grid_row(row));
     }
-    grid(rows).row_spacing(5).column_spacing(5).into()
+    grid(rows).row_spacing(5).column_spacing(10).into()
 }
 
-fn view_arranger<'n>(
-    arranger: &Arranger,
-    names: &'n [Vec<String>],
+fn view_arrangement<'n>(
+    arrangement: &Arrangement,
+    names: &'n HashMap<Id, String>,
     current_group: usize,
 ) -> Element<'n, MainMessage> {
     widget::column![widget::row![
-        widget::text(format!("{} / {}", current_group + 1, arranger.groups.len())),
+        widget::text(format!(
+            "{} / {}",
+            current_group + 1,
+            arrangement.groups.len()
+        )),
         widget::button(assets::TEXT.get("mat_arrange_prev"))
             .on_press(MainMessage::MatArrangeMessage(MatArrangeMessage::PrevGroup)),
         widget::button(assets::TEXT.get("mat_arrange_next"))
-            .on_press(MainMessage::MatArrangeMessage(MatArrangeMessage::NextGroup))
+            .on_press(MainMessage::MatArrangeMessage(MatArrangeMessage::NextGroup)),
+        widget::button(assets::TEXT.get("mat_arrange_sync"))
+            .style(widget::button::secondary)
+            .on_press(MainMessage::MatArrangeMessage(MatArrangeMessage::Sync)),
+        widget::button(assets::TEXT.get("mat_arrange_close"))
+            .style(widget::button::danger)
+            .on_press(MainMessage::MatArrangeMessage(MatArrangeMessage::Close))
     ]
     .spacing(20),]
-    .push_maybe(
-        if let Some((group, names)) = arranger.groups.get(current_group).and_then(|group| {
-            names
-                .get(current_group)
-                .map(move |names| (group, names.as_slice()))
-        }) {
-            Some(widget::scrollable(view_group(group, names)).direction(
-                widget::scrollable::Direction::Both {
-                    vertical: widget::scrollable::Scrollbar::new(),
-                    horizontal: widget::scrollable::Scrollbar::new(),
-                },
-            ))
-        } else {
-            None
+    .push_maybe(arrangement.groups.get(current_group).map(|group| {
+        widget::scrollable(view_group(group, names)).direction(
+            widget::scrollable::Direction::Both {
+                vertical: widget::scrollable::Scrollbar::new(),
+                horizontal: widget::scrollable::Scrollbar::new(),
+            },
+        )
+    }))
+    .into()
+}
+
+fn view_swiss<'n>(swiss: &Swiss, names: &'n HashMap<Id, String>) -> Element<'n, MainMessage> {
+    use iced_aw::{grid, grid_row};
+    let name = |id: &Id| -> Element<'n, MainMessage> {
+        widget::text(names.get(id).map(String::as_str).unwrap_or_default()).into()
+    };
+    let mut pairings = Vec::new();
+    for pairing in swiss.rounds.last().into_iter().flatten().copied() {
+        pairings.push(grid_row(vec![
+            name(&pairing.left),
+            match pairing.right {
+                Some(right) => name(&right),
+                None => widget::text(assets::TEXT.get("mat_arrange_bye")).into(),
+            },
+            match pairing.right {
+                Some(right) if !pairing.is_done() => {
+                    widget::button(assets::TEXT.get("mat_arrange_mat_create"))
+                        .width(iced::Fill)
+                        .on_press_with(move || {
+                            MainMessage::Open(Acquire::new(PanelHandle::new(
+                                mat_create::MatCreatePanel::default()
+                                    .with_left(pairing.left)
+                                    .with_right(right),
+                            )))
+                        })
+                        .into()
+                }
+                _ => widget::text(assets::TEXT.get("mat_arrange_done"))
+                    .style(widget::text::success)
+                    .into(),
+            },
+        ]));
+    }
+    let base_row: Vec<Element<MainMessage>> = vec![
+        widget::horizontal_space().into(),
+        widget::horizontal_space().into(),
+        widget::text(assets::TEXT.get("mat_arrange_points")).into(),
+        widget::text(assets::TEXT.get("mat_arrange_buchholz")).into(),
+        widget::text(assets::TEXT.get("mat_arrange_sonneborn_berger")).into(),
+    ];
+    let mut standings = vec![grid_row(base_row)];
+    for (rank, standing) in swiss.standings().iter().enumerate() {
+        let row: Vec<Element<MainMessage>> = vec![
+            widget::text((rank + 1).to_string()).into(),
+            name(&standing.crew),
+            widget::text(standing.points.to_string()).into(),
+            widget::text(standing.buchholz.to_string()).into(),
+            widget::text(standing.sonneborn_berger.to_string()).into(),
+        ];
+        standings.push(grid_row(row));
+    }
+    widget::column![
+        widget::row![
+            widget::text(format!(
+                "{} {}",
+                assets::TEXT.get("mat_arrange_round"),
+                swiss.rounds.len()
+            )),
+            widget::button(assets::TEXT.get("mat_arrange_next_round")).on_press_maybe(
+                swiss
+                    .is_round_done()
+                    .then_some(MainMessage::MatArrangeMessage(MatArrangeMessage::NextRound))
+            ),
+            widget::button(assets::TEXT.get("mat_arrange_sync"))
+                .style(widget::button::secondary)
+                .on_press(MainMessage::MatArrangeMessage(MatArrangeMessage::Sync)),
+            widget::button(assets::TEXT.get("mat_arrange_close"))
+                .style(widget::button::danger)
+                .on_press(MainMessage::MatArrangeMessage(MatArrangeMessage::Close))
+        ]
+        .spacing(20),
+        widget::scrollable(
+            widget::column![
+                grid(pairings).row_spacing(5).column_spacing(10),
+                grid(standings).row_spacing(5).column_spacing(10),
+            ]
+            .spacing(20)
+        )
+        .direction(widget::scrollable::Direction::Both {
+            vertical: widget::scrollable::Scrollbar::new(),
+            horizontal: widget::scrollable::Scrollbar::new(),
+        }),
+    ]
+    .spacing(10)
+    .into()
+}
+
+/// Loads the names of the crews, which are already loaded when arranging
+fn load_names(login: Arc<Login>, crews: Arc<crew::CrewList>) -> Task<MainMessage> {
+    Task::perform(
+        async move {
+            let mut names = HashMap::new();
+            for crew in crews.iter() {
+                let mut crew = crew.write().await;
+                let id = crew.id();
+                names.insert(id, crew.load(login.as_ref()).await?.name.clone());
+            }
+            Result::<_>::Ok(names)
+        },
+        |result| match result {
+            Ok(names) => MainMessage::MatArrangeMessage(MatArrangeMessage::NamesAcquired(
+                Acquire::new(names),
+            )),
+            Err(err) => {
+                error!("When acquiring names, {}", err);
+                MainMessage::MatArrangeMessage(MatArrangeMessage::Error)
+            }
         },
     )
-    .into()
 }
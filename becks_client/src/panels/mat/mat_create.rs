@@ -7,34 +7,51 @@ struct Selection {
     name: Option<String>,
 }
 
+/// A place of a crew in the match
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Left,
+    Right,
+    LeftPartner,
+    RightPartner,
+}
+
 #[derive(Debug)]
 pub struct MatCreatePanel {
     selection: Option<crew_query::CrewQueryPanel>,
-    select_is_left: bool,
+    select_slot: Slot,
     total: usize,
     left: Option<Selection>,
     right: Option<Selection>,
+    doubles: bool,
+    left_partner: Option<Selection>,
+    right_partner: Option<Selection>,
     rounds: Vec<Option<Round>>,
+    /// The points of each round as being typed, for left and right
+    points: Vec<(String, String)>,
     quit: Quit,
     notes: String,
     error: bool,
+    error_code: Option<error::ErrorCode>,
     local_error: bool,
 }
 
 #[derive(Debug, Clone)]
 pub enum MatCreateMessage {
     StartCreate,
-    Error,
+    Error(Option<error::ErrorCode>),
     LocalError,
     Created,
     UpdateTotal(usize),
-    StartSelect(bool),
-    StartGetName(bool),
-    NameAcquired(bool, String),
+    StartSelect(Slot),
+    StartGetName(Slot),
+    NameAcquired(Slot, String),
+    UpdateDoubles(bool),
     // These are not required since modification could be directly done
     // UpdateLeft(Id),
     // UpdateRight(Id),
     ModifyRound(usize, bool),
+    ModifyPoints(usize, bool, String),
     UpdateQuit(Quit),
     UpdateNotes(String),
 }
@@ -44,14 +61,19 @@ impl Default for MatCreatePanel {
         let total = config::CONFIG.interact.default_rounds;
         Self {
             selection: None,
-            select_is_left: true,
+            select_slot: Slot::Left,
             total,
             left: None,
             right: None,
+            doubles: false,
+            left_partner: None,
+            right_partner: None,
             rounds: vec![None; total],
+            points: vec![Default::default(); total],
             quit: Default::default(),
             notes: Default::default(),
             error: false,
+            error_code: None,
             local_error: false,
         }
     }
@@ -67,6 +89,42 @@ impl MatCreatePanel {
         self.right = Some(Selection { id, name: None });
         self
     }
+
+    fn slot(&self, slot: Slot) -> &Option<Selection> {
+        match slot {
+            Slot::Left => &self.left,
+            Slot::Right => &self.right,
+            Slot::LeftPartner => &self.left_partner,
+            Slot::RightPartner => &self.right_partner,
+        }
+    }
+
+    fn slot_mut(&mut self, slot: Slot) -> &mut Option<Selection> {
+        match slot {
+            Slot::Left => &mut self.left,
+            Slot::Right => &mut self.right,
+            Slot::LeftPartner => &mut self.left_partner,
+            Slot::RightPartner => &mut self.right_partner,
+        }
+    }
+
+    /// Returns the partners of both sides, or `None` if a partner is not yet selected in doubles
+    fn partners(&self) -> Option<(Option<Id>, Option<Id>)> {
+        if self.doubles {
+            let left = self.left_partner.as_ref()?.id;
+            let right = self.right_partner.as_ref()?.id;
+            Some((Some(left), Some(right)))
+        } else {
+            Some((None, None))
+        }
+    }
+
+    fn name_of(&self, slot: Slot, pending: &'static str) -> &str {
+        self.slot(slot)
+            .as_ref()
+            .and_then(|selection| selection.name.as_deref())
+            .unwrap_or_else(|| assets::TEXT.get(pending))
+    }
 }
 
 impl Panel for MatCreatePanel {
@@ -76,16 +134,29 @@ impl Panel for MatCreatePanel {
         match message {
             MainMessage::MatCreateMessage(message) => match message {
                 MatCreateMessage::StartCreate => {
-                    if let Some((left, right, rounds)) = self
+                    if let Some(((left, right, rounds), (left_partner, right_partner))) = self
                         .left
                         .as_ref()
                         .and_then(|left| self.right.as_ref().map(move |right| (left.id, right.id)))
                         .and_then(|(left, right)| {
-                            let rounds = self.rounds.iter().copied().collect::<Option<Vec<_>>>();
+                            // A match that a crew quits keeps only the rounds played
+                            let rounds = if self.quit == Quit::Normal {
+                                self.rounds.iter().copied().collect::<Option<Vec<_>>>()
+                            } else {
+                                Some(self.rounds.iter().map_while(|round| *round).collect())
+                            };
                             rounds.map(move |round| (left, right, round))
                         })
+                        .zip(self.partners())
                     {
                         let mut mat = Match::new(self.total, left, right, current_timestamp());
+                        mat.left_partner = left_partner;
+                        mat.right_partner = right_partner;
+                        if !mat.has_distinct_crews() {
+                            return Task::done(MainMessage::MatCreateMessage(
+                                MatCreateMessage::LocalError,
+                            ));
+                        }
                         mat.rounds = rounds;
                         mat.quit = self.quit;
                         mat.notes = self.notes.clone();
@@ -95,7 +166,9 @@ impl Panel for MatCreatePanel {
                                 Ok(_) => MainMessage::MatCreateMessage(MatCreateMessage::Created),
                                 Err(err) => {
                                     error!("When creating match, {}", err);
-                                    MainMessage::MatCreateMessage(MatCreateMessage::Error)
+                                    MainMessage::MatCreateMessage(MatCreateMessage::Error(
+                                        error::error_code(&err),
+                                    ))
                                 }
                             },
                         )
@@ -103,18 +176,13 @@ impl Panel for MatCreatePanel {
                         Task::done(MainMessage::MatCreateMessage(MatCreateMessage::LocalError))
                     }
                 }
-                MatCreateMessage::StartSelect(left) => {
-                    self.select_is_left = left;
+                MatCreateMessage::StartSelect(slot) => {
+                    self.select_slot = slot;
                     self.selection = Some(crew_query::CrewQueryPanel::default().select_only());
                     Task::none()
                 }
-                MatCreateMessage::StartGetName(left) => {
-                    let id = if left {
-                        self.left.as_ref().map(|left| left.id)
-                    } else {
-                        self.right.as_ref().map(|right| right.id)
-                    };
-                    if let Some(id) = id {
+                MatCreateMessage::StartGetName(slot) => {
+                    if let Some(id) = self.slot(slot).as_ref().map(|selection| selection.id) {
                         Task::perform(
                             async move {
                                 crew::CrewInfo::new(id)
@@ -124,7 +192,7 @@ impl Panel for MatCreatePanel {
                             },
                             move |result| match result {
                                 Ok(name) => MainMessage::MatCreateMessage(
-                                    MatCreateMessage::NameAcquired(left, name),
+                                    MatCreateMessage::NameAcquired(slot, name),
                                 ),
                                 Err(err) => {
                                     error!("When acquiring match crew name, {}", err);
@@ -136,40 +204,59 @@ impl Panel for MatCreatePanel {
                         Task::none()
                     }
                 }
-                MatCreateMessage::NameAcquired(left, name) => {
-                    #[allow(clippy::collapsible_else_if)]
-                    if left {
-                        if let Some(left) = self.left.as_mut() {
-                            left.name = Some(name);
-                        } else {
-                            warn!("When acquired name {}, left does not exist", name);
-                        }
+                MatCreateMessage::NameAcquired(slot, name) => {
+                    if let Some(selection) = self.slot_mut(slot).as_mut() {
+                        selection.name = Some(name);
                     } else {
-                        if let Some(right) = self.right.as_mut() {
-                            right.name = Some(name);
-                        } else {
-                            warn!("When acquired name {}, right does not exist", name);
-                        }
+                        warn!("When acquired name {}, {:?} does not exist", name, slot);
                     }
                     Task::none()
                 }
+                MatCreateMessage::UpdateDoubles(doubles) => {
+                    self.doubles = doubles;
+                    Task::none()
+                }
                 MatCreateMessage::LocalError => {
                     self.local_error = true;
                     Task::none()
                 }
-                MatCreateMessage::Error => {
+                MatCreateMessage::Error(code) => {
                     self.error = true;
+                    self.error_code = code;
                     Task::none()
                 }
                 MatCreateMessage::Created => Task::done(MainMessage::Rewind),
                 MatCreateMessage::UpdateTotal(len) => {
                     self.total = len;
                     self.rounds.resize(len, None);
+                    self.points.resize(len, Default::default());
                     Task::none()
                 }
                 MatCreateMessage::ModifyRound(index, left_win) => {
                     if let Some(round) = self.rounds.get_mut(index) {
-                        *round = Some(Round { left_win });
+                        *round = Some(Round::new(left_win));
+                    }
+                    if let Some(points) = self.points.get_mut(index) {
+                        *points = Default::default();
+                    }
+                    Task::none()
+                }
+                MatCreateMessage::ModifyPoints(index, left, value) => {
+                    if let (Some(round), Some(points)) =
+                        (self.rounds.get_mut(index), self.points.get_mut(index))
+                    {
+                        if left {
+                            points.0 = value;
+                        } else {
+                            points.1 = value;
+                        }
+                        // The round follows the points once any is typed,
+                        // and stays unfinished until they make a valid game
+                        if !points.0.is_empty() || !points.1.is_empty() {
+                            *round = points.0.parse().ok().zip(points.1.parse().ok()).and_then(
+                                |(left, right)| Round::with_points(GameScore::new(left, right)),
+                            );
+                        }
                     }
                     Task::none()
                 }
@@ -189,17 +276,11 @@ impl Panel for MatCreatePanel {
                     let task = if let Some(id) = selection.iter().next().copied() {
                         std::mem::drop(selection);
                         self.selection = None;
-                        if self.select_is_left {
-                            self.left = Some(Selection { id, name: None });
-                            task.chain(Task::done(MainMessage::MatCreateMessage(
-                                MatCreateMessage::StartGetName(true),
-                            )))
-                        } else {
-                            self.right = Some(Selection { id, name: None });
-                            task.chain(Task::done(MainMessage::MatCreateMessage(
-                                MatCreateMessage::StartGetName(false),
-                            )))
-                        }
+                        let slot = self.select_slot;
+                        *self.slot_mut(slot) = Some(Selection { id, name: None });
+                        task.chain(Task::done(MainMessage::MatCreateMessage(
+                            MatCreateMessage::StartGetName(slot),
+                        )))
                     } else {
                         task
                     };
@@ -221,43 +302,59 @@ impl Panel for MatCreatePanel {
             );
             column.push(widget::horizontal_rule(2).into());
             column.push(
-                widget::row![
-                    widget::button(assets::TEXT.get("mat_create_left"))
-                        .on_press(MainMessage::MatCreateMessage(
-                            MatCreateMessage::StartSelect(true)
-                        ))
-                        .style(if self.left.is_some() {
-                            widget::button::secondary
-                        } else {
-                            widget::button::primary
-                        }),
-                    widget::button(assets::TEXT.get("mat_create_right"))
+                widget::checkbox(assets::TEXT.get("mat_create_doubles"), self.doubles)
+                    .on_toggle(|doubles| {
+                        MainMessage::MatCreateMessage(MatCreateMessage::UpdateDoubles(doubles))
+                    })
+                    .into(),
+            );
+            let mut slots = vec![
+                (Slot::Left, "mat_create_left"),
+                (Slot::Right, "mat_create_right"),
+            ];
+            if self.doubles {
+                slots.insert(1, (Slot::LeftPartner, "mat_create_left_partner"));
+                slots.push((Slot::RightPartner, "mat_create_right_partner"));
+            }
+            column.push(
+                widget::Row::from_iter(slots.into_iter().map(|(slot, key)| {
+                    widget::button(assets::TEXT.get(key))
                         .on_press(MainMessage::MatCreateMessage(
-                            MatCreateMessage::StartSelect(false)
+                            MatCreateMessage::StartSelect(slot),
                         ))
-                        .style(if self.right.is_some() {
+                        .style(if self.slot(slot).is_some() {
                             widget::button::secondary
                         } else {
                             widget::button::primary
-                        }),
-                ]
+                        })
+                        .into()
+                }))
                 .spacing(10)
                 .into(),
             );
+            let (left, right) = if self.doubles {
+                (
+                    format!(
+                        "{} / {}",
+                        self.name_of(Slot::Left, "mat_create_left_pending"),
+                        self.name_of(Slot::LeftPartner, "mat_create_left_pending")
+                    ),
+                    format!(
+                        "{} / {}",
+                        self.name_of(Slot::Right, "mat_create_right_pending"),
+                        self.name_of(Slot::RightPartner, "mat_create_right_pending")
+                    ),
+                )
+            } else {
+                (
+                    self.name_of(Slot::Left, "mat_create_left_pending")
+                        .to_owned(),
+                    self.name_of(Slot::Right, "mat_create_right_pending")
+                        .to_owned(),
+                )
+            };
             column.push(
-                widget::text(format!(
-                    "{} {} {}",
-                    self.left
-                        .as_ref()
-                        .and_then(|selection| selection.name.as_deref())
-                        .unwrap_or_else(|| assets::TEXT.get("mat_create_left_pending")),
-                    assets::TEXT.get("vs"),
-                    self.right
-                        .as_ref()
-                        .and_then(|selection| selection.name.as_deref())
-                        .unwrap_or_else(|| assets::TEXT.get("mat_create_right_pending")),
-                ))
-                .into(),
+                widget::text(format!("{} {} {}", left, assets::TEXT.get("vs"), right)).into(),
             );
             if let Some(selection) = self.selection.as_ref() {
                 column.push(
@@ -273,17 +370,18 @@ impl Panel for MatCreatePanel {
             } else {
                 column.push(view_total(self.total));
                 column.push(
-                    widget::scrollable(view_rounds(self.rounds.as_slice()))
+                    widget::scrollable(view_rounds(self.rounds.as_slice(), self.points.as_slice()))
                         .direction(widget::scrollable::Direction::Horizontal(
                             widget::scrollable::Scrollbar::new(),
                         ))
-                        .height(100)
+                        .height(150)
                         .into(),
                 );
+                column.push(view_quit(self.quit));
             }
             if self.error {
                 column.push(
-                    widget::text(assets::TEXT.get("mat_create_error"))
+                    widget::text(assets::TEXT.get_error(self.error_code, "mat_create_error"))
                         .style(widget::text::danger)
                         .into(),
                 )
@@ -306,21 +404,21 @@ impl Panel for MatCreatePanel {
     }
 
     fn on_start_up(&mut self) -> Task<MainMessage> {
-        let left_task = if self.left.is_some() {
-            Task::done(MainMessage::MatCreateMessage(
-                MatCreateMessage::StartGetName(true),
-            ))
-        } else {
-            Task::none()
-        };
-        let right_task = if self.right.is_some() {
-            Task::done(MainMessage::MatCreateMessage(
-                MatCreateMessage::StartGetName(false),
-            ))
-        } else {
-            Task::none()
-        };
-        left_task.chain(right_task)
+        Task::batch(
+            [
+                Slot::Left,
+                Slot::Right,
+                Slot::LeftPartner,
+                Slot::RightPartner,
+            ]
+            .into_iter()
+            .filter(|slot| self.slot(*slot).is_some())
+            .map(|slot| {
+                Task::done(MainMessage::MatCreateMessage(
+                    MatCreateMessage::StartGetName(slot),
+                ))
+            }),
+        )
     }
 }
 
@@ -347,10 +445,30 @@ fn view_total(total: usize) -> Element<'static, MainMessage> {
     .into()
 }
 
-fn view_rounds(rounds: &[Option<Round>]) -> Element<MainMessage> {
+fn view_quit(quit: Quit) -> Element<'static, MainMessage> {
+    let mut row: Vec<Element<MainMessage>> = Vec::new();
+    for (value, key) in [
+        (Quit::Normal, "mat_edit_normal"),
+        (Quit::LeftQuit, "mat_left_quit"),
+        (Quit::RightQuit, "mat_right_quit"),
+    ] {
+        row.push(
+            widget::radio(assets::TEXT.get(key), value, Some(quit), |quit| {
+                MainMessage::MatCreateMessage(MatCreateMessage::UpdateQuit(quit))
+            })
+            .into(),
+        );
+    }
+    widget::Row::from_iter(row).spacing(15).into()
+}
+
+fn view_rounds<'a>(
+    rounds: &'a [Option<Round>],
+    points: &'a [(String, String)],
+) -> Element<'a, MainMessage> {
     let mut row: Vec<Element<MainMessage>> = Vec::new();
     row.push(widget::text(assets::TEXT.get("mat_create_rounds")).into());
-    for (index, round) in rounds.iter().enumerate() {
+    for (index, (round, points)) in rounds.iter().zip(points.iter()).enumerate() {
         row.push(
             widget::row![
                 widget::text((index + 1).to_string()),
@@ -370,9 +488,23 @@ fn view_rounds(rounds: &[Option<Round>]) -> Element<MainMessage> {
                         |value| MainMessage::MatCreateMessage(MatCreateMessage::ModifyRound(
                             index, value
                         ))
-                    )
+                    ),
+                    widget::row![
+                        widget::text_input(assets::TEXT.get("mat_create_points_hint"), &points.0)
+                            .width(40)
+                            .on_input(move |value| MainMessage::MatCreateMessage(
+                                MatCreateMessage::ModifyPoints(index, true, value)
+                            )),
+                        widget::text(":"),
+                        widget::text_input(assets::TEXT.get("mat_create_points_hint"), &points.1)
+                            .width(40)
+                            .on_input(move |value| MainMessage::MatCreateMessage(
+                                MatCreateMessage::ModifyPoints(index, false, value)
+                            )),
+                    ]
+                    .spacing(3)
                 ]
-                .height(60)
+                .height(110)
                 .spacing(10)
             ]
             .spacing(3)
@@ -1,13 +1,27 @@
 use crate::prelude::*;
 use becks_match::*;
+use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct MatLoaded {
+    id: Id,
     mat: Match,
     left: String,
     right: String,
 }
 
+/// A match being edited, which is only sent to the server when saved
+#[derive(Debug)]
+struct MatEdit {
+    id: Id,
+    swapped: bool,
+    rounds: Vec<Round>,
+    quit: Quit,
+    notes: String,
+    error: bool,
+    error_code: Option<error::ErrorCode>,
+}
+
 #[derive(Debug)]
 pub struct MatPanel {
     mat: Arc<mat::MatchList>,
@@ -15,6 +29,7 @@ pub struct MatPanel {
     is_loaded: bool,
     /// The focus crew id, if any
     focus: Option<Id>,
+    edit: Option<MatEdit>,
 }
 
 #[derive(Debug, Clone)]
@@ -22,6 +37,18 @@ pub enum MatMessage {
     Reload,
     Load,
     Loaded(Acquire<Vec<MatLoaded>>),
+    LoadMore,
+    MoreLoaded(Acquire<mat::MatchList>),
+    StartEdit(Id),
+    CancelEdit,
+    EditSwap,
+    EditTotal(usize),
+    EditRound(usize, bool),
+    EditQuit(Quit),
+    EditNotes(String),
+    SaveEdit,
+    Saved,
+    EditError(Option<error::ErrorCode>),
 }
 
 impl MatPanel {
@@ -31,12 +58,36 @@ impl MatPanel {
             loaded: Default::default(),
             is_loaded: false,
             focus,
+            edit: None,
         }
     }
 
     pub fn is_loaded(&self) -> bool {
         self.is_loaded
     }
+
+    /// Returns the modification made by the edit, where unchanged fields are left out
+    fn modification(&self, edit: &MatEdit) -> Option<mat::methods::modify::MatchModification> {
+        let loaded = self.loaded.iter().find(|loaded| loaded.id == edit.id)?;
+        let old = &loaded.mat;
+        let rounds_changed = old.rounds.len() != edit.rounds.len()
+            || old
+                .rounds
+                .iter()
+                .zip(edit.rounds.iter())
+                .any(|(old, new)| old.left_win != new.left_win);
+        Some(mat::methods::modify::MatchModification {
+            notes: (old.notes != edit.notes).then(|| edit.notes.clone()),
+            rounds: rounds_changed.then(|| edit.rounds.clone()),
+            quit: (old.quit != edit.quit).then_some(edit.quit),
+            left: edit.swapped.then_some(old.right),
+            right: edit.swapped.then_some(old.left),
+            partners: (edit.swapped && old.is_doubles())
+                .then_some([old.right_partner, old.left_partner]),
+            total_rounds: None,
+            timestamp: None,
+        })
+    }
 }
 
 impl Panel for MatPanel {
@@ -47,44 +98,63 @@ impl Panel for MatPanel {
                     let mat = self.mat.clone();
                     Task::perform(
                         async move {
-                            let result: Result<Vec<MatLoaded>> = match mat
-                                .reload(login.as_ref())
-                                .await
-                            {
-                                Ok(_) => {
-                                    let mut result = Vec::new();
-                                    for mat in mat.iter() {
-                                        result.push(
-                                            mat.write().await.load(login.as_ref()).await.cloned(),
-                                        );
-                                    }
-                                    let mat = result.into_iter().collect::<Result<Vec<_>>>();
-                                    match mat {
-                                        Ok(mat) => {
-                                            let mut result = Vec::new();
-                                            for mat in mat.into_iter() {
-                                                if let Ok(left) = crew::CrewInfo::new(mat.left)
-                                                    .load(login.as_ref())
+                            let result: Result<Vec<MatLoaded>> =
+                                match mat.reload(login.as_ref()).await {
+                                    Ok(_) => {
+                                        let mut result = Vec::new();
+                                        for mat in mat.iter() {
+                                            let mut mat = mat.write().await;
+                                            let id = mat.id();
+                                            result.push(
+                                                mat.load(login.as_ref())
                                                     .await
-                                                    .map(|crew| crew.name.clone())
-                                                {
-                                                    if let Ok(right) =
-                                                        crew::CrewInfo::new(mat.right)
-                                                            .load(login.as_ref())
-                                                            .await
-                                                            .map(|crew| crew.name.clone())
-                                                    {
-                                                        result.push(MatLoaded { mat, left, right })
+                                                    .map(|mat| (id, mat.clone())),
+                                            );
+                                        }
+                                        let mat = result.into_iter().collect::<Result<Vec<_>>>();
+                                        match mat {
+                                            Ok(mat) => {
+                                                let crews = crew::CrewList::new(
+                                                    mat.iter().flat_map(|(_, mat)| mat.crews()),
+                                                );
+                                                if let Err(err) = crews.load(login.as_ref()).await {
+                                                    warn!("When loading crew of matches, {}", err);
+                                                }
+                                                let mut names = HashMap::new();
+                                                for crew in crews.iter() {
+                                                    let crew = crew.read().await;
+                                                    if let Some(data) = crew.data.as_ref() {
+                                                        names.insert(crew.id(), data.name.clone());
+                                                    }
+                                                }
+                                                let mut result = Vec::new();
+                                                // Partners of a side are shown together
+                                                let side_name = |side: Vec<Id>| {
+                                                    side.iter()
+                                                        .map(|crew| names.get(crew).cloned())
+                                                        .collect::<Option<Vec<_>>>()
+                                                        .map(|names| names.join(" / "))
+                                                };
+                                                for (id, mat) in mat.into_iter() {
+                                                    if let (Some(left), Some(right)) = (
+                                                        side_name(mat.left_side()),
+                                                        side_name(mat.right_side()),
+                                                    ) {
+                                                        result.push(MatLoaded {
+                                                            id,
+                                                            mat,
+                                                            left,
+                                                            right,
+                                                        })
                                                     }
                                                 }
+                                                Ok(result)
                                             }
-                                            Ok(result)
+                                            Err(err) => Err(err),
                                         }
-                                        Err(err) => Err(err),
                                     }
-                                }
-                                Err(err) => Err(err),
-                            };
+                                    Err(err) => Err(err),
+                                };
                             result
                         },
                         |result| match result {
@@ -105,6 +175,35 @@ impl Panel for MatPanel {
                         Task::done(MainMessage::MatMessage(MatMessage::Reload))
                     }
                 }
+                MatMessage::LoadMore => {
+                    let mat = self.mat.clone();
+                    Task::perform(
+                        async move { mat.next_page(login.as_ref()).await },
+                        |result| match result {
+                            Ok(Some(page)) => {
+                                MainMessage::MatMessage(MatMessage::MoreLoaded(Acquire::new(page)))
+                            }
+                            Ok(None) => MainMessage::None,
+                            Err(err) => {
+                                error!("When loading more match, {}", err);
+                                MainMessage::None
+                            }
+                        },
+                    )
+                }
+                MatMessage::MoreLoaded(page) => {
+                    if let Some(page) = page.try_acquire() {
+                        if let Some(mat) = Arc::get_mut(&mut self.mat) {
+                            mat.append(page);
+                            Task::done(MainMessage::MatMessage(MatMessage::Reload))
+                        } else {
+                            warn!("The match list is in use, so its next page is dropped");
+                            Task::none()
+                        }
+                    } else {
+                        Task::none()
+                    }
+                }
                 MatMessage::Loaded(loaded) => {
                     if let Some(loaded) = loaded.try_acquire() {
                         self.is_loaded = true;
@@ -112,6 +211,97 @@ impl Panel for MatPanel {
                     }
                     Task::none()
                 }
+                MatMessage::StartEdit(id) => {
+                    if let Some(loaded) = self.loaded.iter().find(|loaded| loaded.id == id) {
+                        self.edit = Some(MatEdit {
+                            id,
+                            swapped: false,
+                            rounds: loaded.mat.rounds.clone(),
+                            quit: loaded.mat.quit,
+                            notes: loaded.mat.notes.clone(),
+                            error: false,
+                            error_code: None,
+                        });
+                    }
+                    Task::none()
+                }
+                MatMessage::CancelEdit => {
+                    self.edit = None;
+                    Task::none()
+                }
+                MatMessage::EditSwap => {
+                    if let Some(edit) = self.edit.as_mut() {
+                        edit.swapped = !edit.swapped;
+                    }
+                    Task::none()
+                }
+                MatMessage::EditTotal(total) => {
+                    if let Some(edit) = self.edit.as_mut() {
+                        edit.rounds.resize(total, Round::new(true));
+                    }
+                    Task::none()
+                }
+                MatMessage::EditRound(index, left_win) => {
+                    if let Some(round) = self
+                        .edit
+                        .as_mut()
+                        .and_then(|edit| edit.rounds.get_mut(index))
+                    {
+                        // Points that disagree with the winner are dropped
+                        if round.left_win != left_win {
+                            *round = Round::new(left_win);
+                        }
+                    }
+                    Task::none()
+                }
+                MatMessage::EditQuit(quit) => {
+                    if let Some(edit) = self.edit.as_mut() {
+                        edit.quit = quit;
+                    }
+                    Task::none()
+                }
+                MatMessage::EditNotes(notes) => {
+                    if let Some(edit) = self.edit.as_mut() {
+                        edit.notes = notes;
+                    }
+                    Task::none()
+                }
+                MatMessage::SaveEdit => {
+                    let Some(edit) = self.edit.as_ref() else {
+                        return Task::none();
+                    };
+                    let Some(modification) = self.modification(edit) else {
+                        return Task::done(MainMessage::MatMessage(MatMessage::CancelEdit));
+                    };
+                    let id = edit.id;
+                    Task::perform(
+                        async move {
+                            mat::MatchInfo::new(id)
+                                .modify(login.as_ref(), modification)
+                                .await
+                        },
+                        |result| match result {
+                            Ok(_) => MainMessage::MatMessage(MatMessage::Saved),
+                            Err(err) => {
+                                error!("When modifying match, {}", err);
+                                MainMessage::MatMessage(MatMessage::EditError(error::error_code(
+                                    &err,
+                                )))
+                            }
+                        },
+                    )
+                }
+                MatMessage::Saved => {
+                    self.edit = None;
+                    Task::done(MainMessage::MatMessage(MatMessage::Reload))
+                }
+                MatMessage::EditError(code) => {
+                    if let Some(edit) = self.edit.as_mut() {
+                        edit.error = true;
+                        edit.error_code = code;
+                    }
+                    Task::none()
+                }
             },
             _ => Task::none(),
         }
@@ -123,10 +313,20 @@ impl Panel for MatPanel {
                 widget::text(assets::TEXT.get("mat_empty")).into()
             } else {
                 let mut column: Vec<Element<MainMessage>> = Vec::new();
-                for (index, mat) in self.loaded.iter().enumerate() {
-                    column.push(view_mat(mat, self.focus));
+                for mat in self.loaded.iter() {
+                    match self.edit.as_ref() {
+                        Some(edit) if edit.id == mat.id => column.push(view_edit(mat, edit)),
+                        _ => column.push(view_mat(mat, self.focus)),
+                    }
                     column.push(widget::Rule::horizontal(2).into());
                 }
+                if self.mat.has_more() {
+                    column.push(
+                        widget::button(assets::TEXT.get("mat_load_more"))
+                            .on_press(MainMessage::MatMessage(MatMessage::LoadMore))
+                            .into(),
+                    );
+                }
                 widget::scrollable(widget::Column::from_iter(column)).into()
             }
         } else {
@@ -162,6 +362,20 @@ fn view_mat(mat: &MatLoaded, focus: Option<Id>) -> Element<MainMessage> {
                 );
             let right_wins = mat.mat.total_rounds as i32 - left_wins;
             row.push(widget::text(format!("{} : {}", left_wins, right_wins)).into());
+            let points = mat
+                .mat
+                .rounds
+                .iter()
+                .filter_map(|round| round.points)
+                .map(|points| format!("{}-{}", points.left, points.right))
+                .collect::<Vec<_>>();
+            if !points.is_empty() {
+                row.push(
+                    widget::text(points.join(" "))
+                        .style(widget::text::secondary)
+                        .into(),
+                );
+            }
         }
         Quit::LeftQuit => {
             row.push(
@@ -186,10 +400,10 @@ fn view_mat(mat: &MatLoaded, focus: Option<Id>) -> Element<MainMessage> {
     }
     if let Some(focus) = focus {
         let mut earn = 0;
-        if mat.mat.left == focus {
+        if mat.mat.left_side().contains(&focus) {
             earn += mat.mat.left_earn;
         }
-        if mat.mat.right == focus {
+        if mat.mat.right_side().contains(&focus) {
             earn += mat.mat.right_earn;
         }
         row.push(
@@ -202,5 +416,121 @@ fn view_mat(mat: &MatLoaded, focus: Option<Id>) -> Element<MainMessage> {
                 .into(),
         )
     }
+    row.push(
+        widget::button(assets::TEXT.get("mat_edit"))
+            .style(widget::button::secondary)
+            .on_press(MainMessage::MatMessage(MatMessage::StartEdit(mat.id)))
+            .into(),
+    );
     widget::Row::from_iter(row).spacing(15).padding(10).into()
 }
+
+fn view_edit<'a>(mat: &'a MatLoaded, edit: &'a MatEdit) -> Element<'a, MainMessage> {
+    let (left, right) = if edit.swapped {
+        (&mat.right, &mat.left)
+    } else {
+        (&mat.left, &mat.right)
+    };
+    let mut column: Vec<Element<MainMessage>> = Vec::new();
+    column.push(
+        widget::row![
+            widget::text(format!("{} {} {}", left, assets::TEXT.get("vs"), right)),
+            widget::button(assets::TEXT.get("mat_edit_swap"))
+                .style(widget::button::secondary)
+                .on_press(MainMessage::MatMessage(MatMessage::EditSwap)),
+        ]
+        .spacing(15)
+        .into(),
+    );
+    let total = edit.rounds.len();
+    column.push(
+        widget::row![
+            widget::text(assets::TEXT.get("mat_create_total")),
+            widget::text(total.to_string()),
+            widget::button("+")
+                .style(widget::button::secondary)
+                .on_press(MainMessage::MatMessage(MatMessage::EditTotal(total + 1))),
+            widget::button("-")
+                .style(widget::button::secondary)
+                .on_press(MainMessage::MatMessage(MatMessage::EditTotal(
+                    total.saturating_sub(1).max(1)
+                ))),
+        ]
+        .spacing(10)
+        .into(),
+    );
+    let mut rounds: Vec<Element<MainMessage>> = Vec::new();
+    for (index, round) in edit.rounds.iter().enumerate() {
+        rounds.push(
+            widget::row![
+                widget::text((index + 1).to_string()),
+                widget::column![
+                    widget::radio(
+                        assets::TEXT.get("mat_create_left_win"),
+                        true,
+                        Some(round.left_win),
+                        |value| MainMessage::MatMessage(MatMessage::EditRound(index, value))
+                    ),
+                    widget::radio(
+                        assets::TEXT.get("mat_create_right_win"),
+                        false,
+                        Some(round.left_win),
+                        |value| MainMessage::MatMessage(MatMessage::EditRound(index, value))
+                    ),
+                ]
+                .spacing(10)
+            ]
+            .spacing(3)
+            .into(),
+        );
+    }
+    column.push(
+        widget::scrollable(widget::Row::from_iter(rounds).spacing(8))
+            .direction(widget::scrollable::Direction::Horizontal(
+                widget::scrollable::Scrollbar::new(),
+            ))
+            .into(),
+    );
+    let mut quits: Vec<Element<MainMessage>> = Vec::new();
+    for (quit, key) in [
+        (Quit::Normal, "mat_edit_normal"),
+        (Quit::LeftQuit, "mat_left_quit"),
+        (Quit::RightQuit, "mat_right_quit"),
+    ] {
+        quits.push(
+            widget::radio(assets::TEXT.get(key), quit, Some(edit.quit), |quit| {
+                MainMessage::MatMessage(MatMessage::EditQuit(quit))
+            })
+            .into(),
+        );
+    }
+    column.push(widget::Row::from_iter(quits).spacing(15).into());
+    column.push(
+        widget::text_input(assets::TEXT.get("mat_edit_notes_hint"), &edit.notes)
+            .on_input(|notes| MainMessage::MatMessage(MatMessage::EditNotes(notes)))
+            .into(),
+    );
+    column.push(
+        widget::row![
+            widget::button(assets::TEXT.get("mat_edit_save"))
+                .style(widget::button::primary)
+                .on_press(MainMessage::MatMessage(MatMessage::SaveEdit)),
+            widget::button(assets::TEXT.get("mat_edit_cancel"))
+                .style(widget::button::secondary)
+                .on_press(MainMessage::MatMessage(MatMessage::CancelEdit)),
+        ]
+        .spacing(10)
+        .into(),
+    );
+    if edit.error {
+        column.push(
+            widget::text(assets::TEXT.get_error(edit.error_code, "mat_edit_error"))
+                .style(widget::text::danger)
+                .into(),
+        );
+    }
+    widget::Column::from_iter(column)
+        .spacing(10)
+        .padding(10)
+        .into()
+}
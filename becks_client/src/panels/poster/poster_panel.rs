@@ -25,6 +25,8 @@ pub enum PosterMessage {
     Load,
     Loaded(Acquire<Vec<becks_poster::Poster>>),
     View(usize),
+    LoadMore,
+    MoreLoaded(Acquire<poster::PosterList>),
 }
 
 impl Panel for PosterPanel {
@@ -35,6 +37,9 @@ impl Panel for PosterPanel {
                     let poster = self.poster.clone();
                     Task::perform(
                         async move {
+                            if let Err(err) = poster.load(login.as_ref()).await {
+                                warn!("When loading posters at once, {}", err);
+                            }
                             let mut posters = Vec::new();
                             for poster in poster.iter() {
                                 let poster =
@@ -83,6 +88,35 @@ impl Panel for PosterPanel {
                     }
                     Task::none()
                 }
+                PosterMessage::LoadMore => {
+                    let poster = self.poster.clone();
+                    Task::perform(
+                        async move { poster.next_page(login.as_ref()).await },
+                        |result| match result {
+                            Ok(Some(page)) => MainMessage::PosterMessage(
+                                PosterMessage::MoreLoaded(Acquire::new(page)),
+                            ),
+                            Ok(None) => MainMessage::None,
+                            Err(err) => {
+                                error!("When loading more poster, {}", err);
+                                MainMessage::None
+                            }
+                        },
+                    )
+                }
+                PosterMessage::MoreLoaded(page) => {
+                    if let Some(page) = page.try_acquire() {
+                        if let Some(poster) = Arc::get_mut(&mut self.poster) {
+                            poster.append(page);
+                            Task::done(MainMessage::PosterMessage(PosterMessage::Load))
+                        } else {
+                            warn!("The poster list is in use, so its next page is dropped");
+                            Task::none()
+                        }
+                    } else {
+                        Task::none()
+                    }
+                }
                 PosterMessage::View(index) => {
                     let poster = self.poster.clone();
                     Task::perform(
@@ -129,6 +163,13 @@ impl Panel for PosterPanel {
                     );
                     column.push(widget::Rule::horizontal(2).into());
                 }
+                if self.poster.has_more() {
+                    column.push(
+                        widget::button(assets::TEXT.get("poster_load_more"))
+                            .on_press(MainMessage::PosterMessage(PosterMessage::LoadMore))
+                            .into(),
+                    );
+                }
                 widget::scrollable(widget::Column::from_iter(column))
                     .height(iced::FillPortion(1000))
                     .into()
@@ -0,0 +1,180 @@
+use crate::group::Group;
+use crate::prelude::*;
+
+#[derive(Debug, Error)]
+pub enum ArrangementError {
+    #[error("an arrangement needs at least 1 group")]
+    NoGroup,
+    #[error("crew {0:?} is arranged more than once")]
+    DuplicateCrew(Id),
+    #[error("pairing of {0:?} and {1:?} is not within their group")]
+    StrayPairing(Id, Id),
+}
+
+/// The groups of crews arranged for a session, and which of their pairings are played
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Arrangement {
+    pub groups: Vec<Group>,
+    /// When the arrangement is created, so that only matches after it are linked
+    pub timestamp: u64,
+    #[serde(default)]
+    pub closed: bool,
+}
+
+impl Arrangement {
+    /// Creates the arrangement of the scheduled groups, where no pairing is played yet
+    pub fn new(mut groups: Vec<Group>, timestamp: u64) -> Result<Self, ArrangementError> {
+        if groups.is_empty() {
+            return Err(ArrangementError::NoGroup);
+        }
+        let mut crews = Vec::new();
+        for group in groups.iter_mut() {
+            for crew in group.all.iter() {
+                if crews.contains(crew) {
+                    return Err(ArrangementError::DuplicateCrew(*crew));
+                }
+                crews.push(*crew);
+            }
+            for short in group.schedule.iter_mut() {
+                if short.left == short.right
+                    || !group.all.contains(&short.left)
+                    || !group.all.contains(&short.right)
+                {
+                    return Err(ArrangementError::StrayPairing(short.left, short.right));
+                }
+                short.mat = None;
+            }
+        }
+        Ok(Self {
+            groups,
+            timestamp,
+            closed: false,
+        })
+    }
+
+    /// Returns every crew in the arrangement
+    pub fn crews(&self) -> impl Iterator<Item = Id> + '_ {
+        self.groups
+            .iter()
+            .flat_map(|group| group.all.iter().copied())
+    }
+
+    /// Links a recorded singles match to the pairing of its crews, returns whether it is linked;
+    /// Closed arrangements, matches already linked and matches played before the arrangement
+    /// is created are ignored
+    pub fn link(&mut self, id: Id, mat: &Match) -> bool {
+        if self.closed
+            || mat.is_doubles()
+            || mat.timestamp < self.timestamp
+            || self.groups.iter().any(|group| group.is_linked(id))
+        {
+            return false;
+        }
+        self.groups
+            .iter_mut()
+            .any(|group| group.link(id, mat.left, mat.right))
+    }
+
+    /// Links the recorded match again after it is voided or modified, where `mat` is the match
+    /// as it is now, or None if it no longer counts; Returns whether the arrangement is changed
+    ///
+    /// Only matches linked before are amended, and closed arrangements are amended as well,
+    /// so that no pairing stays done by a match that no longer counts
+    pub fn amend(&mut self, id: Id, mat: Option<&Match>) -> bool {
+        if !self.groups.iter_mut().any(|group| group.unlink(id)) {
+            return false;
+        }
+        if let Some(mat) = mat.filter(|mat| !mat.is_doubles() && mat.timestamp >= self.timestamp) {
+            self.groups
+                .iter_mut()
+                .any(|group| group.link(id, mat.left, mat.right));
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::MatchShort;
+    use becks_match::Round;
+
+    fn crew(id: u32) -> Id {
+        Id::from_prim(id)
+    }
+
+    fn arrangement() -> Arrangement {
+        let mut group = Group {
+            all: vec![crew(1), crew(2), crew(3)],
+            tables: 0,
+            schedule: Vec::new(),
+        };
+        group.arrange();
+        Arrangement::new(vec![group], 0).unwrap()
+    }
+
+    fn beat(winner: u32, loser: u32) -> Match {
+        let mut mat = Match::new(1, crew(winner), crew(loser), 0);
+        mat.rounds = vec![Round::new(true)];
+        mat
+    }
+
+    fn linked(arrangement: &Arrangement, id: u32) -> Option<MatchShort> {
+        arrangement.groups[0]
+            .schedule
+            .iter()
+            .find(|short| short.mat == Some(crew(id)))
+            .copied()
+    }
+
+    #[test]
+    fn test_link() {
+        let mut arrangement = arrangement();
+        assert!(arrangement.link(crew(100), &beat(1, 2)));
+        assert!(!arrangement.link(crew(100), &beat(1, 2)));
+        assert!(linked(&arrangement, 100)
+            .unwrap()
+            .is_between(crew(1), crew(2)));
+        // The only pairing of the crews is done
+        assert!(!arrangement.link(crew(101), &beat(2, 1)));
+        arrangement.closed = true;
+        assert!(!arrangement.link(crew(102), &beat(2, 3)));
+    }
+
+    #[test]
+    fn test_link_before() {
+        let mut arrangement = arrangement();
+        arrangement.timestamp = 10;
+        assert!(!arrangement.link(crew(100), &beat(1, 2)));
+        let mut later = beat(1, 2);
+        later.timestamp = 10;
+        assert!(arrangement.link(crew(100), &later));
+        // A match moved to before the arrangement no longer decides its pairing
+        assert!(arrangement.amend(crew(100), Some(&beat(1, 2))));
+        assert!(linked(&arrangement, 100).is_none());
+    }
+
+    #[test]
+    fn test_amend() {
+        let mut arrangement = arrangement();
+        arrangement.link(crew(100), &beat(1, 2));
+        // A flipped result keeps the pairing of the crews
+        assert!(arrangement.amend(crew(100), Some(&beat(2, 1))));
+        assert!(linked(&arrangement, 100)
+            .unwrap()
+            .is_between(crew(1), crew(2)));
+        // Other crews move the match to their pairing
+        assert!(arrangement.amend(crew(100), Some(&beat(3, 1))));
+        assert!(linked(&arrangement, 100)
+            .unwrap()
+            .is_between(crew(1), crew(3)));
+        // A voided match leaves its pairing to be played
+        assert!(arrangement.amend(crew(100), None));
+        assert!(linked(&arrangement, 100).is_none());
+        assert!(arrangement.groups[0]
+            .schedule
+            .iter()
+            .all(|short| !short.is_done()));
+        assert!(!arrangement.amend(crew(100), None));
+    }
+}
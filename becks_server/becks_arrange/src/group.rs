@@ -0,0 +1,315 @@
+use crate::prelude::*;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// The most steps tried when searching for a schedule without back-to-back pairings
+const REST_BUDGET: usize = 10000;
+
+/// A pairing in the schedule of a group
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct MatchShort {
+    pub left: Id,
+    pub right: Id,
+    /// The turn the pairing is played in, counting from 0
+    pub turn: usize,
+    /// The table the pairing is played on, counting from 0
+    pub table: usize,
+    /// The recorded match of the pairing, once it is played
+    #[serde(default)]
+    pub mat: Option<Id>,
+}
+
+impl MatchShort {
+    pub fn is_done(&self) -> bool {
+        self.mat.is_some()
+    }
+
+    /// Returns whether the pairing is between the two crews, in either order
+    pub fn is_between(&self, lhs: Id, rhs: Id) -> bool {
+        (self.left, self.right) == (lhs, rhs) || (self.left, self.right) == (rhs, lhs)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Group {
+    pub all: Vec<Id>,
+    /// The number of tables the group plays on, where 0 gives every pairing of a round a table
+    pub tables: usize,
+    pub schedule: Vec<MatchShort>,
+}
+
+/// Pairs everyone with everyone else by the circle method, where the first crew stays
+/// and the others rotate around it; With an odd count, a crew rests in each round
+fn circle(all: &[Id]) -> Vec<Vec<(Id, Id)>> {
+    let mut ring = all.iter().copied().map(Some).collect::<Vec<_>>();
+    if ring.len() % 2 == 1 {
+        ring.push(None);
+    }
+    let len = ring.len();
+    let mut rounds = Vec::new();
+    for round in 0..len.saturating_sub(1) {
+        let mut pairs = Vec::new();
+        for index in 0..len / 2 {
+            if let (Some(left), Some(right)) = (ring[index], ring[len - 1 - index]) {
+                // The staying crew switches sides, so that it is not always on the left
+                if index == 0 && round % 2 == 1 {
+                    pairs.push((right, left));
+                } else {
+                    pairs.push((left, right));
+                }
+            }
+        }
+        rounds.push(pairs);
+        ring[1..].rotate_right(1);
+    }
+    rounds
+}
+
+/// Splits the pairings into turns of `tables` pairings where no crew plays in two turns in a row,
+/// going back to an earlier choice when the rest cannot be split; `last` holds the crews of the
+/// previous turn, and `turn` the pairings of the current one, which are taken from `from` on
+fn rested(
+    pending: &[(Id, Id)],
+    tables: usize,
+    last: &[Id],
+    turn: &[(Id, Id)],
+    from: usize,
+    budget: &mut usize,
+) -> Option<Vec<Vec<(Id, Id)>>> {
+    if pending.is_empty() {
+        return Some(vec![turn.to_vec()]);
+    }
+    if turn.len() == tables {
+        let crews = turn
+            .iter()
+            .flat_map(|(left, right)| [*left, *right])
+            .collect::<Vec<_>>();
+        // Crews with more pairings left are tried first, so that they are not left to play
+        // each other in a row at the end
+        let left_over = |crew: &Id| {
+            pending
+                .iter()
+                .filter(|(left, right)| left == crew || right == crew)
+                .count()
+        };
+        let mut pending = pending.to_vec();
+        pending.sort_by_key(|(left, right)| Reverse(left_over(left) + left_over(right)));
+        let mut turns = rested(&pending, tables, &crews, &[], 0, budget)?;
+        turns.insert(0, turn.to_vec());
+        return Some(turns);
+    }
+    for (index, (left, right)) in pending.iter().enumerate().skip(from) {
+        if *budget == 0 {
+            return None;
+        }
+        *budget -= 1;
+        let busy = |crew: &Id| {
+            last.contains(crew) || turn.iter().any(|(lhs, rhs)| lhs == crew || rhs == crew)
+        };
+        if busy(left) || busy(right) {
+            continue;
+        }
+        let mut remaining = pending.to_vec();
+        remaining.remove(index);
+        let mut turn = turn.to_vec();
+        turn.push((*left, *right));
+        if let Some(turns) = rested(&remaining, tables, last, &turn, index, budget) {
+            return Some(turns);
+        }
+    }
+    None
+}
+
+impl Group {
+    /// Schedules every pairing of the group onto the tables turn by turn, where no crew plays
+    /// in two turns in a row if such a schedule is found; Otherwise a crew that just played
+    /// rests in the next turn whenever another pairing can be played
+    pub fn arrange(&mut self) {
+        let per_round = (self.all.len() / 2).max(1);
+        let tables = if self.tables == 0 {
+            per_round
+        } else {
+            self.tables.min(per_round)
+        };
+        let mut pending = circle(&self.all).into_iter().flatten().collect::<Vec<_>>();
+        self.schedule.clear();
+        let mut budget = REST_BUDGET;
+        if let Some(turns) = rested(&pending, tables, &[], &[], 0, &mut budget) {
+            for (turn, pairs) in turns.into_iter().enumerate() {
+                for (table, (left, right)) in pairs.into_iter().enumerate() {
+                    self.schedule.push(MatchShort {
+                        left,
+                        right,
+                        turn,
+                        table,
+                        mat: None,
+                    });
+                }
+            }
+            return;
+        }
+        let mut last_turn = HashMap::<Id, usize>::new();
+        let mut turn = 0;
+        while !pending.is_empty() {
+            let mut busy = Vec::new();
+            for table in 0..tables {
+                let rest = |crew: &Id| last_turn.get(crew).map_or(usize::MAX, |last| turn - last);
+                let best = pending
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (left, right))| !busy.contains(left) && !busy.contains(right))
+                    .min_by_key(|(index, (left, right))| {
+                        let rest = rest(left).min(rest(right));
+                        (rest == 1, Reverse(rest), *index)
+                    })
+                    .map(|(index, _)| index);
+                let Some(index) = best else {
+                    break;
+                };
+                let (left, right) = pending.remove(index);
+                busy.extend([left, right]);
+                last_turn.insert(left, turn);
+                last_turn.insert(right, turn);
+                self.schedule.push(MatchShort {
+                    left,
+                    right,
+                    turn,
+                    table,
+                    mat: None,
+                });
+            }
+            turn += 1;
+        }
+    }
+
+    /// Links the recorded match to the first pairing of the crews that is not done,
+    /// returns whether such pairing is found
+    pub fn link(&mut self, mat: Id, left: Id, right: Id) -> bool {
+        if let Some(short) = self
+            .schedule
+            .iter_mut()
+            .find(|short| !short.is_done() && short.is_between(left, right))
+        {
+            short.mat = Some(mat);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Unlinks the recorded match from its pairing, returns whether it was linked
+    pub fn unlink(&mut self, mat: Id) -> bool {
+        if let Some(short) = self
+            .schedule
+            .iter_mut()
+            .find(|short| short.mat == Some(mat))
+        {
+            short.mat = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns whether the match is already linked to a pairing
+    pub fn is_linked(&self, mat: Id) -> bool {
+        self.schedule.iter().any(|short| short.mat == Some(mat))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(count: u32, tables: usize) -> Group {
+        let mut group = Group {
+            all: (1..=count).map(Id::from_prim).collect(),
+            tables,
+            schedule: Vec::new(),
+        };
+        group.arrange();
+        group
+    }
+
+    /// Returns the pairings of each turn
+    fn turns(group: &Group) -> Vec<Vec<MatchShort>> {
+        let mut turns = Vec::<Vec<MatchShort>>::new();
+        for short in group.schedule.iter() {
+            if turns.len() <= short.turn {
+                turns.resize(short.turn + 1, Vec::new());
+            }
+            turns[short.turn].push(*short);
+        }
+        turns
+    }
+
+    fn plays(turn: &[MatchShort], crew: Id) -> bool {
+        turn.iter()
+            .any(|short| short.left == crew || short.right == crew)
+    }
+
+    #[test]
+    fn test_arrange_pairs_once() {
+        for count in 2..=9 {
+            for tables in 0..=3 {
+                let group = group(count, tables);
+                let count = count as usize;
+                assert_eq!(group.schedule.len(), count * (count - 1) / 2);
+                for (index, lhs) in group.all.iter().enumerate() {
+                    for rhs in group.all[index + 1..].iter() {
+                        let met = group
+                            .schedule
+                            .iter()
+                            .filter(|short| short.is_between(*lhs, *rhs))
+                            .count();
+                        assert_eq!(met, 1, "{:?} and {:?} of {} crews", lhs, rhs, count);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_arrange_tables() {
+        for count in 2..=9 {
+            for tables in 0..=3 {
+                let group = group(count, tables);
+                let limit = if tables == 0 {
+                    count as usize / 2
+                } else {
+                    tables
+                };
+                for turn in turns(&group) {
+                    assert!(!turn.is_empty());
+                    assert!(turn.len() <= limit);
+                    for (index, short) in turn.iter().enumerate() {
+                        assert!(short.table < limit);
+                        // No crew plays twice at once
+                        assert!(!plays(&turn[index + 1..], short.left));
+                        assert!(!plays(&turn[index + 1..], short.right));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_arrange_rest() {
+        // With fewer tables than pairings in a round, no crew needs to play in consecutive turns
+        for (count, tables) in [(5, 1), (6, 1), (7, 1), (8, 1), (9, 1), (9, 2), (10, 2)] {
+            let group = group(count, tables);
+            let turns = turns(&group);
+            for pair in turns.windows(2) {
+                for crew in group.all.iter() {
+                    assert!(
+                        !(plays(&pair[0], *crew) && plays(&pair[1], *crew)),
+                        "{:?} plays back to back with {} crews on {} tables",
+                        crew,
+                        count,
+                        tables
+                    );
+                }
+            }
+        }
+    }
+}
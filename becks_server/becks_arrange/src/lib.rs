@@ -0,0 +1,9 @@
+mod arrangement;
+mod group;
+mod prelude;
+mod swiss;
+
+pub use arrangement::*;
+pub use becks_crew::Id;
+pub use group::*;
+pub use swiss::*;
@@ -0,0 +1,4 @@
+pub(crate) use becks_crew::*;
+pub(crate) use becks_match::Match;
+pub(crate) use serde::{Deserialize, Serialize};
+pub(crate) use thiserror::Error;
@@ -0,0 +1,509 @@
+use crate::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// The most steps tried when pairing without rematches, before rematches are allowed
+const PAIR_BUDGET: usize = 10000;
+
+#[derive(Debug, Error)]
+pub enum SwissError {
+    #[error("a Swiss event needs at least 2 crews")]
+    TooFewCrews,
+    #[error("crew {0:?} is entered more than once")]
+    DuplicateCrew(Id),
+}
+
+/// A crew in a Swiss event, with its score when the event is created
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct SwissPlayer {
+    pub crew: Id,
+    pub score: Score,
+}
+
+/// A pairing of a Swiss round, where a crew without an opponent has a bye
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct SwissPairing {
+    pub left: Id,
+    pub right: Option<Id>,
+    /// The recorded match of the pairing, once it is played
+    #[serde(default)]
+    pub mat: Option<Id>,
+    /// Whether the left crew wins, once the match is recorded
+    #[serde(default)]
+    pub left_win: Option<bool>,
+}
+
+impl SwissPairing {
+    pub fn is_bye(&self) -> bool {
+        self.right.is_none()
+    }
+
+    /// Returns whether the result is known, where a bye needs no match
+    pub fn is_done(&self) -> bool {
+        self.is_bye() || self.left_win.is_some()
+    }
+
+    /// Returns the winner, where a bye counts as a win
+    fn winner(&self) -> Option<Id> {
+        match self.right {
+            None => Some(self.left),
+            Some(right) => self
+                .left_win
+                .map(|left_win| if left_win { self.left } else { right }),
+        }
+    }
+}
+
+/// The record of a crew, where Buchholz sums the points of all opponents
+/// and Sonneborn-Berger sums the points of the opponents beaten
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwissStanding {
+    pub crew: Id,
+    pub points: u32,
+    pub buchholz: u32,
+    pub sonneborn_berger: u32,
+}
+
+/// Rounds of crews paired by their records, where crews that have met are not paired again
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Swiss {
+    pub players: Vec<SwissPlayer>,
+    /// Pairs of crews that have met, which are not paired again as long as possible
+    met: BTreeSet<(Id, Id)>,
+    pub rounds: Vec<Vec<SwissPairing>>,
+    /// When the event is created, so that only matches after it are linked
+    pub timestamp: u64,
+    #[serde(default)]
+    pub closed: bool,
+}
+
+fn meeting(lhs: Id, rhs: Id) -> (Id, Id) {
+    (lhs.min(rhs), lhs.max(rhs))
+}
+
+impl Swiss {
+    /// Creates a Swiss event of the crews, where the history gives the crews that have met;
+    /// No round is paired yet
+    pub fn new(
+        players: Vec<SwissPlayer>,
+        history: impl IntoIterator<Item = (Id, Id)>,
+        timestamp: u64,
+    ) -> Result<Self, SwissError> {
+        if players.len() < 2 {
+            return Err(SwissError::TooFewCrews);
+        }
+        let mut crews = HashSet::new();
+        for player in players.iter() {
+            if !crews.insert(player.crew) {
+                return Err(SwissError::DuplicateCrew(player.crew));
+            }
+        }
+        Ok(Self {
+            players,
+            met: history
+                .into_iter()
+                .filter(|(lhs, rhs)| lhs != rhs)
+                .map(|(lhs, rhs)| meeting(lhs, rhs))
+                .collect(),
+            rounds: Vec::new(),
+            timestamp,
+            closed: false,
+        })
+    }
+
+    /// Returns every crew in the event
+    pub fn crews(&self) -> impl Iterator<Item = Id> + '_ {
+        self.players.iter().map(|player| player.crew)
+    }
+
+    fn points(&self) -> HashMap<Id, u32> {
+        let mut points = HashMap::new();
+        for winner in self
+            .rounds
+            .iter()
+            .flatten()
+            .filter_map(SwissPairing::winner)
+        {
+            *points.entry(winner).or_default() += 1;
+        }
+        points
+    }
+
+    /// Returns whether every pairing of the latest round has its result
+    pub fn is_round_done(&self) -> bool {
+        self.rounds
+            .last()
+            .is_none_or(|round| round.iter().all(SwissPairing::is_done))
+    }
+
+    /// Pairs the crews in order, each with the highest crew below it that it has not met,
+    /// going back to an earlier choice when the rest cannot be paired
+    fn pair(&self, crews: &[Id], budget: &mut usize) -> Option<Vec<(Id, Id)>> {
+        let Some((first, rest)) = crews.split_first() else {
+            return Some(Vec::new());
+        };
+        for (index, other) in rest.iter().enumerate() {
+            if *budget == 0 {
+                return None;
+            }
+            *budget -= 1;
+            if self.met.contains(&meeting(*first, *other)) {
+                continue;
+            }
+            let mut remaining = rest.to_vec();
+            remaining.remove(index);
+            if let Some(mut pairs) = self.pair(&remaining, budget) {
+                pairs.insert(0, (*first, *other));
+                return Some(pairs);
+            }
+        }
+        None
+    }
+
+    /// Pairs the next round if the latest round is done, returns whether a round is paired;
+    /// Crews of similar points meet, and if rematches cannot be avoided, neighbours are paired
+    pub fn pair_next(&mut self) -> bool {
+        if self.closed || !self.is_round_done() {
+            return false;
+        }
+        let points = self.points();
+        let mut order = self.players.clone();
+        order.sort_by_key(|player| {
+            (
+                Reverse(points.get(&player.crew).copied().unwrap_or_default()),
+                Reverse(player.score.0),
+            )
+        });
+        let mut crews = order.iter().map(|player| player.crew).collect::<Vec<_>>();
+        // The lowest crew that has not had a bye sits out
+        let bye = (crews.len() % 2 == 1).then(|| {
+            let had_bye = self
+                .rounds
+                .iter()
+                .flatten()
+                .filter(|pairing| pairing.is_bye())
+                .map(|pairing| pairing.left)
+                .collect::<HashSet<_>>();
+            let index = crews
+                .iter()
+                .rposition(|crew| !had_bye.contains(crew))
+                .unwrap_or(crews.len() - 1);
+            crews.remove(index)
+        });
+        let mut budget = PAIR_BUDGET;
+        let pairs = self
+            .pair(&crews, &mut budget)
+            .unwrap_or_else(|| crews.chunks(2).map(|pair| (pair[0], pair[1])).collect());
+        let mut round = Vec::new();
+        for (left, right) in pairs {
+            self.met.insert(meeting(left, right));
+            round.push(SwissPairing {
+                left,
+                right: Some(right),
+                mat: None,
+                left_win: None,
+            });
+        }
+        round.extend(bye.map(|left| SwissPairing {
+            left,
+            right: None,
+            mat: None,
+            left_win: None,
+        }));
+        self.rounds.push(round);
+        true
+    }
+
+    /// Gives the result of the match to the earliest pairing of its crews without a result
+    fn put_result(&mut self, id: Id, mat: &Match) -> bool {
+        let Some(left_won) = mat.left_won().filter(|_| !mat.is_doubles()) else {
+            return false;
+        };
+        let pairing = self.rounds.iter_mut().flatten().find(|pairing| {
+            !pairing.is_done()
+                && pairing.right.is_some_and(|right| {
+                    meeting(pairing.left, right) == meeting(mat.left, mat.right)
+                })
+        });
+        if let Some(pairing) = pairing {
+            pairing.mat = Some(id);
+            pairing.left_win = Some(left_won == (pairing.left == mat.left));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Links a recorded singles match to the pairing of its crews that has no result,
+    /// returns whether it is linked; Closed events, matches already linked and matches
+    /// played before the event is created are ignored
+    pub fn link(&mut self, id: Id, mat: &Match) -> bool {
+        if self.closed
+            || mat.timestamp < self.timestamp
+            || self
+                .rounds
+                .iter()
+                .flatten()
+                .any(|pairing| pairing.mat == Some(id))
+        {
+            return false;
+        }
+        self.put_result(id, mat)
+    }
+
+    /// Links the recorded match again after it is voided or modified, where `mat` is the match
+    /// as it is now, or None if it no longer counts; Returns whether the event is changed
+    ///
+    /// Only matches linked before are amended, and closed events are amended as well,
+    /// so that no standing keeps the result of a match that no longer counts
+    pub fn amend(&mut self, id: Id, mat: Option<&Match>) -> bool {
+        let Some(pairing) = self
+            .rounds
+            .iter_mut()
+            .flatten()
+            .find(|pairing| pairing.mat == Some(id))
+        else {
+            return false;
+        };
+        pairing.mat = None;
+        pairing.left_win = None;
+        if let Some(mat) = mat.filter(|mat| mat.timestamp >= self.timestamp) {
+            self.put_result(id, mat);
+        }
+        true
+    }
+
+    /// Returns the standings sorted by points, Buchholz and Sonneborn-Berger,
+    /// where crews of the same record keep the order of their scores
+    pub fn standings(&self) -> Vec<SwissStanding> {
+        let points = self.points();
+        let points_of = |crew: &Id| points.get(crew).copied().unwrap_or_default();
+        let mut players = self.players.clone();
+        players.sort_by_key(|player| Reverse(player.score.0));
+        let mut standings = players
+            .iter()
+            .map(|player| {
+                let mut standing = SwissStanding {
+                    crew: player.crew,
+                    points: points_of(&player.crew),
+                    buchholz: 0,
+                    sonneborn_berger: 0,
+                };
+                for pairing in self.rounds.iter().flatten() {
+                    let Some(right) = pairing.right else {
+                        continue;
+                    };
+                    let opponent = if pairing.left == player.crew {
+                        right
+                    } else if right == player.crew {
+                        pairing.left
+                    } else {
+                        continue;
+                    };
+                    standing.buchholz += points_of(&opponent);
+                    if pairing.winner() == Some(player.crew) {
+                        standing.sonneborn_berger += points_of(&opponent);
+                    }
+                }
+                standing
+            })
+            .collect::<Vec<_>>();
+        standings.sort_by_key(|standing| {
+            Reverse((
+                standing.points,
+                standing.buchholz,
+                standing.sonneborn_berger,
+            ))
+        });
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use becks_match::Round;
+
+    fn crew(id: u32) -> Id {
+        Id::from_prim(id)
+    }
+
+    /// Crews 1 to `count`, where a lower id has a higher score
+    fn swiss(count: u32, history: Vec<(u32, u32)>) -> Swiss {
+        let players = (1..=count)
+            .map(|id| SwissPlayer {
+                crew: crew(id),
+                score: Score(1000 - id as i32),
+            })
+            .collect();
+        let history = history.into_iter().map(|(lhs, rhs)| (crew(lhs), crew(rhs)));
+        Swiss::new(players, history, 0).unwrap()
+    }
+
+    fn beat(winner: u32, loser: u32) -> Match {
+        let mut mat = Match::new(1, crew(winner), crew(loser), 0);
+        mat.rounds = vec![Round::new(true)];
+        mat
+    }
+
+    fn pairs(round: &[SwissPairing]) -> Vec<(u32, Option<u32>)> {
+        round
+            .iter()
+            .map(|pairing| {
+                (
+                    pairing.left.to_prim(),
+                    pairing.right.map(|right| right.to_prim()),
+                )
+            })
+            .collect()
+    }
+
+    /// Plays the latest round, where the crew of the lower id wins
+    fn play_round(swiss: &mut Swiss, next_id: &mut u32) {
+        let round = swiss.rounds.last().unwrap().clone();
+        for pairing in round {
+            if let Some(right) = pairing.right {
+                let (winner, loser) = (pairing.left.min(right), pairing.left.max(right));
+                assert!(swiss.link(crew(*next_id), &beat(winner.to_prim(), loser.to_prim())));
+                *next_id += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(matches!(
+            Swiss::new(Vec::new(), [], 0),
+            Err(SwissError::TooFewCrews)
+        ));
+        let player = SwissPlayer {
+            crew: crew(1),
+            score: Score(500),
+        };
+        assert!(matches!(
+            Swiss::new(vec![player, player], [], 0),
+            Err(SwissError::DuplicateCrew(_))
+        ));
+    }
+
+    #[test]
+    fn test_pair_without_rematches() {
+        // Six crews meet at most once in five rounds, which makes a round robin
+        let mut swiss = swiss(6, Vec::new());
+        let mut next_id = 100;
+        let mut met = HashSet::new();
+        for _ in 0..5 {
+            assert!(swiss.pair_next());
+            assert!(!swiss.pair_next());
+            for pairing in swiss.rounds.last().unwrap() {
+                let right = pairing.right.unwrap();
+                assert!(met.insert(meeting(pairing.left, right)));
+            }
+            play_round(&mut swiss, &mut next_id);
+        }
+        assert_eq!(met.len(), 15);
+    }
+
+    #[test]
+    fn test_pair_history() {
+        // Crews that have met before the event are not paired in the first round
+        let mut swiss = swiss(4, vec![(2, 1)]);
+        assert!(swiss.pair_next());
+        assert_eq!(pairs(&swiss.rounds[0]), vec![(1, Some(3)), (2, Some(4))]);
+    }
+
+    #[test]
+    fn test_pair_backtracking() {
+        // 1 takes 2 first, but then 3 and 4 would meet again, so 1 takes 3 instead
+        let mut swiss = swiss(4, vec![(3, 4)]);
+        assert!(swiss.pair_next());
+        assert_eq!(pairs(&swiss.rounds[0]), vec![(1, Some(3)), (2, Some(4))]);
+    }
+
+    #[test]
+    fn test_pair_rematch_fallback() {
+        // No pairing avoids every rematch, so neighbours meet again
+        let mut swiss = swiss(4, vec![(1, 2), (1, 3), (1, 4)]);
+        assert!(swiss.pair_next());
+        assert_eq!(pairs(&swiss.rounds[0]), vec![(1, Some(2)), (3, Some(4))]);
+    }
+
+    #[test]
+    fn test_pair_bye() {
+        // The lowest crew without a bye sits out, and the bye counts as a win
+        let mut swiss = swiss(3, Vec::new());
+        let mut next_id = 100;
+        assert!(swiss.pair_next());
+        assert_eq!(pairs(&swiss.rounds[0]), vec![(1, Some(2)), (3, None)]);
+        play_round(&mut swiss, &mut next_id);
+        assert!(swiss.pair_next());
+        assert_eq!(pairs(&swiss.rounds[1]), vec![(1, Some(3)), (2, None)]);
+        play_round(&mut swiss, &mut next_id);
+        assert!(swiss.pair_next());
+        assert_eq!(pairs(&swiss.rounds[2]), vec![(2, Some(3)), (1, None)]);
+    }
+
+    #[test]
+    fn test_link_and_amend() {
+        let mut swiss = swiss(4, Vec::new());
+        swiss.pair_next();
+        assert!(!swiss.link(crew(100), &beat(1, 3)));
+        assert!(swiss.link(crew(100), &beat(2, 1)));
+        assert!(!swiss.link(crew(100), &beat(2, 1)));
+        assert_eq!(swiss.rounds[0][0].left_win, Some(false));
+        assert!(!swiss.is_round_done());
+        // A flipped result is given to the same pairing
+        assert!(swiss.amend(crew(100), Some(&beat(1, 2))));
+        assert_eq!(swiss.rounds[0][0].mat, Some(crew(100)));
+        assert_eq!(swiss.rounds[0][0].left_win, Some(true));
+        // A voided match leaves its pairing to be played, even when the event is closed
+        swiss.closed = true;
+        assert!(swiss.amend(crew(100), None));
+        assert!(!swiss.rounds[0][0].is_done());
+        assert!(!swiss.amend(crew(100), None));
+        assert!(!swiss.link(crew(101), &beat(1, 2)));
+    }
+
+    #[test]
+    fn test_link_before() {
+        let mut swiss = swiss(4, Vec::new());
+        swiss.timestamp = 10;
+        swiss.pair_next();
+        assert!(!swiss.link(crew(100), &beat(1, 2)));
+        let mut later = beat(1, 2);
+        later.timestamp = 10;
+        assert!(swiss.link(crew(100), &later));
+        // A match moved to before the event no longer decides its pairing
+        assert!(swiss.amend(crew(100), Some(&beat(1, 2))));
+        assert!(!swiss.rounds[0][0].is_done());
+    }
+
+    #[test]
+    fn test_standings() {
+        // Round 1: 1 beats 2, 3 beats 4; Round 2: 1 beats 3, 4 beats 2
+        let mut swiss = swiss(4, Vec::new());
+        swiss.pair_next();
+        swiss.link(crew(100), &beat(1, 2));
+        swiss.link(crew(101), &beat(3, 4));
+        swiss.pair_next();
+        assert_eq!(pairs(&swiss.rounds[1]), vec![(1, Some(3)), (2, Some(4))]);
+        swiss.link(crew(102), &beat(1, 3));
+        swiss.link(crew(103), &beat(4, 2));
+        let standing = |id: u32, points: u32, buchholz: u32, sonneborn_berger: u32| SwissStanding {
+            crew: crew(id),
+            points,
+            buchholz,
+            sonneborn_berger,
+        };
+        // 3 and 4 have the same points, but 3 has met the stronger crews
+        assert_eq!(
+            swiss.standings(),
+            vec![
+                standing(1, 2, 1, 1),
+                standing(3, 1, 3, 1),
+                standing(4, 1, 1, 0),
+                standing(2, 0, 3, 0),
+            ]
+        );
+    }
+}
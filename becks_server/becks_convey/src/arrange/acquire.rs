@@ -0,0 +1,14 @@
+use crate::prelude::*;
+use becks_arrange::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct AcquireRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub arrangement: Id,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AcquireResponse {
+    pub arrangement: Arrangement,
+}
@@ -0,0 +1,15 @@
+use crate::prelude::*;
+use becks_arrange::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    /// The groups with their schedules, where no pairing should be played yet
+    pub groups: Vec<Group>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateResponse {
+    pub arrangement: Id,
+}
@@ -0,0 +1,17 @@
+use crate::prelude::*;
+use becks_arrange::Id;
+
+#[derive(Serialize, Deserialize)]
+pub struct ListRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    /// Whether closed arrangements are listed as well
+    #[serde(default)]
+    pub closed: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ListResponse {
+    /// The arrangements from the latest one
+    pub arrangements: Vec<Id>,
+}
@@ -0,0 +1,4 @@
+pub mod acquire;
+pub mod close;
+pub mod create;
+pub mod list;
@@ -3,7 +3,8 @@ use becks_crew::*;
 
 #[derive(Serialize, Deserialize)]
 pub struct CreateRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub name: String,
     pub social: Social,
 }
@@ -3,6 +3,7 @@ use becks_crew::*;
 
 #[derive(Serialize, Deserialize)]
 pub struct DeleteRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub crew: Id,
 }
@@ -0,0 +1,18 @@
+use crate::prelude::*;
+use becks_crew::*;
+use becks_match::ScorePoint;
+
+#[derive(Serialize, Deserialize)]
+pub struct HistoryRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub crew: Id,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HistoryResponse {
+    /// The score before any match
+    pub initial: i32,
+    /// The score after each match, from the earliest one
+    pub history: Vec<ScorePoint>,
+}
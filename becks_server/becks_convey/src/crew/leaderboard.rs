@@ -0,0 +1,41 @@
+use crate::prelude::*;
+use becks_crew::*;
+
+/// Conditions of the crews to rank, where None matches every crew
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LeaderboardFilter {
+    #[serde(default)]
+    pub social: Option<Social>,
+    #[serde(default)]
+    pub gender: Option<Gender>,
+    #[serde(default)]
+    pub hand: Option<Hand>,
+    /// Crews that have played fewer matches than this are left out
+    #[serde(default)]
+    pub min_played: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LeaderboardRow {
+    pub crew: Id,
+    pub name: String,
+    pub score: Score,
+    /// The rank counting from 1, where crews of the same score share a rank,
+    /// and the crew after them is ranked as if they were not tied
+    pub rank: usize,
+    /// The matches played that are not voided
+    pub played: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LeaderboardRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    #[serde(flatten)]
+    pub filter: LeaderboardFilter,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LeaderboardResponse {
+    pub rows: Vec<LeaderboardRow>,
+}
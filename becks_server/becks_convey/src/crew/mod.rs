@@ -1,4 +1,7 @@
 pub mod create;
 pub mod delete;
+pub mod history;
+pub mod leaderboard;
 pub mod modify;
 pub mod query;
+pub mod stats;
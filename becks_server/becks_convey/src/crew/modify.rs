@@ -1,16 +1,19 @@
 use crate::prelude::*;
 use becks_crew::*;
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ModifyRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub crew: Id,
     pub loc: CrewLocation,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct AcquireRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub crew: Id,
 }
 
@@ -18,3 +21,16 @@ pub struct AcquireRequest {
 pub struct AcquireResponse {
     pub crew: CrewData,
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct AcquireManyRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub crews: Vec<Id>,
+}
+
+/// Maps each found crew to its data, leaving out ids that do not exist
+#[derive(Serialize, Deserialize)]
+pub struct AcquireManyResponse {
+    pub crews: HashMap<Id, CrewData>,
+}
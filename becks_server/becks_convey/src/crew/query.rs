@@ -1,15 +1,21 @@
+use crate::page::{PageInfo, PageRequest};
 use crate::prelude::*;
 use becks_crew::*;
 
 #[derive(Serialize, Deserialize)]
 pub struct QueryByRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub by: Vec<CrewLocation>,
     #[serde(default)]
     pub fuzzy: bool,
+    #[serde(flatten)]
+    pub page: PageRequest,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct QueryByResponse {
     pub ids: Vec<Id>,
+    #[serde(flatten)]
+    pub page: PageInfo,
 }
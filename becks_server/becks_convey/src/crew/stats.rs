@@ -0,0 +1,18 @@
+use crate::prelude::*;
+use becks_crew::*;
+use becks_match::CrewStats;
+
+#[derive(Serialize, Deserialize)]
+pub struct StatsRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub crew: Id,
+    /// The crew to give the head-to-head record against
+    #[serde(default)]
+    pub opponent: Option<Id>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StatsResponse {
+    pub stats: CrewStats,
+}
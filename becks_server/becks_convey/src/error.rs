@@ -0,0 +1,76 @@
+use crate::prelude::*;
+
+/// A stable, machine-readable code of an error returned by the server
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// The request body cannot be parsed
+    MalformedRequest,
+    /// A name, password or other input contains illegal characters
+    IllegalInput,
+    /// No token is given with the request
+    MissingToken,
+    /// The token is invalid, expired or revoked
+    InvalidToken,
+    /// The role of the user is not sufficient for the operation
+    Forbidden,
+    /// The name or password is wrong
+    WrongCredentials,
+    /// The user name is already taken
+    UserConflict,
+    /// The reset code is wrong or expired
+    InvalidResetCode,
+    /// The session is not found
+    SessionNotFound,
+    /// The member is not found in the club
+    MemberNotFound,
+    /// The role cannot be granted to the user
+    GrantRejected,
+    /// The crew is not found
+    CrewNotFound,
+    /// The match is not found
+    MatchNotFound,
+    /// The poster is not found
+    PosterNotFound,
+    /// The tournament is not found
+    TournamentNotFound,
+    /// The Swiss event is not found
+    SwissNotFound,
+    /// The arrangement is not found
+    ArrangementNotFound,
+    /// The rounds of the match do not agree with the declared total
+    MatchIncomplete,
+    /// The points of a round break the rules of table tennis
+    InvalidPoints,
+    /// The field cannot be modified to the given value
+    InvalidModification,
+    /// The server fails unexpectedly
+    Internal,
+    /// A code unknown to this version
+    #[serde(other)]
+    Unknown,
+}
+
+/// The body of every error response of the server
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    /// A human-readable description, which is not meant to be matched against
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
@@ -1,5 +1,10 @@
+pub mod arrange;
 pub mod crew;
+pub mod error;
 pub mod mat;
+pub mod page;
 pub mod poster;
 mod prelude;
+pub mod swiss;
+pub mod tournament;
 pub mod user;
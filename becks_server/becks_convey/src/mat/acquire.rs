@@ -1,9 +1,11 @@
 use crate::prelude::*;
 use becks_match::*;
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize)]
 pub struct AcquireRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub mat: Id,
 }
 
@@ -11,3 +13,15 @@ pub struct AcquireRequest {
 pub struct AcquireResponse {
     pub mat: Match,
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct AcquireManyRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub mats: Vec<Id>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AcquireManyResponse {
+    pub mats: HashMap<Id, Match>,
+}
@@ -3,7 +3,8 @@ use becks_match::*;
 
 #[derive(Serialize, Deserialize)]
 pub struct CreateRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub mat: Match,
 }
 
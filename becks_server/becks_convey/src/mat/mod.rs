@@ -2,4 +2,5 @@ pub mod acquire;
 pub mod create;
 pub mod modify;
 pub mod query;
-
+pub mod replay;
+pub mod void;
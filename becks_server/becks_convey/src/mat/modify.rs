@@ -1,9 +1,54 @@
 use crate::prelude::*;
 use becks_match::*;
 
+pub use super::replay::ScoreDiff;
+
+/// The fields of a match to modify, where fields that are not given are kept
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct MatchModification {
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub rounds: Option<Vec<Round>>,
+    #[serde(default)]
+    pub total_rounds: Option<usize>,
+    #[serde(default)]
+    pub quit: Option<Quit>,
+    #[serde(default)]
+    pub left: Option<Id>,
+    #[serde(default)]
+    pub right: Option<Id>,
+    /// The partners of the left and right side, where `None` of a side makes it singles
+    #[serde(default)]
+    pub partners: Option<[Option<Id>; 2]>,
+    #[serde(default)]
+    pub timestamp: Option<u64>,
+}
+
+impl MatchModification {
+    /// Returns whether the modification changes how the match affects scores
+    pub fn affects_score(&self) -> bool {
+        self.rounds.is_some()
+            || self.total_rounds.is_some()
+            || self.quit.is_some()
+            || self.left.is_some()
+            || self.right.is_some()
+            || self.partners.is_some()
+            || self.timestamp.is_some()
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ModifyRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub mat: Id,
-    pub notes: String,
+    #[serde(flatten)]
+    pub modify: MatchModification,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ModifyResponse {
+    /// Crews whose score changes, sorted from the most gained to the most lost
+    pub diffs: Vec<ScoreDiff>,
 }
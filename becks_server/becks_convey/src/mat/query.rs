@@ -1,8 +1,9 @@
+use crate::page::{PageInfo, PageRequest};
 use crate::prelude::*;
 use becks_match::*;
 use std::ops::{Deref, DerefMut};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum QueryMatchBy {
     Player(Id),
     Note(String),
@@ -11,8 +12,14 @@ pub enum QueryMatchBy {
 
 #[derive(Serialize, Deserialize)]
 pub struct QueryRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub by: Vec<QueryMatchBy>,
+    /// Whether voided matches are included
+    #[serde(default)]
+    pub include_voided: bool,
+    #[serde(flatten)]
+    pub page: PageRequest,
 }
 impl Deref for QueryRequest {
     type Target = Vec<QueryMatchBy>;
@@ -29,4 +36,6 @@ impl DerefMut for QueryRequest {
 #[derive(Serialize, Deserialize)]
 pub struct QueryResponse {
     pub ids: Vec<Id>,
+    #[serde(flatten)]
+    pub page: PageInfo,
 }
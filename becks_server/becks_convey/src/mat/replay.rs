@@ -0,0 +1,29 @@
+use crate::prelude::*;
+use becks_crew::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct ReplayRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    /// Reports the changes without applying them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// The score of a crew before and after replaying
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScoreDiff {
+    pub crew: Id,
+    pub name: String,
+    pub before: Score,
+    pub after: Score,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReplayResponse {
+    pub applied: bool,
+    /// The number of replayed matches
+    pub matches: usize,
+    /// Crews whose score changes, sorted from the most gained to the most lost
+    pub diffs: Vec<ScoreDiff>,
+}
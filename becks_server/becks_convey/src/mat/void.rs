@@ -0,0 +1,17 @@
+use crate::prelude::*;
+use becks_match::*;
+
+pub use super::replay::ScoreDiff;
+
+#[derive(Serialize, Deserialize)]
+pub struct VoidRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub mat: Id,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct VoidResponse {
+    /// Crews whose score changes, sorted from the most gained to the most lost
+    pub diffs: Vec<ScoreDiff>,
+}
@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+/// Selects a page of query results, flattened into query requests
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PageRequest {
+    /// The number of matching results to skip
+    #[serde(default)]
+    pub offset: usize,
+    /// The largest number of results to return, where the server decides if not given
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Describes where a page lies in all query results, flattened into query responses
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PageInfo {
+    /// The number of all matching results, across all pages
+    #[serde(default)]
+    pub total: usize,
+    /// The offset of the next page, or None if this is the last page
+    #[serde(default)]
+    pub next: Option<usize>,
+}
@@ -1,9 +1,11 @@
 use crate::prelude::*;
 use becks_poster::*;
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize)]
 pub struct AcquireRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub poster: Id,
 }
 
@@ -11,3 +13,15 @@ pub struct AcquireRequest {
 pub struct AcquireResponse {
     pub poster: Poster,
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct AcquireManyRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub posters: Vec<Id>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AcquireManyResponse {
+    pub posters: HashMap<Id, Poster>,
+}
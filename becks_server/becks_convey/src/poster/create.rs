@@ -3,7 +3,8 @@ use becks_poster::*;
 
 #[derive(Serialize, Deserialize)]
 pub struct CreateRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub value: String,
     pub images: Vec<String>,
 }
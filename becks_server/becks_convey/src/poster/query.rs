@@ -1,8 +1,9 @@
+use crate::page::{PageInfo, PageRequest};
 use crate::prelude::*;
 use becks_poster::*;
 use std::ops::{Deref, DerefMut};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum QueryPosterBy {
     Content(String),
     Time { mid: u64, error: u64 },
@@ -10,8 +11,11 @@ pub enum QueryPosterBy {
 
 #[derive(Serialize, Deserialize)]
 pub struct QueryRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
     pub by: Vec<QueryPosterBy>,
+    #[serde(flatten)]
+    pub page: PageRequest,
 }
 impl Deref for QueryRequest {
     type Target = Vec<QueryPosterBy>;
@@ -28,4 +32,6 @@ impl DerefMut for QueryRequest {
 #[derive(Serialize, Deserialize)]
 pub struct QueryResponse {
     pub ids: Vec<Id>,
+    #[serde(flatten)]
+    pub page: PageInfo,
 }
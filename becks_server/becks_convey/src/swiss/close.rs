@@ -0,0 +1,14 @@
+use crate::prelude::*;
+use becks_arrange::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct CloseRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub swiss: Id,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CloseResponse {
+    pub swiss: Swiss,
+}
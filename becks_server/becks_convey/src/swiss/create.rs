@@ -0,0 +1,18 @@
+use crate::prelude::*;
+use becks_arrange::Id;
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub crews: Vec<Id>,
+    /// Crews that have played a match since this timestamp are not paired again
+    /// as long as possible
+    #[serde(default)]
+    pub since: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateResponse {
+    pub swiss: Id,
+}
@@ -0,0 +1,5 @@
+pub mod acquire;
+pub mod close;
+pub mod create;
+pub mod list;
+pub mod next;
@@ -0,0 +1,15 @@
+use crate::prelude::*;
+use becks_arrange::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct NextRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub swiss: Id,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct NextResponse {
+    /// The event with the next round paired
+    pub swiss: Swiss,
+}
@@ -0,0 +1,18 @@
+use crate::prelude::*;
+use becks_tournament::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct AcquireRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub tournament: Id,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AcquireResponse {
+    pub tournament: Tournament,
+    /// The standings of each group in a round robin, which is empty for brackets
+    pub standings: Vec<Vec<Standing>>,
+    /// The winner of a finished bracket
+    pub champion: Option<Id>,
+}
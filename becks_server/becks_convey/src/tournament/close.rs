@@ -0,0 +1,14 @@
+use crate::prelude::*;
+use becks_tournament::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct CloseRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub tournament: Id,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CloseResponse {
+    pub tournament: Tournament,
+}
@@ -0,0 +1,17 @@
+use crate::prelude::*;
+use becks_tournament::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub name: String,
+    pub format: Format,
+    /// The crews to enter, which are seeded by their scores
+    pub crews: Vec<Id>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateResponse {
+    pub tournament: Id,
+}
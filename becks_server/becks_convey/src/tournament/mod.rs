@@ -0,0 +1,3 @@
+pub mod acquire;
+pub mod close;
+pub mod create;
@@ -1,30 +1,46 @@
 use crate::prelude::*;
-use std::num::NonZeroU128;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
-pub struct Token(NonZeroU128);
+/// A signed session token, which should be sent in the `Authorization: Bearer` header;
+/// The `token` fields in request bodies are deprecated and only kept as a fallback
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[serde(transparent)]
+pub struct Token(String);
 
 impl Token {
-    pub fn new(value: NonZeroU128) -> Self {
+    pub fn new(value: String) -> Self {
         Self(value)
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct LoginRequest {
     pub name: String,
     pub pass: String,
+    /// The owner of the club database to log in to, or the own database if not given
+    #[serde(default)]
+    pub club: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct LoginResponse {
     pub token: Token,
+    pub role: super::member::Role,
 }
 
-/// This is implemented as [`Copy`] so that it is callable from within the server
-#[derive(Serialize, Deserialize, Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct LogoutRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -2,5 +2,6 @@ use crate::prelude::*;
 
 #[derive(Serialize, Deserialize)]
 pub struct UpdateRequest {
-    pub token: Token,
+    #[serde(default)]
+    pub token: Option<Token>,
 }
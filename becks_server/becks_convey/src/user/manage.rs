@@ -0,0 +1,36 @@
+use crate::prelude::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct PasswordRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeleteRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub pass: String,
+}
+
+/// Issued by an administrator to generate a one-time reset code for the named user
+#[derive(Serialize, Deserialize)]
+pub struct IssueResetRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IssueResetResponse {
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ResetRequest {
+    pub name: String,
+    pub code: String,
+    pub pass: String,
+}
@@ -0,0 +1,65 @@
+use crate::prelude::*;
+
+/// The role of an account on a club database, ordered by increasing rights
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Can only read the database
+    Viewer,
+    /// Can also create and modify matches and posters
+    Recorder,
+    /// Can also create, modify and delete crew
+    Admin,
+    /// Owns the database, and can grant roles to other accounts
+    Owner,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MemberInfo {
+    pub name: String,
+    pub role: Role,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ClubInfo {
+    pub owner: String,
+    pub role: Role,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MembersRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MembersResponse {
+    pub members: Vec<MemberInfo>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ClubsRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ClubsResponse {
+    pub clubs: Vec<ClubInfo>,
+}
+
+/// Grants a role on the club of the requesting owner, replacing any existing role
+#[derive(Serialize, Deserialize)]
+pub struct GrantRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub member: String,
+    pub role: Role,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RevokeMemberRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub member: String,
+}
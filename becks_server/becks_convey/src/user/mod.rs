@@ -1,2 +1,5 @@
 pub mod auth;
 pub mod clean;
+pub mod manage;
+pub mod member;
+pub mod session;
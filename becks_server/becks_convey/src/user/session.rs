@@ -0,0 +1,30 @@
+use crate::prelude::*;
+
+#[derive(Serialize, Deserialize)]
+pub struct SessionsRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+}
+
+/// A session of the user, with timestamps in seconds since the unix epoch
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    pub id: String,
+    pub issued: u64,
+    pub expiry: u64,
+    pub last_seen: u64,
+    /// Whether this is the session making the request
+    pub current: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SessionsResponse {
+    pub sessions: Vec<SessionInfo>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RevokeSessionRequest {
+    #[serde(default)]
+    pub token: Option<Token>,
+    pub session: String,
+}
@@ -0,0 +1,267 @@
+use crate::prelude::*;
+use crate::session::current_timestamp;
+use crate::Db;
+
+impl Db {
+    /// Overwrites the stored password of the user with the hash of the given one
+    fn set_pass(&self, name: &str, pass: &str) -> bool {
+        let Some(hash) = crate::pass::hash_pass(pass) else {
+            return false;
+        };
+        self.user()
+            .execute(
+                "UPDATE user SET pass = ?1 WHERE name = ?2",
+                [hash.as_str(), name],
+            )
+            .inspect_err(|err| {
+                error!("When setting password of user {}, {}", name, err);
+            })
+            .is_ok_and(|modified| modified > 0)
+    }
+
+    /// Changes the password of the user, revoking its sessions other than the current one;
+    /// Returns true on success
+    pub fn change_pass(&self, name: &str, old: &str, new: &str, current: Option<&Token>) -> bool {
+        check!(alnum new);
+        if self.verify(name, old) && self.set_pass(name, new) {
+            info!("Changed password of user {}", name);
+            self.revoke_user(name, current);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Deletes the user after checking the password, logging it out and
+    /// archiving or removing its database file; Returns true on success
+    pub fn delete(&self, name: &str, pass: &str) -> bool {
+        if !self.verify(name, pass) {
+            return false;
+        }
+        let deleted = self
+            .user()
+            .execute("DELETE FROM user WHERE name = ?1", [name])
+            .inspect_err(|err| {
+                error!("When deleting user {}, {}", name, err);
+            })
+            .is_ok_and(|modified| modified > 0);
+        if !deleted {
+            return false;
+        }
+        // Tokens of the deleted user should not be accepted if the name is registered again
+        self.revoke_user(name, None);
+        self.user()
+            .execute("DELETE FROM reset WHERE name = ?1", [name])
+            .inspect_err(|err| {
+                error!("When deleting reset records of user {}, {}", name, err);
+            })
+            .ok();
+        self.user()
+            .execute("DELETE FROM member WHERE owner = ?1 OR member = ?1", [name])
+            .inspect_err(|err| {
+                error!("When deleting memberships of user {}, {}", name, err);
+            })
+            .ok();
+
+        let archive = current_timestamp();
+        if CONFIG.db.archive_deleted {
+            info!("Archiving database of user {}", name);
+        } else {
+            info!("Removing database of user {}", name);
+        }
+        // The write-ahead log is normally merged when the last connection closes,
+        // but is moved along with the database in case a connection is still open
+        for suffix in ["", "-wal", "-shm"] {
+            let path = CONFIG.db.user_base.join(format!("{}.db{}", name, suffix));
+            if suffix.is_empty() || path.exists() {
+                if CONFIG.db.archive_deleted {
+                    let to = CONFIG
+                        .db
+                        .user_base
+                        .join(format!("{}.{}.deleted.db{}", name, archive, suffix));
+                    std::fs::rename(&path, &to)
+                } else {
+                    std::fs::remove_file(&path)
+                }
+                .inspect_err(|err| {
+                    warn!("When cleaning up user database {:?}, {}", path, err);
+                })
+                .ok();
+            }
+        }
+        true
+    }
+
+    /// Issues a one-time reset code for the user, if the issuer is an administrator
+    pub fn issue_reset(&self, admin: &str, name: &str) -> Option<String> {
+        if !CONFIG.user.admins.iter().any(|value| value == admin) {
+            warn!("User {} is not allowed to issue reset codes", admin);
+            return None;
+        }
+        self.user()
+            .query_row("SELECT name FROM user WHERE name = ?1", [name], |row| {
+                row.get::<_, String>(0)
+            })
+            .inspect_err(|err| {
+                warn!("When issuing reset code for user {}, {}", name, err);
+            })
+            .ok()?;
+        let code = format!("{:016x}", rand::random::<u64>());
+        let hash = crate::pass::hash_pass(&code)?;
+        let expiry = current_timestamp() + CONFIG.user.reset_timeout.as_secs();
+        self.user()
+            .execute(
+                "INSERT OR REPLACE INTO reset (name, code, expiry) VALUES (?1, ?2, ?3)",
+                rusqlite::params![name, hash, expiry],
+            )
+            .inspect_err(|err| {
+                error!("When storing reset code for user {}, {}", name, err);
+            })
+            .ok()?;
+        info!("User {} issued a reset code for user {}", admin, name);
+        Some(code)
+    }
+
+    /// Resets the password of the user with a previously issued code,
+    /// which is consumed on success; Returns true on success
+    pub fn reset_pass(&self, name: &str, code: &str, pass: &str) -> bool {
+        check!(alnum pass);
+        let target = self.user().query_row(
+            "SELECT code, expiry FROM reset WHERE name = ?1",
+            [name],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?)),
+        );
+        let Ok((target, expiry)) = target else {
+            warn!("No reset code is issued for user {}", name);
+            return false;
+        };
+        if expiry < current_timestamp() {
+            warn!("Reset code for user {} has expired", name);
+            return false;
+        }
+        if crate::pass::verify_pass(code, &target) != crate::pass::Verified::Hashed {
+            warn!("Reset code is wrong for user {}", name);
+            return false;
+        }
+        self.user()
+            .execute("DELETE FROM reset WHERE name = ?1", [name])
+            .inspect_err(|err| {
+                error!("When consuming reset code of user {}, {}", name, err);
+            })
+            .ok();
+        if !self.set_pass(name, pass) {
+            return false;
+        }
+        info!("Reset password of user {}", name);
+        self.revoke_user(name, None);
+        true
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Removes the database files the user leaves in the user base, archived or not
+    pub(crate) fn clean_up(name: &str) {
+        let Ok(entries) = std::fs::read_dir(&CONFIG.db.user_base) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let file = entry.file_name();
+            let file = file.to_string_lossy();
+            if file.starts_with(&format!("{}.", name)) && file.contains(".db") {
+                std::fs::remove_file(entry.path()).ok();
+            }
+        }
+    }
+
+    /// Creates the user and logs it in twice, as if from two devices
+    fn two_sessions(db: &Db, name: &str) -> (Token, Token) {
+        clean_up(name);
+        assert!(db.create(name, "old"));
+        let (first, _) = db.log_in(name, "old", None).unwrap();
+        let (second, _) = db.log_in(name, "old", None).unwrap();
+        (first, second)
+    }
+
+    #[test]
+    fn test_change_pass() {
+        let db = Db::in_memory();
+        let name = "accountchange";
+        let (first, second) = two_sessions(&db, name);
+        assert!(!db.change_pass(name, "wrong", "new", Some(&first)));
+        assert!(db.get_login(&second).is_some());
+
+        assert!(db.change_pass(name, "old", "new", Some(&first)));
+        assert!(db.get_login(&first).is_some());
+        assert!(db.get_login(&second).is_none());
+        assert_eq!(db.sessions(name, Some(&first)).len(), 1);
+        assert!(db.log_in(name, "old", None).is_none());
+        assert!(db.log_in(name, "new", None).is_some());
+        db.login_map_mut().remove_user(name);
+        clean_up(name);
+    }
+
+    #[test]
+    fn test_reset_pass() {
+        let db = Db::in_memory();
+        let name = "accountreset";
+        let (first, second) = two_sessions(&db, name);
+        assert!(db.issue_reset(name, name).is_none());
+        assert!(!db.reset_pass(name, "code", "new"));
+
+        let insert = |code: &str, expiry: u64| {
+            db.user()
+                .execute(
+                    "INSERT OR REPLACE INTO reset (name, code, expiry) VALUES (?1, ?2, ?3)",
+                    rusqlite::params![name, crate::pass::hash_pass(code).unwrap(), expiry],
+                )
+                .unwrap();
+        };
+        insert("code", 0);
+        assert!(!db.reset_pass(name, "code", "new"));
+        insert("code", current_timestamp() + 60);
+        assert!(!db.reset_pass(name, "wrong", "new"));
+        assert!(db.get_login(&first).is_some());
+
+        assert!(db.reset_pass(name, "code", "new"));
+        assert!(db.get_login(&first).is_none());
+        assert!(db.get_login(&second).is_none());
+        assert!(db.sessions(name, None).is_empty());
+        // The code is consumed
+        assert!(!db.reset_pass(name, "code", "other"));
+        assert!(db.log_in(name, "new", None).is_some());
+        db.login_map_mut().remove_user(name);
+        clean_up(name);
+    }
+
+    #[test]
+    fn test_delete() {
+        let db = Db::in_memory();
+        let name = "accountdelete";
+        let (first, second) = two_sessions(&db, name);
+        assert!(!db.delete(name, "wrong"));
+        assert!(db.get_login(&first).is_some());
+
+        assert!(db.delete(name, "old"));
+        assert!(!db.exists(name));
+        assert!(db.login_map().is_empty());
+        assert!(!CONFIG.db.user_base.join(format!("{}.db", name)).exists());
+        let archived = std::fs::read_dir(&CONFIG.db.user_base)
+            .unwrap()
+            .flatten()
+            .any(|entry| {
+                let file = entry.file_name();
+                let file = file.to_string_lossy();
+                file.starts_with(&format!("{}.", name)) && file.ends_with(".deleted.db")
+            });
+        assert_eq!(archived, CONFIG.db.archive_deleted);
+
+        // Tokens of the deleted user are not accepted once the name is registered again
+        assert!(db.create(name, "old"));
+        assert!(db.get_login(&first).is_none());
+        assert!(db.get_login(&second).is_none());
+        clean_up(name);
+    }
+}
@@ -4,11 +4,24 @@ use std::sync::LazyLock;
 use std::sync::RwLock;
 
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct Database {
     pub becksman: PathBuf,
     pub user_base: PathBuf,
     pub mat_limit: usize,
     pub beat_limit: usize,
+    /// The number of crews in a query page if the request does not give a limit
+    pub crew_limit: usize,
+    /// The number of posters in a query page if the request does not give a limit
+    pub poster_limit: usize,
+    /// The largest number of results a request may ask for in one query page
+    pub page_limit: usize,
+    /// Whether the database of a deleted user is kept under a renamed file instead of removed
+    pub archive_deleted: bool,
+    /// The number of read connections opened for each database
+    pub readers: usize,
+    /// How long a connection waits for a locked database before failing
+    pub busy_timeout: std::time::Duration,
 }
 impl Default for Database {
     fn default() -> Self {
@@ -17,6 +30,12 @@ impl Default for Database {
             user_base: PathBuf::from("./"),
             mat_limit: 10,
             beat_limit: 10,
+            crew_limit: 50,
+            poster_limit: 20,
+            page_limit: 200,
+            archive_deleted: true,
+            readers: 4,
+            busy_timeout: std::time::Duration::new(5, 0),
         }
     }
 }
@@ -33,16 +52,56 @@ impl Default for Server {
     }
 }
 
+/// The rating system that updates crew scores after matches
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RatingKind {
+    /// Elo with a fixed factor of `round_worth * elo_scaler`
+    #[default]
+    Elo,
+    /// Glicko-2, which tracks the deviation and volatility of each crew
+    Glicko2,
+}
+
+/// How a match is settled when one crew quits
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ForfeitPolicy {
+    /// The crew that stays wins every round of the match
+    #[default]
+    Sweep,
+    /// The rounds played are kept, and the crew that stays wins the remaining rounds,
+    /// but at least one round more than the crew that quits, whose rounds are cut if needed
+    /// so that no more rounds are given than the match has
+    Remaining,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct User {
     pub timeout: std::time::Duration,
+    pub rating: RatingKind,
     pub elo_scaler: f32,
+    /// The system constant of Glicko-2, which limits how fast the volatility changes
+    pub glicko_tau: f64,
+    pub forfeit: ForfeitPolicy,
+    /// Users that are allowed to issue password reset codes
+    pub admins: Vec<String>,
+    pub reset_timeout: std::time::Duration,
+    /// How long a session token stays valid after logging in
+    pub token_lifetime: std::time::Duration,
 }
 impl Default for User {
     fn default() -> Self {
         Self {
             timeout: std::time::Duration::new(60, 0),
+            rating: RatingKind::Elo,
             elo_scaler: 3.0,
+            glicko_tau: 0.5,
+            forfeit: ForfeitPolicy::Sweep,
+            admins: Vec::new(),
+            reset_timeout: std::time::Duration::new(86400, 0),
+            token_lifetime: std::time::Duration::new(86400 * 7, 0),
         }
     }
 }
@@ -1,33 +1,52 @@
+use crate::pool::{Pool, PoolGuard};
 use crate::prelude::*;
 use crate::CONFIG;
-use std::sync::RwLockReadGuard;
-use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+use std::sync::{MutexGuard, RwLock};
+use std::sync::{RwLockReadGuard, RwLockWriteGuard};
 
 pub struct Db {
-    user: Mutex<Connection>,
+    user: Pool,
     login: RwLock<crate::LoginMap>,
+    pub(crate) secret: Vec<u8>,
 }
 
 impl Db {
-    fn user(&self) -> MutexGuard<Connection> {
-        self.user.lock().unwrap()
+    /// Locks the write connection to the main database
+    pub(crate) fn user(&self) -> MutexGuard<Connection> {
+        self.user.write()
+    }
+
+    /// Borrows a read connection to the main database
+    pub(crate) fn user_read(&self) -> PoolGuard {
+        self.user.read()
     }
 
     pub fn connect() -> Self {
         trace!("Connecting to main database");
-        let user = Connection::open(&CONFIG.db.becksman).unwrap_or_else(|err| {
+        let pool = Pool::open(&CONFIG.db.becksman).unwrap_or_else(|err| {
             warn!(
                 "When opening main database {:?}, {}; Opening database in memory",
                 CONFIG.db.becksman, err
             );
-            Connection::open_in_memory().expect("rusqlite should connect to the database")
+            Pool::open_in_memory().expect("rusqlite should connect to the database")
         });
+        Self::with_pool(pool)
+    }
+
+    /// Creates a main database in memory, whose records are lost when it is dropped
+    #[cfg(test)]
+    pub(crate) fn in_memory() -> Self {
+        Self::with_pool(Pool::open_in_memory().expect("rusqlite should connect to the database"))
+    }
+
+    fn with_pool(pool: Pool) -> Self {
         trace!("Initializing the main database");
+        let user = pool.write();
         user.execute(
             indoc! {
                 "CREATE TABLE IF NOT EXISTS user (
                     name VARCHAR(20) PRIMARY KEY,
-                    pass VARCHAR(20)
+                    pass TEXT
                 )"
             },
             [],
@@ -36,10 +55,81 @@ impl Db {
             error!("When initializing user database, {}", err);
         })
         .ok();
-        Self {
-            user: Mutex::new(user),
+        user.execute(
+            indoc! {
+                "CREATE TABLE IF NOT EXISTS reset (
+                    name VARCHAR(20) PRIMARY KEY,
+                    code TEXT,
+                    expiry INTEGER
+                )"
+            },
+            [],
+        )
+        .inspect_err(|err| {
+            error!("When initializing reset database, {}", err);
+        })
+        .ok();
+        user.execute(
+            indoc! {
+                "CREATE TABLE IF NOT EXISTS revoked (
+                    session TEXT PRIMARY KEY,
+                    expiry INTEGER
+                )"
+            },
+            [],
+        )
+        .inspect_err(|err| {
+            error!("When initializing revoked database, {}", err);
+        })
+        .ok();
+        user.execute(
+            indoc! {
+                "CREATE TABLE IF NOT EXISTS session (
+                    id TEXT PRIMARY KEY,
+                    name VARCHAR(20),
+                    club VARCHAR(20),
+                    issued INTEGER,
+                    expiry INTEGER,
+                    last_seen INTEGER
+                )"
+            },
+            [],
+        )
+        .inspect_err(|err| {
+            error!("When initializing session database, {}", err);
+        })
+        .ok();
+        user.execute(
+            indoc! {
+                "CREATE TABLE IF NOT EXISTS member (
+                    owner VARCHAR(20),
+                    member VARCHAR(20),
+                    role TINYINT,
+                    PRIMARY KEY (owner, member)
+                )"
+            },
+            [],
+        )
+        .inspect_err(|err| {
+            error!("When initializing member database, {}", err);
+        })
+        .ok();
+        let secret = crate::session::load_secret(&user);
+        drop(user);
+        let db = Self {
+            user: pool,
             login: Default::default(),
-        }
+            secret,
+        };
+        db.prune_sessions();
+        db
+    }
+
+    /// Returns whether a user with the name exists
+    pub(crate) fn exists(&self, name: &str) -> bool {
+        self.user_read()
+            .query_row("SELECT name FROM user WHERE name = ?1", [name], |_| Ok(()))
+            .is_ok()
     }
 
     /// Attempts to create a new user, return true if a user is created
@@ -56,10 +146,13 @@ impl Db {
             // Replicate users
             false
         } else {
+            let Some(hash) = crate::pass::hash_pass(pass) else {
+                return false;
+            };
             self.user()
                 .execute(
                     "INSERT INTO user (name, pass) values (?1, ?2)",
-                    [name, pass],
+                    [name, hash.as_str()],
                 )
                 .inspect_err(|err| {
                     error!("When creating user, {}", err);
@@ -68,51 +161,74 @@ impl Db {
         }
     }
 
-    /// Attempts to log in to the program, returns [`Some`] if a token is given
-    pub fn log_in(&self, name: &str, pass: &str) -> Option<Token> {
+    /// Attempts to log in to the program on the club database, or the own database if not given;
+    /// Returns [`Some`] with the token and the role of the user on success
+    pub fn log_in(&self, name: &str, pass: &str, club: Option<&str>) -> Option<(Token, Role)> {
         check!(alnum name);
         check!(alnum pass);
-        trace!("Attempt to log in with name {}, password {}", name, pass);
+        trace!("Attempt to log in with name {}", name);
+        if self.verify(name, pass) {
+            let club = club.unwrap_or(name);
+            let Some(role) = self.role(name, club) else {
+                warn!("User {} is not a member of {}", name, club);
+                return None;
+            };
+            Some((self.start_session(name, club)?, role))
+        } else {
+            None
+        }
+    }
+
+    /// Checks the password of the user, returning true if it matches;
+    /// Legacy plaintext passwords are re-hashed on success
+    pub(crate) fn verify(&self, name: &str, pass: &str) -> bool {
+        use crate::pass::Verified;
         let target =
-            self.user()
+            self.user_read()
                 .query_row("SELECT pass FROM user WHERE name = ?1", [name], |row| {
                     row.get::<_, String>(0)
                 });
         if let Ok(target) = target {
-            if target != pass {
-                warn!("Password {} is wrong for user {}", pass, name);
-                return None;
+            match crate::pass::verify_pass(pass, &target) {
+                Verified::Wrong => {
+                    warn!("Password is wrong for user {}", name);
+                    false
+                }
+                Verified::Hashed => true,
+                Verified::Plain => {
+                    self.rehash(name, pass);
+                    true
+                }
             }
-            let mut login = self.login.write().unwrap();
-            let value = crate::Login::new(name.to_owned());
-            Some(login.insert(value))
         } else {
             warn!("User {} is not found", name);
-            None
-        }
-    }
-
-    /// Attempts to log out of the program, returns true on success
-    pub fn log_out(&self, token: Token) -> bool {
-        let mut login = self.login.write().unwrap();
-        trace!("Attempt to log out with token {:?}", token);
-        if let Some(login) = login.remove(token) {
-            trace!("User {} with token {:?} logged out", login.name, token);
-            true
-        } else {
-            warn!("When logging out, token {:?} cannot be found", token);
             false
         }
     }
 
-    pub fn get_login(&self, token: &Token) -> Option<Arc<crate::Login>> {
-        let login = self.login.read().unwrap();
-        trace!("Request with token {:?}", token);
-        login.get(token).cloned()
+    /// Replaces a legacy plaintext password of the user with its hash
+    fn rehash(&self, name: &str, pass: &str) {
+        if let Some(hash) = crate::pass::hash_pass(pass) {
+            info!("Migrating plaintext password of user {} to a hash", name);
+            self.user()
+                .execute(
+                    "UPDATE user SET pass = ?1 WHERE name = ?2",
+                    [hash.as_str(), name],
+                )
+                .inspect_err(|err| {
+                    error!("When re-hashing password of user {}, {}", name, err);
+                })
+                .ok();
+        }
     }
 
     /// Locks and gets the login map
     pub fn login_map(&self) -> RwLockReadGuard<crate::LoginMap> {
         self.login.read().unwrap()
     }
+
+    /// Locks and gets the login map for writing
+    pub(crate) fn login_map_mut(&self) -> RwLockWriteGuard<crate::LoginMap> {
+        self.login.write().unwrap()
+    }
 }
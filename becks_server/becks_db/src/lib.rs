@@ -1,8 +1,16 @@
+mod account;
 mod config;
 mod handle;
 mod login;
+mod member;
+mod migrate;
+mod pass;
+mod pool;
 mod prelude;
+mod session;
 
-pub use config::{save_config, Config, CONFIG};
+pub use config::{save_config, Config, ForfeitPolicy, RatingKind, CONFIG};
 pub use handle::Db;
-pub use login::{Login, LoginMap};
+pub use login::{Access, Login, LoginMap, Session};
+pub use migrate::{migrate, schema_version, MigrateError, SCHEMA_VERSION};
+pub use pool::{Pool, PoolGuard};
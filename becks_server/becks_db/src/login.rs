@@ -1,210 +1,106 @@
 use crate::prelude::*;
-use std::cell::Cell;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, SystemTime};
 
 pub struct Login {
+    /// The owner of the database
     pub name: String,
-    pub db: Mutex<Connection>,
-    last_update: Mutex<Cell<SystemTime>>,
-}
-
-/// Returns whether the table exists in a database file, returning true on sqlite errors
-fn table_exists(conn: &Connection, table_name: &str) -> bool {
-    // Query the sqlite_master table to check if the table exists
-    match conn.prepare("SELECT name FROM sqlite_master WHERE type='table' AND name=?") {
-        Ok(mut stmt) => stmt.exists([table_name]).unwrap_or_else(|err| {
-            error!("When checking table {} existence, {}", table_name, err);
-            true
-        }),
-        Err(err) => {
-            error!("When checking table {} existence, {}", table_name, err);
-            true
-        }
-    }
+    pool: crate::Pool,
 }
 
 impl Login {
-    /// Connects to the corresponding database
-    pub fn new(name: String) -> Self {
+    /// Connects to the corresponding database, migrating it to the current schema
+    pub fn new(name: String) -> Result<Self, crate::MigrateError> {
         let path = CONFIG.db.user_base.join(format!("{}.db", name));
         trace!("Connecting to user database {:?}", path);
-        let db = Connection::open(&path).unwrap_or_else(|err| {
+        let pool = crate::Pool::open(&path).unwrap_or_else(|err| {
             error!(
                 "When opening user database {:?}, {}; Opening database in memory",
                 path, err
             );
-            Connection::open_in_memory().expect("rusqlite should connect to the database")
+            crate::Pool::open_in_memory().expect("rusqlite should connect to the database")
         });
 
-        if !table_exists(&db, "crew") {
-            db.execute(
-                indoc! {
-                    "CREATE TABLE IF NOT EXISTS crew (
-                    id INTEGER PRIMARY KEY,
-                    name TEXT,
-                    social BIT,
-                    score INTEGER,
-                    gender BIT,
-                    clothes TINYINT,
-                    hand BIT,
-                    hold BIT,
-                    paddle TEXT,
-                    red_rubber TEXT,
-                    black_rubber TEXT,
-                    beat TEXT,
-                    deleted BIT,
-                    score_applied BIT
-                )"
-                },
-                [],
-            )
-            .inspect_err(|err| {
-                error!("When initializing crew database, {}", err);
-            })
-            .ok();
-            db.execute(
-                indoc! {"
-                    CREATE INDEX idx_name ON crew (name);
-                    CREATE INDEX idx_social ON crew (social);
-                    CREATE INDEX idx_score ON crew (score)
-                "},
-                [],
-            )
-            .inspect_err(|err| {
-                error!("When creating crew indices, {}", err);
-            })
-            .ok();
-        }
+        crate::migrate::migrate(&mut pool.write())?;
 
-        if !table_exists(&db, "round") {
-            db.execute(
-                indoc! {"
-                    CREATE TABLE IF NOT EXISTS round (
-                        id INTEGER PRIMARY KEY,
-                        left_win BIT
-                    )
-                "},
-                [],
-            )
-            .inspect_err(|err| {
-                error!("When initializing round database, {}", err);
-            })
-            .ok();
-        }
+        Ok(Self { name, pool })
+    }
 
-        if !table_exists(&db, "match") {
-            db.execute(
-                indoc! {"
-                CREATE TABLE IF NOT EXISTS match (
-                    id INTEGER PRIMARY KEY,
-                    left INTEGER,
-                    right INTEGER,
-                    round_worth INTEGER,
-                    timestamp INTEGER,
-                    rounds TEXT,
-                    quit INTEGER,
-                    notes TEXT,
-                    left_earn INTEGER,
-                    right_earn INTEGER
-                )
-            "},
-                [],
-            )
-            .inspect_err(|err| {
-                error!("When initializing match database, {}", err);
-            })
-            .ok();
-            db.execute(
-                indoc! {"
-                    CREATE INDEX idx_left ON match (left);
-                    CREATE INDEX idx_right ON match (right);
-                    CREATE INDEX idx_timestamp ON match (timestamp);
-                    CREATE INDEX idx_notes ON match (notes)
-                "},
-                [],
-            )
-            .inspect_err(|err| {
-                error!("When creating match indices, {}", err);
-            })
-            .ok();
-        }
+    /// Locks the write connection, which should be used for writes and transactions
+    pub fn db(&self) -> MutexGuard<Connection> {
+        self.pool.write()
+    }
 
-        if !table_exists(&db, "poster") {
-            db.execute(
-                indoc! {"
-                    CREATE TABLE IF NOT EXISTS poster (
-                        id INTEGER PRIMARY KEY,
-                        value TEXT,
-                        images TEXT,
-                        timestamp INTEGER
-                    )
-                "},
-                [],
-            )
-            .inspect_err(|err| {
-                error!("When initializing poster database, {}", err);
-            })
-            .ok();
-            db.execute(
-                indoc! {"
-                    CREATE INDEX idx_value ON poster (value);
-                    CREATE INDEX idx_timestamp ON poster (timestamp)
-                "},
-                [],
-            )
-            .inspect_err(|err| {
-                error!("When creating poster indices, {}", err);
-            })
-            .ok();
-        }
+    /// Borrows a read connection, which does not wait for writes in progress
+    pub fn read(&self) -> crate::PoolGuard {
+        self.pool.read()
+    }
+}
 
+/// A logged-in device of a user, sharing the login with other sessions on the same database
+pub struct Session {
+    pub id: u128,
+    pub user: String,
+    pub login: Arc<Login>,
+    last_seen: Mutex<SystemTime>,
+}
+
+impl Session {
+    fn new(id: u128, user: String, login: Arc<Login>) -> Self {
         Self {
-            name,
-            db: Mutex::new(db),
-            last_update: Mutex::new(Cell::new(std::time::SystemTime::now())),
+            id,
+            user,
+            login,
+            last_seen: Mutex::new(SystemTime::now()),
         }
     }
 
-    pub fn db(&self) -> MutexGuard<Connection> {
-        self.db.lock().unwrap()
+    /// Updates the time this session is last seen
+    pub fn touch(&self) {
+        *self.last_seen.lock().unwrap() = SystemTime::now();
     }
 
-    /// Updates the time stored in this login
-    pub fn update_time(&self) {
-        self.last_update
-            .lock()
-            .unwrap()
-            .set(std::time::SystemTime::now());
+    pub fn last_seen(&self) -> SystemTime {
+        *self.last_seen.lock().unwrap()
     }
 
-    /// Returns the time elapsed since last update call
-    pub fn duration_since_last_update(&self) -> std::time::Duration {
-        std::time::SystemTime::now()
-            .duration_since(self.last_update.lock().unwrap().get())
-            .unwrap()
+    /// Returns the time elapsed since the session is last seen
+    pub fn duration_since_last_seen(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.last_seen())
+            .unwrap_or_default()
     }
 }
 
-pub struct LoginMap {
-    map: HashMap<Token, Arc<Login>>,
-    logged: HashMap<String, Token>,
+/// A login as seen by the requesting user, who may be a member of the club owning the database
+pub struct Access {
+    pub user: String,
+    pub role: Role,
+    pub login: Arc<Login>,
 }
 
-impl Default for LoginMap {
-    fn default() -> Self {
-        trace!("LoginMap CREATED");
-        Self {
-            map: Default::default(),
-            logged: Default::default(),
-        }
+impl Deref for Access {
+    type Target = Login;
+    fn deref(&self) -> &Self::Target {
+        &self.login
+    }
+}
+
+impl AsRef<Login> for Access {
+    fn as_ref(&self) -> &Login {
+        &self.login
     }
 }
 
+#[derive(Default)]
+pub struct LoginMap {
+    map: HashMap<Token, Session>,
+}
+
 impl Deref for LoginMap {
-    type Target = HashMap<Token, Arc<Login>>;
+    type Target = HashMap<Token, Session>;
     fn deref(&self) -> &Self::Target {
         &self.map
     }
@@ -216,33 +112,55 @@ impl LoginMap {
         Self::default()
     }
 
-    fn gen_token(&self) -> Token {
-        loop {
-            let token = Token::new(rand::random());
-            if !self.map.contains_key(&token) {
-                return token;
-            }
+    /// Finds the login of the session of the token, or the login shared by other sessions on
+    /// the database of `club`; Returns None if the database is not open
+    pub(crate) fn find(&self, token: &Token, club: &str) -> Option<Arc<Login>> {
+        if let Some(session) = self.map.get(token) {
+            return Some(session.login.clone());
         }
+        self.map
+            .values()
+            .find(|session| session.login.name == club)
+            .map(|session| session.login.clone())
     }
 
-    /// Inserts a new login into the map, returning its token;
-    /// If the user has already logged in, the old token is returned
-    pub(crate) fn insert(&mut self, login: Login) -> Token {
-        let token = self.gen_token();
-        let Self { logged, map } = self;
-        let entry = logged.entry(login.name.clone());
-        *entry.or_insert_with(|| {
-            map.insert(token, Arc::new(login));
-            token
-        })
+    /// Inserts a session of the user on `login`, returning the login it ends up with;
+    /// If the token or the database has been opened meanwhile, the existing login is kept
+    pub(crate) fn insert(
+        &mut self,
+        token: Token,
+        id: u128,
+        user: &str,
+        login: Arc<Login>,
+    ) -> Arc<Login> {
+        let login = self.find(&token, &login.name).unwrap_or(login);
+        self.map
+            .entry(token)
+            .or_insert_with(|| Session::new(id, user.to_owned(), login.clone()));
+        login
     }
 
-    /// Removes the login entry of the user, or return None
-    pub(crate) fn remove(&mut self, token: Token) -> Option<Arc<Login>> {
-        let login = self.map.remove(&token);
-        if let Some(ref login) = login {
-            self.logged.remove(&login.name);
-        }
-        login
+    /// Removes the session of the token, or return None
+    pub(crate) fn remove(&mut self, token: &Token) -> Option<Session> {
+        self.map.remove(token)
+    }
+
+    /// Removes the session with the given id, or return None
+    pub(crate) fn remove_session(&mut self, id: u128) -> Option<Session> {
+        let token = self
+            .map
+            .iter()
+            .find(|(_, session)| session.id == id)
+            .map(|(token, _)| token.clone())?;
+        self.map.remove(&token)
+    }
+
+    /// Removes all sessions of the user with the given name, and all sessions on its database,
+    /// returning true if any is removed
+    pub(crate) fn remove_user(&mut self, name: &str) -> bool {
+        let len = self.map.len();
+        self.map
+            .retain(|_, session| session.user != name && session.login.name != name);
+        self.map.len() != len
     }
 }
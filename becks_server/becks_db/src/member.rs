@@ -0,0 +1,124 @@
+use crate::prelude::*;
+use crate::Db;
+use becks_convey::user::member::{ClubInfo, MemberInfo};
+
+fn role_to_level(role: Role) -> u8 {
+    match role {
+        Role::Viewer => 0,
+        Role::Recorder => 1,
+        Role::Admin => 2,
+        Role::Owner => 3,
+    }
+}
+
+fn role_from_level(level: u8) -> Option<Role> {
+    match level {
+        0 => Some(Role::Viewer),
+        1 => Some(Role::Recorder),
+        2 => Some(Role::Admin),
+        3 => Some(Role::Owner),
+        _ => None,
+    }
+}
+
+impl Db {
+    /// Returns the role of the user on the club database, or None if the user is not a member
+    pub fn role(&self, user: &str, club: &str) -> Option<Role> {
+        if user == club {
+            return Some(Role::Owner);
+        }
+        self.user_read()
+            .query_row(
+                "SELECT role FROM member WHERE owner = ?1 AND member = ?2",
+                [club, user],
+                |row| row.get::<_, u8>(0),
+            )
+            .ok()
+            .and_then(role_from_level)
+    }
+
+    /// Grants a role on the club of the owner to another user, returns true on success
+    pub fn grant(&self, owner: &str, member: &str, role: Role) -> bool {
+        check!(alnum member);
+        if owner == member || role == Role::Owner {
+            warn!("User {} cannot grant {:?} to {}", owner, role, member);
+            return false;
+        }
+        if !self.exists(member) {
+            warn!("User {} is not found", member);
+            return false;
+        }
+        info!("Granting {:?} on club {} to user {}", role, owner, member);
+        self.user()
+            .execute(
+                "INSERT OR REPLACE INTO member (owner, member, role) VALUES (?1, ?2, ?3)",
+                (owner, member, role_to_level(role)),
+            )
+            .inspect_err(|err| {
+                error!("When granting role to user {}, {}", member, err);
+            })
+            .is_ok()
+    }
+
+    /// Removes a member from the club of the owner, returns true on success
+    pub fn revoke_member(&self, owner: &str, member: &str) -> bool {
+        info!("Revoking membership of user {} on club {}", member, owner);
+        self.user()
+            .execute(
+                "DELETE FROM member WHERE owner = ?1 AND member = ?2",
+                [owner, member],
+            )
+            .inspect_err(|err| {
+                error!("When revoking membership of user {}, {}", member, err);
+            })
+            .is_ok_and(|modified| modified > 0)
+    }
+
+    /// Lists the members of the club of the owner
+    pub fn members(&self, owner: &str) -> Vec<MemberInfo> {
+        self.user_read()
+            .prepare("SELECT member, role FROM member WHERE owner = ?1 ORDER BY member")
+            .and_then(|mut stmt| {
+                stmt.query_map([owner], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, u8>(1)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .inspect_err(|err| {
+                error!("When listing members of club {}, {}", owner, err);
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(name, role)| {
+                Some(MemberInfo {
+                    name,
+                    role: role_from_level(role)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Lists the clubs the user is a member of, not including its own
+    pub fn clubs(&self, member: &str) -> Vec<ClubInfo> {
+        self.user_read()
+            .prepare("SELECT owner, role FROM member WHERE member = ?1 ORDER BY owner")
+            .and_then(|mut stmt| {
+                stmt.query_map([member], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, u8>(1)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .inspect_err(|err| {
+                error!("When listing clubs of user {}, {}", member, err);
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(owner, role)| {
+                Some(ClubInfo {
+                    owner,
+                    role: role_from_level(role)?,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,537 @@
+//! Versioned schema migrations of the per-user databases
+//!
+//! The schema version is stored in `PRAGMA user_version`, where databases created before
+//! versioning have version 0. Each step upgrades the database by one version in its own
+//! transaction; Steps must stay idempotent for version 0, since unversioned databases may
+//! be at any historical schema.
+
+use crate::prelude::*;
+use rusqlite::Transaction;
+
+#[derive(Debug, Error)]
+pub enum MigrateError {
+    #[error("database version {found} is newer than the supported version {supported}")]
+    TooNew { found: u32, supported: u32 },
+    #[error("migration to version {version} failed, {source}")]
+    Step {
+        version: u32,
+        source: rusqlite::Error,
+    },
+    #[error(transparent)]
+    Sql(#[from] rusqlite::Error),
+}
+
+struct Step {
+    description: &'static str,
+    apply: fn(&Transaction) -> rusqlite::Result<()>,
+}
+
+/// Migration steps in order, where the step at index `i` upgrades the database to version `i + 1`
+const STEPS: &[Step] = &[
+    Step {
+        description: "create tables",
+        apply: create_tables,
+    },
+    Step {
+        description: "add columns missing from older databases",
+        apply: add_legacy_columns,
+    },
+    Step {
+        description: "create indices",
+        apply: create_indices,
+    },
+    Step {
+        description: "add rating deviation and volatility",
+        apply: add_rating_columns,
+    },
+    Step {
+        description: "add initial score",
+        apply: add_initial_score,
+    },
+    Step {
+        description: "add voided matches",
+        apply: add_voided,
+    },
+    Step {
+        description: "create match audit",
+        apply: create_match_audit,
+    },
+    Step {
+        description: "add total rounds of matches",
+        apply: add_total_rounds,
+    },
+    Step {
+        description: "add points of rounds",
+        apply: add_round_points,
+    },
+    Step {
+        description: "add partners of doubles matches",
+        apply: add_partners,
+    },
+    Step {
+        description: "create tournaments",
+        apply: create_tournaments,
+    },
+    Step {
+        description: "create swiss events",
+        apply: create_swiss,
+    },
+    Step {
+        description: "create arrangements",
+        apply: create_arrangements,
+    },
+];
+
+/// The schema version this binary creates and supports
+pub const SCHEMA_VERSION: u32 = STEPS.len() as u32;
+
+/// Returns whether the column exists in the table
+fn column_exists(tx: &Transaction, table: &str, column: &str) -> rusqlite::Result<bool> {
+    let mut stmt = tx.prepare(&format!("PRAGMA table_info({})", table))?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(names.iter().any(|name| name == column))
+}
+
+/// Adds the column to the table unless it already exists
+fn add_column(
+    tx: &Transaction,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> rusqlite::Result<()> {
+    if !column_exists(tx, table, column)? {
+        info!("Adding column {} to table {}", column, table);
+        tx.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+fn create_tables(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(indoc! {"
+        CREATE TABLE IF NOT EXISTS crew (
+            id INTEGER PRIMARY KEY,
+            name TEXT,
+            social BIT,
+            score INTEGER,
+            gender BIT,
+            clothes TINYINT,
+            hand BIT,
+            hold BIT,
+            paddle TEXT,
+            red_rubber TEXT,
+            black_rubber TEXT,
+            beat TEXT,
+            deleted BIT,
+            score_applied BIT
+        );
+        CREATE TABLE IF NOT EXISTS round (
+            id INTEGER PRIMARY KEY,
+            left_win BIT
+        );
+        CREATE TABLE IF NOT EXISTS match (
+            id INTEGER PRIMARY KEY,
+            left INTEGER,
+            right INTEGER,
+            round_worth INTEGER,
+            timestamp INTEGER,
+            rounds TEXT,
+            quit INTEGER,
+            notes TEXT,
+            left_earn INTEGER,
+            right_earn INTEGER
+        );
+        CREATE TABLE IF NOT EXISTS poster (
+            id INTEGER PRIMARY KEY,
+            value TEXT,
+            images TEXT,
+            timestamp INTEGER
+        );
+    "})
+}
+
+fn add_legacy_columns(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "crew", "score_applied", "BIT DEFAULT 0")?;
+    add_column(tx, "match", "left_earn", "INTEGER DEFAULT 0")?;
+    add_column(tx, "match", "right_earn", "INTEGER DEFAULT 0")?;
+    Ok(())
+}
+
+fn create_indices(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(indoc! {"
+        CREATE INDEX IF NOT EXISTS idx_name ON crew (name);
+        CREATE INDEX IF NOT EXISTS idx_social ON crew (social);
+        CREATE INDEX IF NOT EXISTS idx_score ON crew (score);
+        CREATE INDEX IF NOT EXISTS idx_left ON match (left);
+        CREATE INDEX IF NOT EXISTS idx_right ON match (right);
+        CREATE INDEX IF NOT EXISTS idx_match_timestamp ON match (timestamp);
+        CREATE INDEX IF NOT EXISTS idx_notes ON match (notes);
+        CREATE INDEX IF NOT EXISTS idx_value ON poster (value);
+        CREATE INDEX IF NOT EXISTS idx_poster_timestamp ON poster (timestamp);
+    "})
+}
+
+fn add_rating_columns(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "crew", "deviation", "REAL DEFAULT 350.0")?;
+    add_column(tx, "crew", "volatility", "REAL DEFAULT 0.06")?;
+    Ok(())
+}
+
+/// Adds the score each crew starts with, which is recovered for existing crews
+/// by taking back what they have earned from matches
+fn add_initial_score(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "crew", "initial_score", "INTEGER")?;
+    tx.execute_batch(indoc! {"
+        UPDATE crew
+        SET initial_score = score
+            - COALESCE((SELECT SUM(left_earn) FROM match WHERE left = crew.id), 0)
+            - COALESCE((SELECT SUM(right_earn) FROM match WHERE right = crew.id), 0)
+        WHERE initial_score IS NULL;
+    "})
+}
+
+fn add_voided(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "match", "voided", "BIT DEFAULT 0")?;
+    tx.execute_batch("CREATE INDEX IF NOT EXISTS idx_voided ON match (voided);")
+}
+
+/// Creates the table that keeps the old and new values of every match modification
+fn create_match_audit(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(indoc! {"
+        CREATE TABLE IF NOT EXISTS match_audit (
+            id INTEGER PRIMARY KEY,
+            mat INTEGER,
+            editor TEXT,
+            timestamp INTEGER,
+            old TEXT,
+            new TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_audit_mat ON match_audit (mat);
+    "})
+}
+
+/// Adds the number of rounds a match is declared with, which differs from the rounds
+/// played if a crew quits; Existing matches are counted from their rounds
+fn add_total_rounds(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "match", "total_rounds", "INTEGER")?;
+    tx.execute_batch(indoc! {"
+        UPDATE match
+        SET total_rounds = CASE
+            WHEN TRIM(COALESCE(rounds, '')) = '' THEN 0
+            ELSE LENGTH(TRIM(rounds)) - LENGTH(REPLACE(TRIM(rounds), ' ', '')) + 1
+        END
+        WHERE total_rounds IS NULL;
+    "})
+}
+
+fn add_round_points(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "round", "left_points", "INTEGER")?;
+    add_column(tx, "round", "right_points", "INTEGER")?;
+    Ok(())
+}
+
+fn add_partners(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "match", "left_partner", "INTEGER")?;
+    add_column(tx, "match", "right_partner", "INTEGER")?;
+    tx.execute_batch(indoc! {"
+        CREATE INDEX IF NOT EXISTS idx_left_partner ON match (left_partner);
+        CREATE INDEX IF NOT EXISTS idx_right_partner ON match (right_partner);
+    "})
+}
+
+/// Creates the table of tournaments, where the fixtures are kept as JSON in `state`
+/// and advanced as matches are recorded
+fn create_tournaments(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(indoc! {"
+        CREATE TABLE IF NOT EXISTS tournament (
+            id INTEGER PRIMARY KEY,
+            name TEXT,
+            state TEXT,
+            closed BIT DEFAULT 0,
+            timestamp INTEGER
+        );
+        CREATE INDEX IF NOT EXISTS idx_tournament_closed ON tournament (closed);
+    "})
+}
+
+/// Creates the table of Swiss events, where the crews and their rounds are kept
+/// as JSON in `state`, so that an event outlives the client that made it
+fn create_swiss(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(indoc! {"
+        CREATE TABLE IF NOT EXISTS swiss (
+            id INTEGER PRIMARY KEY,
+            state TEXT,
+            closed BIT DEFAULT 0,
+            timestamp INTEGER
+        );
+        CREATE INDEX IF NOT EXISTS idx_swiss_closed ON swiss (closed);
+    "})
+}
+
+/// Creates the table of arrangements, where the groups and their schedules are kept
+/// as JSON in `state`, so that an arrangement outlives the client that made it
+fn create_arrangements(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(indoc! {"
+        CREATE TABLE IF NOT EXISTS arrangement (
+            id INTEGER PRIMARY KEY,
+            state TEXT,
+            closed BIT DEFAULT 0,
+            timestamp INTEGER
+        );
+        CREATE INDEX IF NOT EXISTS idx_arrangement_closed ON arrangement (closed);
+    "})
+}
+
+/// Returns the schema version of the database
+pub fn schema_version(conn: &Connection) -> rusqlite::Result<u32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+/// Upgrades the database to [`SCHEMA_VERSION`], refusing databases newer than that
+pub fn migrate(conn: &mut Connection) -> Result<(), MigrateError> {
+    let found = schema_version(conn)?;
+    if found > SCHEMA_VERSION {
+        return Err(MigrateError::TooNew {
+            found,
+            supported: SCHEMA_VERSION,
+        });
+    }
+    for (index, step) in STEPS.iter().enumerate().skip(found as usize) {
+        let version = index as u32 + 1;
+        debug!("Migrating to version {}: {}", version, step.description);
+        let apply = |conn: &mut Connection| {
+            let tx = conn.transaction()?;
+            (step.apply)(&tx)?;
+            tx.pragma_update(None, "user_version", version)?;
+            tx.commit()
+        };
+        apply(conn).map_err(|source| MigrateError::Step { version, source })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Opens a database in memory that has been migrated up to the given version
+    fn at_version(version: u32) -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        for (index, step) in STEPS.iter().enumerate().take(version as usize) {
+            let tx = conn.transaction().unwrap();
+            (step.apply)(&tx).unwrap();
+            tx.pragma_update(None, "user_version", index as u32 + 1)
+                .unwrap();
+            tx.commit().unwrap();
+        }
+        conn
+    }
+
+    fn has_column(conn: &mut Connection, table: &str, column: &str) -> bool {
+        let tx = conn.transaction().unwrap();
+        column_exists(&tx, table, column).unwrap()
+    }
+
+    fn has_table(conn: &Connection, table: &str) -> bool {
+        conn.query_row(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [table],
+            |_| Ok(()),
+        )
+        .is_ok()
+    }
+
+    fn assert_current(conn: &mut Connection) {
+        assert_eq!(schema_version(conn).unwrap(), SCHEMA_VERSION);
+        for (table, column) in [
+            ("crew", "score_applied"),
+            ("crew", "deviation"),
+            ("crew", "volatility"),
+            ("crew", "initial_score"),
+            ("match", "left_earn"),
+            ("match", "right_earn"),
+            ("match", "voided"),
+            ("match", "total_rounds"),
+            ("match", "left_partner"),
+            ("match", "right_partner"),
+            ("round", "left_points"),
+            ("round", "right_points"),
+        ] {
+            assert!(has_column(conn, table, column), "{}.{}", table, column);
+        }
+        for table in ["match_audit", "tournament", "swiss", "arrangement"] {
+            assert!(has_table(conn, table), "{}", table);
+        }
+    }
+
+    #[test]
+    fn test_migrate_empty() {
+        let mut conn = at_version(0);
+        migrate(&mut conn).unwrap();
+        assert_current(&mut conn);
+    }
+
+    #[test]
+    fn test_migrate_unversioned() {
+        // A database from before versioning, which lacks the columns added later on
+        let mut conn = at_version(0);
+        conn.execute_batch(indoc! {"
+            CREATE TABLE crew (
+                id INTEGER PRIMARY KEY,
+                name TEXT,
+                social BIT,
+                score INTEGER,
+                gender BIT,
+                clothes TINYINT,
+                hand BIT,
+                hold BIT,
+                paddle TEXT,
+                red_rubber TEXT,
+                black_rubber TEXT,
+                beat TEXT,
+                deleted BIT
+            );
+            CREATE TABLE round (
+                id INTEGER PRIMARY KEY,
+                left_win BIT
+            );
+            CREATE TABLE match (
+                id INTEGER PRIMARY KEY,
+                left INTEGER,
+                right INTEGER,
+                round_worth INTEGER,
+                timestamp INTEGER,
+                rounds TEXT,
+                quit INTEGER,
+                notes TEXT
+            );
+            CREATE INDEX idx_name ON crew (name);
+            CREATE INDEX idx_timestamp ON match (timestamp);
+            INSERT INTO crew (id, name, score, deleted) VALUES (1, 'left', 1000, 0);
+            INSERT INTO crew (id, name, score, deleted) VALUES (2, 'right', 1000, 0);
+            INSERT INTO round (id, left_win) VALUES (1, 1);
+            INSERT INTO round (id, left_win) VALUES (2, 0);
+            INSERT INTO round (id, left_win) VALUES (3, 1);
+            INSERT INTO match (id, left, right, round_worth, timestamp, rounds, quit, notes)
+                VALUES (1, 1, 2, 10, 0, '1 2 3', 0, '');
+        "})
+            .unwrap();
+        migrate(&mut conn).unwrap();
+        assert_current(&mut conn);
+
+        let (score_applied, initial_score): (bool, i64) = conn
+            .query_row(
+                "SELECT score_applied, initial_score FROM crew WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert!(!score_applied);
+        assert_eq!(initial_score, 1000);
+        let (left_earn, total_rounds, voided): (i64, u32, bool) = conn
+            .query_row(
+                "SELECT left_earn, total_rounds, voided FROM match WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(left_earn, 0);
+        assert_eq!(total_rounds, 3);
+        assert!(!voided);
+    }
+
+    #[test]
+    fn test_migrate_initial_score() {
+        // Scores earned from matches are taken back to find the initial score
+        let mut conn = at_version(4);
+        conn.execute_batch(indoc! {"
+            INSERT INTO crew (id, name, score, deleted) VALUES (1, 'left', 1030, 0);
+            INSERT INTO crew (id, name, score, deleted) VALUES (2, 'right', 970, 0);
+            INSERT INTO crew (id, name, score, deleted) VALUES (3, 'idle', 1200, 0);
+            INSERT INTO match (id, left, right, rounds, left_earn, right_earn)
+                VALUES (1, 1, 2, '1 2', 20, -20);
+            INSERT INTO match (id, left, right, rounds, left_earn, right_earn)
+                VALUES (2, 2, 1, '', -10, 10);
+        "})
+            .unwrap();
+        migrate(&mut conn).unwrap();
+        assert_current(&mut conn);
+
+        let initial = |id: u32| -> i64 {
+            conn.query_row(
+                "SELECT initial_score FROM crew WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(initial(1), 1000);
+        assert_eq!(initial(2), 1000);
+        assert_eq!(initial(3), 1200);
+        let total_rounds = |id: u32| -> u32 {
+            conn.query_row(
+                "SELECT total_rounds FROM match WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(total_rounds(1), 2);
+        assert_eq!(total_rounds(2), 0);
+    }
+
+    #[test]
+    fn test_migrate_every_version() {
+        for version in 1..=SCHEMA_VERSION {
+            let mut conn = at_version(version);
+            conn.execute(
+                "INSERT INTO crew (id, name, score, deleted) VALUES (1, 'crew', 1000, 0)",
+                [],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO match (id, left, right, rounds) VALUES (1, 1, 1, '')",
+                [],
+            )
+            .unwrap();
+            migrate(&mut conn).unwrap_or_else(|err| panic!("from version {}, {}", version, err));
+            assert_current(&mut conn);
+            let name: String = conn
+                .query_row("SELECT name FROM crew WHERE id = 1", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(name, "crew");
+            let matches: u32 = conn
+                .query_row("SELECT COUNT(*) FROM match", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(matches, 1);
+        }
+    }
+
+    #[test]
+    fn test_migrate_twice() {
+        let mut conn = at_version(0);
+        migrate(&mut conn).unwrap();
+        migrate(&mut conn).unwrap();
+        assert_current(&mut conn);
+    }
+
+    #[test]
+    fn test_migrate_too_new() {
+        let mut conn = at_version(0);
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+        match migrate(&mut conn) {
+            Err(MigrateError::TooNew { found, supported }) => {
+                assert_eq!(found, SCHEMA_VERSION + 1);
+                assert_eq!(supported, SCHEMA_VERSION);
+            }
+            other => panic!("expected TooNew, got {:?}", other),
+        }
+        assert!(!has_table(&conn, "crew"));
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION + 1);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::prelude::*;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+
+/// The result of checking a password against the value stored in the user table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Verified {
+    /// The password does not match
+    Wrong,
+    /// The password matches a hashed entry
+    Hashed,
+    /// The password matches a legacy plaintext entry, which should be re-hashed
+    Plain,
+}
+
+/// Compares two byte strings without short-circuiting on the first difference
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    lhs.iter()
+        .zip(rhs.iter())
+        .fold(0u8, |diff, (lhs, rhs)| diff | (lhs ^ rhs))
+        == 0
+}
+
+/// Hashes the password with a newly generated salt, returning the PHC string to be stored
+pub(crate) fn hash_pass(pass: &str) -> Option<String> {
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())
+        .inspect_err(|err| {
+            error!("When generating password salt, {}", err);
+        })
+        .ok()?;
+    Argon2::default()
+        .hash_password(pass.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .inspect_err(|err| {
+            error!("When hashing password, {}", err);
+        })
+        .ok()
+}
+
+/// Verifies the password against the stored value;
+/// Values that are not PHC strings are treated as legacy plaintext passwords
+pub(crate) fn verify_pass(pass: &str, stored: &str) -> Verified {
+    match PasswordHash::new(stored) {
+        Ok(hash) => {
+            if Argon2::default()
+                .verify_password(pass.as_bytes(), &hash)
+                .is_ok()
+            {
+                Verified::Hashed
+            } else {
+                Verified::Wrong
+            }
+        }
+        Err(_) => {
+            if constant_time_eq(pass.as_bytes(), stored.as_bytes()) {
+                Verified::Plain
+            } else {
+                Verified::Wrong
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Db;
+
+    #[test]
+    fn test_verify_pass() {
+        let hash = hash_pass("secret").unwrap();
+        assert_ne!(hash, "secret");
+        assert_eq!(verify_pass("secret", &hash), Verified::Hashed);
+        assert_eq!(verify_pass("Secret", &hash), Verified::Wrong);
+        assert_eq!(verify_pass("", &hash), Verified::Wrong);
+        // Each hash has its own salt
+        assert_ne!(hash_pass("secret").unwrap(), hash);
+    }
+
+    #[test]
+    fn test_verify_plain() {
+        assert_eq!(verify_pass("secret", "secret"), Verified::Plain);
+        assert_eq!(verify_pass("secret", "secrets"), Verified::Wrong);
+        assert_eq!(verify_pass("secret", "Secret"), Verified::Wrong);
+    }
+
+    #[test]
+    fn test_rehash_plain() {
+        let db = Db::in_memory();
+        db.user()
+            .execute(
+                "INSERT INTO user (name, pass) VALUES ('legacy', 'secret')",
+                [],
+            )
+            .unwrap();
+        let stored = || {
+            db.user_read()
+                .query_row("SELECT pass FROM user WHERE name = 'legacy'", [], |row| {
+                    row.get::<_, String>(0)
+                })
+                .unwrap()
+        };
+        assert!(!db.verify("legacy", "wrong"));
+        assert_eq!(stored(), "secret");
+
+        assert!(db.verify("legacy", "secret"));
+        let hash = stored();
+        assert_eq!(verify_pass("secret", &hash), Verified::Hashed);
+        // The hash is kept once the password is migrated
+        assert!(db.verify("legacy", "secret"));
+        assert_eq!(stored(), hash);
+        assert!(!db.verify("legacy", "wrong"));
+    }
+}
@@ -0,0 +1,273 @@
+use crate::prelude::*;
+use crate::CONFIG;
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::{Condvar, Mutex, MutexGuard};
+
+struct Readers {
+    idle: Vec<Connection>,
+    opened: usize,
+}
+
+/// Connections to a database file in WAL mode, where writes go through a single connection
+/// and reads are spread over a pool of read-only connections that do not block the writer
+pub struct Pool {
+    path: Option<std::path::PathBuf>,
+    writer: Mutex<Connection>,
+    readers: Mutex<Readers>,
+    available: Condvar,
+}
+
+/// A connection borrowed from a [`Pool`], which is returned on drop
+pub enum PoolGuard<'a> {
+    Reader {
+        pool: &'a Pool,
+        conn: Option<Connection>,
+    },
+    Writer(MutexGuard<'a, Connection>),
+}
+
+impl Deref for PoolGuard<'_> {
+    type Target = Connection;
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Reader { conn, .. } => conn.as_ref().expect("reader is only taken on drop"),
+            Self::Writer(conn) => conn,
+        }
+    }
+}
+
+impl Drop for PoolGuard<'_> {
+    fn drop(&mut self) {
+        if let Self::Reader { pool, conn } = self {
+            if let Some(conn) = conn.take() {
+                pool.readers.lock().unwrap().idle.push(conn);
+                pool.available.notify_one();
+            }
+        }
+    }
+}
+
+impl Pool {
+    fn configure(conn: &Connection) -> rusqlite::Result<()> {
+        conn.busy_timeout(CONFIG.db.busy_timeout)?;
+        conn.pragma_update(None, "synchronous", "NORMAL")
+    }
+
+    /// Opens the database file in WAL mode
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        let writer = Connection::open(path)?;
+        let mode: String =
+            writer.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+        if !mode.eq_ignore_ascii_case("wal") {
+            warn!(
+                "Database {:?} is in journal mode {} instead of WAL",
+                path, mode
+            );
+        }
+        Self::configure(&writer)?;
+        Ok(Self::new(Some(path.to_owned()), writer))
+    }
+
+    /// Opens a database in memory, where all reads go through the writer
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Ok(Self::new(None, Connection::open_in_memory()?))
+    }
+
+    fn new(path: Option<std::path::PathBuf>, writer: Connection) -> Self {
+        Self {
+            path,
+            writer: Mutex::new(writer),
+            readers: Mutex::new(Readers {
+                idle: Vec::new(),
+                opened: 0,
+            }),
+            available: Condvar::new(),
+        }
+    }
+
+    fn open_reader(path: &Path) -> rusqlite::Result<Connection> {
+        let conn = Connection::open(path)?;
+        Self::configure(&conn)?;
+        conn.pragma_update(None, "query_only", true)?;
+        Ok(conn)
+    }
+
+    /// Locks the write connection, which should also be used for reads within a transaction
+    pub fn write(&self) -> MutexGuard<'_, Connection> {
+        self.writer.lock().unwrap()
+    }
+
+    /// Borrows a read-only connection, waiting if all of them are in use;
+    /// Falls back to the write connection if the database has no file or a reader cannot be opened
+    pub fn read(&self) -> PoolGuard<'_> {
+        let Some(path) = &self.path else {
+            return PoolGuard::Writer(self.write());
+        };
+        let mut readers = self.readers.lock().unwrap();
+        loop {
+            if let Some(conn) = readers.idle.pop() {
+                return PoolGuard::Reader {
+                    pool: self,
+                    conn: Some(conn),
+                };
+            }
+            if readers.opened < CONFIG.db.readers.max(1) {
+                readers.opened += 1;
+                drop(readers);
+                return match Self::open_reader(path) {
+                    Ok(conn) => PoolGuard::Reader {
+                        pool: self,
+                        conn: Some(conn),
+                    },
+                    Err(err) => {
+                        error!("When opening reader of {:?}, {}", path, err);
+                        self.readers.lock().unwrap().opened -= 1;
+                        PoolGuard::Writer(self.write())
+                    }
+                };
+            }
+            readers = self.available.wait(readers).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{mpsc, Arc};
+    use std::time::Duration;
+
+    /// A database file in the temporary directory, which is removed on drop
+    struct TempDb(std::path::PathBuf);
+
+    impl TempDb {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("becks_pool_{}_{}.db", name, std::process::id()));
+            let db = Self(path);
+            db.remove();
+            db
+        }
+
+        fn remove(&self) {
+            for suffix in ["", "-wal", "-shm"] {
+                let mut path = self.0.clone().into_os_string();
+                path.push(suffix);
+                std::fs::remove_file(path).ok();
+            }
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            self.remove();
+        }
+    }
+
+    fn open(db: &TempDb) -> Pool {
+        let pool = Pool::open(&db.0).unwrap();
+        pool.write()
+            .execute_batch(indoc! {"
+                CREATE TABLE item (id INTEGER PRIMARY KEY, value INTEGER);
+                INSERT INTO item (value) VALUES (1), (2), (3);
+            "})
+            .unwrap();
+        pool
+    }
+
+    fn count(conn: &Connection) -> u32 {
+        conn.query_row("SELECT COUNT(*) FROM item", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_pool_wal() {
+        let db = TempDb::new("wal");
+        let pool = open(&db);
+        let mode: String = pool
+            .read()
+            .pragma_query_value(None, "journal_mode", |row| row.get(0))
+            .unwrap();
+        assert!(mode.eq_ignore_ascii_case("wal"));
+        assert!(matches!(pool.read(), PoolGuard::Reader { .. }));
+    }
+
+    #[test]
+    fn test_pool_readers_during_write() {
+        const READERS: usize = 16;
+        let db = TempDb::new("readers");
+        let pool = Arc::new(open(&db));
+
+        // The writer keeps a transaction open while the readers run
+        let mut writer = pool.write();
+        let tx = writer.transaction().unwrap();
+        tx.execute("INSERT INTO item (value) VALUES (4)", [])
+            .unwrap();
+
+        let (done, finished) = mpsc::channel();
+        let readers: Vec<_> = (0..READERS)
+            .map(|_| {
+                let pool = pool.clone();
+                let done = done.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        // Readers see the last committed state
+                        assert_eq!(count(&pool.read()), 3);
+                    }
+                    done.send(()).unwrap();
+                })
+            })
+            .collect();
+        for _ in 0..READERS {
+            finished
+                .recv_timeout(Duration::from_secs(10))
+                .expect("readers should not wait for the writer");
+        }
+        tx.commit().unwrap();
+        drop(writer);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(count(&pool.read()), 4);
+    }
+
+    #[test]
+    fn test_pool_write_during_reads() {
+        let db = TempDb::new("writer");
+        let pool = Arc::new(open(&db));
+
+        // Every reader is borrowed and in the middle of a read transaction
+        let guards: Vec<_> = (0..CONFIG.db.readers.max(1))
+            .map(|_| {
+                let guard = pool.read();
+                guard.execute_batch("BEGIN").unwrap();
+                assert_eq!(count(&guard), 3);
+                guard
+            })
+            .collect();
+
+        let writer = {
+            let pool = pool.clone();
+            std::thread::spawn(move || {
+                for value in 4..=100 {
+                    pool.write()
+                        .execute("INSERT INTO item (value) VALUES (?1)", [value])
+                        .unwrap();
+                }
+            })
+        };
+        let (done, finished) = mpsc::channel();
+        std::thread::spawn(move || done.send(writer.join()).unwrap());
+        finished
+            .recv_timeout(Duration::from_secs(10))
+            .expect("the writer should not wait for the readers")
+            .unwrap();
+
+        for guard in guards.iter() {
+            assert_eq!(count(guard), 3);
+            guard.execute_batch("COMMIT").unwrap();
+            assert_eq!(count(guard), 100);
+        }
+    }
+}
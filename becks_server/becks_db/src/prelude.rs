@@ -1,7 +1,9 @@
 pub use crate::CONFIG;
 pub(crate) use becks_convey::user::auth::Token;
+pub(crate) use becks_convey::user::member::Role;
 pub(crate) use becks_crew::check;
 pub(crate) use indoc::indoc;
 pub(crate) use log::{debug, error, info, trace, warn};
 pub(crate) use rusqlite::Connection;
 pub(crate) use serde::{Deserialize, Serialize};
+pub(crate) use thiserror::Error;
@@ -0,0 +1,521 @@
+use crate::prelude::*;
+use crate::{Db, CONFIG};
+use becks_convey::user::session::SessionInfo;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Returns the current unix timestamp in seconds
+pub(crate) fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Loads the token signing secret from the main database, generating one if it does not exist
+pub(crate) fn load_secret(conn: &Connection) -> Vec<u8> {
+    conn.execute(
+        indoc! {
+            "CREATE TABLE IF NOT EXISTS secret (
+                id INTEGER PRIMARY KEY CHECK (id = 0),
+                value BLOB
+            )"
+        },
+        [],
+    )
+    .inspect_err(|err| {
+        error!("When initializing secret database, {}", err);
+    })
+    .ok();
+    if let Ok(secret) = conn.query_row("SELECT value FROM secret WHERE id = 0", [], |row| {
+        row.get::<_, Vec<u8>>(0)
+    }) {
+        return secret;
+    }
+    info!("Generating a new token signing secret");
+    let secret = rand::random::<[u8; 32]>().to_vec();
+    conn.execute(
+        "INSERT INTO secret (id, value) VALUES (0, ?1)",
+        [secret.as_slice()],
+    )
+    .inspect_err(|err| {
+        error!(
+            "When saving token signing secret, {}; Tokens will not survive a restart",
+            err
+        );
+    })
+    .ok();
+    secret
+}
+
+/// The content of a session token, which is signed by the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Claims {
+    pub name: String,
+    pub session: u128,
+    pub expiry: u64,
+}
+
+impl Claims {
+    /// Creates claims for a new session of the user, expiring after the configured lifetime
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            session: rand::random(),
+            expiry: current_timestamp() + CONFIG.user.token_lifetime.as_secs(),
+        }
+    }
+
+    fn payload(&self) -> String {
+        format!("{}.{:032x}.{}", self.name, self.session, self.expiry)
+    }
+
+    fn mac(secret: &[u8], payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(secret).expect("hmac should accept any key size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+
+    /// Signs the claims into a token
+    pub fn sign(&self, secret: &[u8]) -> Token {
+        let payload = self.payload();
+        let sig = hex::encode(Self::mac(secret, &payload).finalize().into_bytes());
+        Token::new(format!("{}.{}", payload, sig))
+    }
+
+    /// Checks the signature and expiry of the token, returning its claims if it is valid
+    pub fn verify(token: &Token, secret: &[u8]) -> Option<Self> {
+        let (payload, sig) = token.as_str().rsplit_once('.')?;
+        let sig = hex::decode(sig).ok()?;
+        if Self::mac(secret, payload).verify_slice(&sig).is_err() {
+            warn!("A token with an invalid signature is given");
+            return None;
+        }
+        let mut parts = payload.split('.');
+        let name = parts.next()?.to_owned();
+        let session = u128::from_str_radix(parts.next()?, 16).ok()?;
+        let expiry = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        if expiry < current_timestamp() {
+            debug!(
+                "Session {} of user {} has expired",
+                session_key(session),
+                name
+            );
+            return None;
+        }
+        Some(Self {
+            name,
+            session,
+            expiry,
+        })
+    }
+}
+
+/// Formats the session id as stored in the database
+fn session_key(id: u128) -> String {
+    format!("{:032x}", id)
+}
+
+fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Connects to the database of the club, logging migration errors
+fn connect(club: &str) -> Option<crate::Login> {
+    crate::Login::new(club.to_owned())
+        .inspect_err(|err| {
+            error!("When opening database of {}, {}", club, err);
+        })
+        .ok()
+}
+
+impl Db {
+    /// Returns whether the session has been revoked
+    fn is_revoked(&self, id: u128) -> bool {
+        self.user_read()
+            .query_row(
+                "SELECT session FROM revoked WHERE session = ?1",
+                [session_key(id)],
+                |_| Ok(()),
+            )
+            .is_ok()
+    }
+
+    /// Records the session as revoked until its expiry, and forgets it
+    fn revoke(&self, name: &str, id: u128, expiry: u64) -> bool {
+        let revoked = self
+            .user()
+            .execute(
+                "INSERT OR IGNORE INTO revoked (session, expiry) VALUES (?1, ?2)",
+                (session_key(id), expiry),
+            )
+            .inspect_err(|err| {
+                error!("When revoking session of user {}, {}", name, err);
+            })
+            .is_ok_and(|modified| modified > 0);
+        self.user()
+            .execute("DELETE FROM session WHERE id = ?1", [session_key(id)])
+            .inspect_err(|err| {
+                error!("When deleting session of user {}, {}", name, err);
+            })
+            .ok();
+        self.login_map_mut().remove_session(id);
+        revoked
+    }
+
+    /// Revokes the sessions of the user other than the one of `keep`, and closes all logins
+    /// of the user, so that tokens issued before a credential change are no longer accepted
+    pub(crate) fn revoke_user(&self, name: &str, keep: Option<&Token>) {
+        let keep = keep
+            .and_then(|token| Claims::verify(token, &self.secret))
+            .map(|claims| session_key(claims.session))
+            .unwrap_or_default();
+        self.user()
+            .execute(
+                "INSERT OR IGNORE INTO revoked (session, expiry)
+                    SELECT id, expiry FROM session WHERE name = ?1 AND id != ?2",
+                [name, keep.as_str()],
+            )
+            .inspect_err(|err| {
+                error!("When revoking sessions of user {}, {}", name, err);
+            })
+            .ok();
+        self.user()
+            .execute(
+                "DELETE FROM session WHERE name = ?1 AND id != ?2",
+                [name, keep.as_str()],
+            )
+            .inspect_err(|err| {
+                error!("When deleting sessions of user {}, {}", name, err);
+            })
+            .ok();
+        // The kept session is reopened from its record on the next request
+        self.login_map_mut().remove_user(name);
+    }
+
+    /// Removes session and revocation records of tokens that have expired anyway
+    pub fn prune_sessions(&self) {
+        let now = current_timestamp();
+        for table in ["revoked", "session"] {
+            self.user()
+                .execute(&format!("DELETE FROM {} WHERE expiry < ?1", table), [now])
+                .inspect_err(|err| {
+                    error!("When pruning expired {} records, {}", table, err);
+                })
+                .ok();
+        }
+    }
+
+    /// Records the session on the club database in the session table if it is not there yet
+    fn record_session(&self, claims: &Claims, club: &str) {
+        let now = current_timestamp();
+        self.user()
+            .execute(
+                "INSERT OR IGNORE INTO session (id, name, club, issued, expiry, last_seen)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                (
+                    session_key(claims.session),
+                    &claims.name,
+                    club,
+                    now,
+                    claims.expiry,
+                    now,
+                ),
+            )
+            .inspect_err(|err| {
+                error!("When recording session of user {}, {}", claims.name, err);
+            })
+            .ok();
+    }
+
+    /// Returns the club database the session was opened on
+    fn session_club(&self, id: u128) -> Option<String> {
+        self.user_read()
+            .query_row(
+                "SELECT club FROM session WHERE id = ?1",
+                [session_key(id)],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .ok()
+            .flatten()
+    }
+
+    /// Opens a session of the user on the database of `club`, sharing the login with other
+    /// sessions on the database; The database is connected and migrated before the login map
+    /// is locked, so that a slow migration does not hold up other requests
+    fn open_session(
+        &self,
+        token: Token,
+        id: u128,
+        user: &str,
+        club: &str,
+    ) -> Option<Arc<crate::Login>> {
+        // The read lock is released before connecting
+        let open = self.login_map().find(&token, club);
+        let login = match open {
+            Some(login) => login,
+            None => Arc::new(connect(club)?),
+        };
+        Some(self.login_map_mut().insert(token, id, user, login))
+    }
+
+    /// Starts a new session for the user on the club database, returning its signed token;
+    /// Returns None if the database cannot be opened
+    pub(crate) fn start_session(&self, name: &str, club: &str) -> Option<Token> {
+        let claims = Claims::new(name);
+        let token = claims.sign(&self.secret);
+        self.open_session(token.clone(), claims.session, name, club)?;
+        self.record_session(&claims, club);
+        Some(token)
+    }
+
+    /// Attempts to log out of the program, revoking the token; Returns true on success
+    pub fn log_out(&self, token: &Token) -> bool {
+        let Some(claims) = Claims::verify(token, &self.secret) else {
+            warn!("When logging out, the given token is not valid");
+            return false;
+        };
+        let id = session_key(claims.session);
+        trace!(
+            "Attempt to log out of session {} of user {}",
+            id,
+            claims.name
+        );
+        if self.revoke(&claims.name, claims.session, claims.expiry) {
+            trace!("User {} logged out of session {}", claims.name, id);
+            true
+        } else {
+            warn!(
+                "When logging out, session {} of user {} is already revoked",
+                id, claims.name
+            );
+            false
+        }
+    }
+
+    /// Closes an idle session without revoking its token, so that it is reopened on the next request;
+    /// The user database is closed once no session of the user is open
+    pub fn evict(&self, token: &Token) {
+        let Some(session) = self.login_map_mut().remove(token) else {
+            return;
+        };
+        trace!("Closed idle session of user {}", session.user);
+        self.user()
+            .execute(
+                "UPDATE session SET last_seen = ?1 WHERE id = ?2",
+                (unix_timestamp(session.last_seen()), session_key(session.id)),
+            )
+            .inspect_err(|err| {
+                error!("When saving session of user {}, {}", session.user, err);
+            })
+            .ok();
+    }
+
+    /// Gets the login of a valid token along with the role of its user, reopening the
+    /// database if the token was issued before a restart or an idle eviction
+    pub fn get_login(&self, token: &Token) -> Option<crate::Access> {
+        let claims = Claims::verify(token, &self.secret)?;
+        trace!(
+            "Request with session {} of user {}",
+            session_key(claims.session),
+            claims.name
+        );
+        let open = self.login_map().get(token).map(|session| {
+            session.touch();
+            session.login.clone()
+        });
+        let login = match open {
+            Some(login) => login,
+            None => {
+                if self.is_revoked(claims.session) {
+                    warn!(
+                        "Session {} of user {} has been revoked",
+                        session_key(claims.session),
+                        claims.name
+                    );
+                    return None;
+                }
+                if !self.exists(&claims.name) {
+                    warn!("User {} of the session no longer exists", claims.name);
+                    return None;
+                }
+                let club = self
+                    .session_club(claims.session)
+                    .unwrap_or_else(|| claims.name.clone());
+                if self.role(&claims.name, &club).is_none() {
+                    warn!("User {} is no longer a member of {}", claims.name, club);
+                    return None;
+                }
+                trace!("Restoring session of user {} on {}", claims.name, club);
+                let login =
+                    self.open_session(token.clone(), claims.session, &claims.name, &club)?;
+                self.record_session(&claims, &club);
+                login
+            }
+        };
+        // Roles are looked up on each request so that revoking a membership takes effect at once
+        let Some(role) = self.role(&claims.name, &login.name) else {
+            warn!(
+                "User {} is no longer a member of {}",
+                claims.name, login.name
+            );
+            return None;
+        };
+        Some(crate::Access {
+            user: claims.name,
+            role,
+            login,
+        })
+    }
+
+    /// Lists the unexpired sessions of the user, marking the one of the given token as current
+    pub fn sessions(&self, name: &str, current: Option<&Token>) -> Vec<SessionInfo> {
+        let current = current
+            .and_then(|token| Claims::verify(token, &self.secret))
+            .map(|claims| claims.session);
+        let rows = self
+            .user_read()
+            .prepare(
+                "SELECT id, issued, expiry, last_seen FROM session
+                    WHERE name = ?1 AND expiry >= ?2 ORDER BY issued",
+            )
+            .and_then(|mut stmt| {
+                stmt.query_map((name, current_timestamp()), |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, u64>(1)?,
+                        row.get::<_, u64>(2)?,
+                        row.get::<_, u64>(3)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .inspect_err(|err| {
+                error!("When listing sessions of user {}, {}", name, err);
+            })
+            .unwrap_or_default();
+        let map = self.login_map();
+        rows.into_iter()
+            .filter_map(|(id, issued, expiry, last_seen)| {
+                let value = u128::from_str_radix(&id, 16).ok()?;
+                // Open sessions have a more recent time than the stored one
+                let last_seen = map
+                    .values()
+                    .find(|session| session.id == value)
+                    .map(|session| unix_timestamp(session.last_seen()))
+                    .unwrap_or(last_seen);
+                Some(SessionInfo {
+                    id,
+                    issued,
+                    expiry,
+                    last_seen,
+                    current: current == Some(value),
+                })
+            })
+            .collect()
+    }
+
+    /// Revokes a session of the user by its id, returning true on success
+    pub fn revoke_session(&self, name: &str, id: &str) -> bool {
+        let Ok(value) = u128::from_str_radix(id, 16) else {
+            warn!("Session id {:?} is not legal", id);
+            return false;
+        };
+        let expiry = self.user_read().query_row(
+            "SELECT expiry FROM session WHERE id = ?1 AND name = ?2",
+            (session_key(value), name),
+            |row| row.get::<_, u64>(0),
+        );
+        match expiry {
+            Ok(expiry) => {
+                info!("Revoking session {} of user {}", id, name);
+                self.revoke(name, value, expiry)
+            }
+            Err(_) => {
+                warn!("Session {} of user {} is not found", id, name);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::tests::clean_up;
+
+    const SECRET: &[u8] = b"secret";
+
+    fn claims(expiry: u64) -> Claims {
+        Claims {
+            name: "someone".to_owned(),
+            session: 0x1234,
+            expiry,
+        }
+    }
+
+    #[test]
+    fn test_claims_round_trip() {
+        let claims = claims(current_timestamp() + 60);
+        let token = claims.sign(SECRET);
+        assert_eq!(Claims::verify(&token, SECRET), Some(claims));
+    }
+
+    #[test]
+    fn test_claims_tampered() {
+        let claims = claims(current_timestamp() + 60);
+        let token = claims.sign(SECRET);
+        assert!(Claims::verify(&token, b"other").is_none());
+
+        let (payload, sig) = token.as_str().rsplit_once('.').unwrap();
+        let forged = payload.replacen("someone", "admin", 1);
+        assert!(Claims::verify(&Token::new(format!("{}.{}", forged, sig)), SECRET).is_none());
+        let mut sig = sig.to_owned();
+        let last = if sig.ends_with('0') { "1" } else { "0" };
+        sig.replace_range(sig.len() - 1.., last);
+        assert!(Claims::verify(&Token::new(format!("{}.{}", payload, sig)), SECRET).is_none());
+        assert!(Claims::verify(&Token::new(payload.to_owned()), SECRET).is_none());
+    }
+
+    #[test]
+    fn test_claims_expired() {
+        let token = claims(current_timestamp() - 1).sign(SECRET);
+        assert!(Claims::verify(&token, SECRET).is_none());
+    }
+
+    #[test]
+    fn test_revoked_session() {
+        let db = Db::in_memory();
+        let name = "sessionrevoked";
+        clean_up(name);
+        assert!(db.create(name, "pass"));
+        let (first, _) = db.log_in(name, "pass", None).unwrap();
+        let (second, _) = db.log_in(name, "pass", None).unwrap();
+        assert!(db.log_out(&first));
+        assert!(!db.log_out(&first));
+        assert!(db.get_login(&first).is_none());
+        assert!(db.get_login(&second).is_some());
+
+        // A revoked token is not restored after its session is closed, unlike a valid one
+        db.evict(&second);
+        assert!(db.get_login(&second).is_some());
+        db.login_map_mut().remove_user(name);
+        assert!(db.get_login(&first).is_none());
+        assert!(db.revoke_session(
+            name,
+            &session_key(Claims::verify(&second, &db.secret).unwrap().session)
+        ));
+        assert!(db.get_login(&second).is_none());
+        db.login_map_mut().remove_user(name);
+        clean_up(name);
+    }
+}
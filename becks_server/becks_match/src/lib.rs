@@ -1,5 +1,7 @@
 mod mat;
 mod prelude;
+mod stats;
 
 pub use becks_crew::Id;
 pub use mat::*;
+pub use stats::*;
@@ -1,11 +1,75 @@
 use crate::prelude::*;
 
+/// The points of both crews in a game, following the rules of table tennis
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct GameScore {
+    pub left: u32,
+    pub right: u32,
+}
+
+impl GameScore {
+    /// The points a crew needs to win a game
+    pub const GAME_POINT: u32 = 11;
+
+    pub fn new(left: u32, right: u32) -> Self {
+        Self { left, right }
+    }
+
+    /// Returns whether the game is finished by the rules, where the winner reaches 11 points
+    /// with a lead of at least 2, and a game past 10-all ends as soon as the lead is 2
+    pub fn is_valid(&self) -> bool {
+        let (winner, loser) = (self.left.max(self.right), self.left.min(self.right));
+        if loser + 1 < Self::GAME_POINT {
+            winner == Self::GAME_POINT
+        } else {
+            winner == loser + 2
+        }
+    }
+
+    /// Returns whether the left crew wins, or None if the game is not valid
+    pub fn left_win(&self) -> Option<bool> {
+        self.is_valid().then_some(self.left > self.right)
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, Clone, Copy)]
 pub struct Round {
     pub left_win: bool,
+    /// The points of the game, if they are written down
+    #[serde(default)]
+    pub points: Option<GameScore>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default, TryFromPrimitive, IntoPrimitive, Clone, Copy)]
+impl Round {
+    /// Creates a round where only the winner is known
+    pub fn new(left_win: bool) -> Self {
+        Self {
+            left_win,
+            points: None,
+        }
+    }
+
+    /// Creates a round from the points, or returns None if the game is not valid
+    pub fn with_points(points: GameScore) -> Option<Self> {
+        Some(Self {
+            left_win: points.left_win()?,
+            points: Some(points),
+        })
+    }
+}
+
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    Default,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
 #[repr(u8)]
 pub enum Quit {
     #[default]
@@ -30,6 +94,15 @@ pub struct Match {
     pub left_earn: i32,
     #[serde(default)]
     pub right_earn: i32,
+    /// Whether the match is voided, so that it takes no effect on scores
+    #[serde(default)]
+    pub voided: bool,
+    /// The partner of the left crew in a doubles match
+    #[serde(default)]
+    pub left_partner: Option<Id>,
+    /// The partner of the right crew in a doubles match
+    #[serde(default)]
+    pub right_partner: Option<Id>,
 }
 
 impl Match {
@@ -38,13 +111,137 @@ impl Match {
             total_rounds,
             left,
             right,
-            round_worth: (total_rounds as u32 * 10).div_ceil(3),
+            round_worth: Self::default_round_worth(total_rounds),
             rounds: Default::default(),
             timestamp,
             quit: Default::default(),
             notes: Default::default(),
             left_earn: Default::default(),
             right_earn: Default::default(),
+            voided: false,
+            left_partner: None,
+            right_partner: None,
+        }
+    }
+
+    /// Creates a doubles match, where each side has a crew and its partner
+    pub fn doubles(total_rounds: usize, left: [Id; 2], right: [Id; 2], timestamp: u64) -> Self {
+        Self {
+            left_partner: Some(left[1]),
+            right_partner: Some(right[1]),
+            ..Self::new(total_rounds, left[0], right[0], timestamp)
         }
     }
+
+    pub fn is_doubles(&self) -> bool {
+        self.left_partner.is_some() && self.right_partner.is_some()
+    }
+
+    /// Returns whether both sides have the same number of crews,
+    /// so that a partner is never given on one side only
+    pub fn has_balanced_sides(&self) -> bool {
+        self.left_partner.is_some() == self.right_partner.is_some()
+    }
+
+    /// Returns the crews on the left side
+    pub fn left_side(&self) -> Vec<Id> {
+        std::iter::once(self.left)
+            .chain(self.left_partner)
+            .collect()
+    }
+
+    /// Returns the crews on the right side
+    pub fn right_side(&self) -> Vec<Id> {
+        std::iter::once(self.right)
+            .chain(self.right_partner)
+            .collect()
+    }
+
+    /// Returns all crews playing in the match
+    pub fn crews(&self) -> Vec<Id> {
+        let mut crews = self.left_side();
+        crews.extend(self.right_side());
+        crews
+    }
+
+    /// Returns whether no crew plays more than once in the match
+    pub fn has_distinct_crews(&self) -> bool {
+        let crews = self.crews();
+        crews
+            .iter()
+            .enumerate()
+            .all(|(index, crew)| !crews[index + 1..].contains(crew))
+    }
+
+    /// Returns whether the rounds are consistent with the total, where a match that
+    /// a crew quits may end before all rounds are played
+    pub fn is_complete(&self) -> bool {
+        match self.quit {
+            Quit::Normal => self.rounds.len() == self.total_rounds,
+            Quit::LeftQuit | Quit::RightQuit => self.rounds.len() <= self.total_rounds,
+        }
+    }
+
+    /// Returns whether the left side wins the match, where the side that quits loses,
+    /// or None if neither side wins more rounds
+    pub fn left_won(&self) -> Option<bool> {
+        match self.quit {
+            Quit::LeftQuit => Some(false),
+            Quit::RightQuit => Some(true),
+            Quit::Normal => {
+                let wins = self.rounds.iter().filter(|round| round.left_win).count();
+                let losses = self.rounds.len() - wins;
+                (wins != losses).then_some(wins > losses)
+            }
+        }
+    }
+
+    /// Returns the round worth of a match with the given number of rounds
+    pub fn default_round_worth(total_rounds: usize) -> u32 {
+        (total_rounds as u32 * 10).div_ceil(3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_score() {
+        for (left, right, valid) in [
+            (11, 9, true),
+            (11, 0, true),
+            (11, 10, false),
+            (12, 10, true),
+            (13, 10, false),
+            (13, 11, true),
+            (10, 8, false),
+            (0, 0, false),
+        ] {
+            assert_eq!(
+                GameScore::new(left, right).is_valid(),
+                valid,
+                "{}-{}",
+                left,
+                right
+            );
+            assert_eq!(
+                GameScore::new(right, left).is_valid(),
+                valid,
+                "{}-{}",
+                right,
+                left
+            );
+        }
+    }
+
+    #[test]
+    fn test_left_win() {
+        assert_eq!(GameScore::new(11, 9).left_win(), Some(true));
+        assert_eq!(GameScore::new(10, 12).left_win(), Some(false));
+        assert_eq!(GameScore::new(11, 10).left_win(), None);
+        let round = Round::with_points(GameScore::new(8, 11)).unwrap();
+        assert!(!round.left_win);
+        assert!(Round::with_points(GameScore::new(10, 8)).is_none());
+    }
 }
@@ -0,0 +1,183 @@
+use crate::mat::Match;
+use crate::prelude::*;
+use std::collections::HashMap;
+
+/// Matches and rounds won and lost, where drawn matches count as neither
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct Record {
+    pub wins: usize,
+    pub losses: usize,
+    pub rounds_won: usize,
+    pub rounds_lost: usize,
+}
+
+impl Record {
+    /// Adds the match played on the side, returns whether the side wins it
+    fn add(&mut self, on_left: bool, mat: &Match) -> Option<bool> {
+        let rounds_won = mat
+            .rounds
+            .iter()
+            .filter(|round| round.left_win == on_left)
+            .count();
+        self.rounds_won += rounds_won;
+        self.rounds_lost += mat.rounds.len() - rounds_won;
+        let won = mat.left_won().map(|left_won| left_won == on_left);
+        match won {
+            Some(true) => self.wins += 1,
+            Some(false) => self.losses += 1,
+            None => {}
+        }
+        won
+    }
+
+    /// Returns the ratio of matches won to matches decided
+    pub fn win_rate(&self) -> Option<f64> {
+        let decided = self.wins + self.losses;
+        (decided != 0).then(|| self.wins as f64 / decided as f64)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CrewStats {
+    pub record: Record,
+    /// Wins in a row up to the latest match
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    /// The average score the opposing side had right before each match
+    pub average_opponent_score: Option<f64>,
+    /// The record against the opponent asked for, if any
+    pub head_to_head: Option<Record>,
+}
+
+impl CrewStats {
+    /// Computes the stats of the crew from the matches and their ids, which are sorted from
+    /// the earliest, where `scores` holds the score of each opponent right before each match,
+    /// keyed by the match and the opponent; Matches the crew does not play in are skipped,
+    /// and so are opponents without a score
+    pub fn new<'m>(
+        crew: Id,
+        matches: impl IntoIterator<Item = (Id, &'m Match)>,
+        scores: &HashMap<(Id, Id), Score>,
+        opponent: Option<Id>,
+    ) -> Self {
+        let mut stats = Self {
+            head_to_head: opponent.map(|_| Record::default()),
+            ..Default::default()
+        };
+        let mut opponent_total = 0.0;
+        let mut opponent_count = 0usize;
+        for (id, mat) in matches {
+            let (on_left, opponents) = if mat.left_side().contains(&crew) {
+                (true, mat.right_side())
+            } else if mat.right_side().contains(&crew) {
+                (false, mat.left_side())
+            } else {
+                continue;
+            };
+            let won = stats.record.add(on_left, mat);
+            if won == Some(true) {
+                stats.current_streak += 1;
+                stats.longest_streak = stats.longest_streak.max(stats.current_streak);
+            } else {
+                stats.current_streak = 0;
+            }
+            if let (Some(record), Some(opponent)) = (stats.head_to_head.as_mut(), opponent) {
+                if opponents.contains(&opponent) {
+                    record.add(on_left, mat);
+                }
+            }
+            let known = opponents
+                .iter()
+                .filter_map(|opponent| scores.get(&(id, *opponent)))
+                .map(|score| score.0 as f64)
+                .collect::<Vec<_>>();
+            if !known.is_empty() {
+                opponent_total += known.iter().sum::<f64>() / known.len() as f64;
+                opponent_count += 1;
+            }
+        }
+        stats.average_opponent_score =
+            (opponent_count != 0).then(|| opponent_total / opponent_count as f64);
+        stats
+    }
+}
+
+/// The score of a crew right after a match it plays
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct ScorePoint {
+    pub mat: Id,
+    pub timestamp: u64,
+    pub score: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mat::Round;
+
+    const CREW: Id = Id::from_prim(1);
+    const RIVAL: Id = Id::from_prim(2);
+    const OTHER: Id = Id::from_prim(3);
+
+    /// A match of three rounds, where the crew wins the given number of them
+    fn play(id: u32, opponent: Id, won: usize) -> (Id, Match) {
+        let mut mat = Match::new(3, CREW, opponent, id as u64);
+        mat.rounds = (0..3).map(|index| Round::new(index < won)).collect();
+        (Id::from_prim(id), mat)
+    }
+
+    #[test]
+    fn test_streaks() {
+        let matches = [
+            play(1, RIVAL, 2),
+            play(2, OTHER, 3),
+            play(3, RIVAL, 1),
+            play(4, OTHER, 2),
+            play(5, RIVAL, 3),
+            play(6, OTHER, 2),
+        ];
+        let stats = CrewStats::new(
+            CREW,
+            matches.iter().map(|(id, mat)| (*id, mat)),
+            &HashMap::new(),
+            None,
+        );
+        assert_eq!(stats.record.wins, 5);
+        assert_eq!(stats.record.losses, 1);
+        assert_eq!(stats.record.rounds_won, 13);
+        assert_eq!(stats.record.rounds_lost, 5);
+        assert_eq!(stats.longest_streak, 3);
+        assert_eq!(stats.current_streak, 3);
+        assert!(stats.head_to_head.is_none());
+        assert!(stats.average_opponent_score.is_none());
+    }
+
+    #[test]
+    fn test_head_to_head() {
+        let mut doubles = Match::doubles(3, [RIVAL, OTHER], [CREW, Id::from_prim(4)], 4);
+        doubles.rounds = vec![Round::new(true); 2];
+        let matches = [
+            play(1, RIVAL, 2),
+            play(2, OTHER, 0),
+            play(3, RIVAL, 1),
+            (Id::from_prim(4), doubles),
+        ];
+        let scores = [((1, RIVAL), 1000), ((2, OTHER), 1200), ((3, RIVAL), 1100)]
+            .into_iter()
+            .map(|((mat, crew), score)| ((Id::from_prim(mat), crew), Score(score)))
+            .collect::<HashMap<_, _>>();
+        let stats = CrewStats::new(
+            CREW,
+            matches.iter().map(|(id, mat)| (*id, mat)),
+            &scores,
+            Some(RIVAL),
+        );
+        let record = stats.head_to_head.unwrap();
+        // The doubles match is lost on the right side against the rival
+        assert_eq!((record.wins, record.losses), (1, 2));
+        assert_eq!((record.rounds_won, record.rounds_lost), (3, 5));
+        assert_eq!(stats.current_streak, 0);
+        // The doubles match has no known score of the opponents
+        assert_eq!(stats.average_opponent_score, Some(1100.0));
+    }
+}
@@ -0,0 +1,7 @@
+use crate::event::acquire_event_with;
+use crate::prelude::*;
+use becks_arrange::*;
+
+pub fn acquire_arrangement(login: &Login, id: Id) -> Option<Arrangement> {
+    acquire_event_with(&login.read(), id)
+}
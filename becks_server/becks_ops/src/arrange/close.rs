@@ -0,0 +1,30 @@
+use crate::event::{acquire_event_with, save_event};
+use crate::prelude::*;
+use becks_arrange::*;
+
+#[derive(Debug, Error)]
+pub enum CloseArrangementError {
+    #[error("arrangement {0:?} is not found")]
+    ArrangementNotFound(Id),
+    #[error("arrangement {0:?} is already closed")]
+    AlreadyClosed(Id),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Closes the arrangement, so that it is no longer listed as open or linked to new matches,
+/// returning its final state
+pub fn close_arrangement(login: &Login, id: Id) -> Result<Arrangement, CloseArrangementError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let mut arrangement = acquire_event_with::<Arrangement>(&tx, id)
+        .ok_or(CloseArrangementError::ArrangementNotFound(id))?;
+    if arrangement.closed {
+        return Err(CloseArrangementError::AlreadyClosed(id));
+    }
+    arrangement.closed = true;
+    save_event(&tx, id, &arrangement)?;
+    tx.commit()?;
+    info!("Closed arrangement {:?}", id);
+    Ok(arrangement)
+}
@@ -0,0 +1,60 @@
+use crate::crew::Column;
+use crate::prelude::*;
+use becks_arrange::*;
+
+#[derive(Debug, Error)]
+pub enum CreateArrangementError {
+    #[error("crew {0:?} is not found")]
+    CrewNotFound(Id),
+    #[error(transparent)]
+    Invalid(#[from] ArrangementError),
+    #[error("unable to serialize arrangement, {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Stores the groups as a new open arrangement through the connection, which should be a transaction
+pub(super) fn create_arrangement_with(
+    conn: &Connection,
+    groups: Vec<Group>,
+) -> Result<(Id, Arrangement), CreateArrangementError> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let arrangement = Arrangement::new(groups, timestamp)?;
+    for crew in arrangement.crews() {
+        if String::query_with(conn, crew).is_err() {
+            return Err(CreateArrangementError::CrewNotFound(crew));
+        }
+    }
+    let id = unused_id(conn, "arrangement");
+    conn.execute(
+        indoc! {"
+            INSERT INTO arrangement
+            (id, state, closed, timestamp)
+            VALUES ((:id), (:state), FALSE, (:timestamp))
+        "},
+        rusqlite::named_params! {
+            ":id": id.to_prim(),
+            ":state": serde_json::to_string(&arrangement)?,
+            ":timestamp": timestamp,
+        },
+    )?;
+    Ok((id, arrangement))
+}
+
+/// Stores the groups scheduled by the client as a new open arrangement
+pub fn create_arrangement(login: &Login, groups: Vec<Group>) -> Result<Id, CreateArrangementError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let (id, arrangement) = create_arrangement_with(&tx, groups)?;
+    tx.commit()?;
+    info!(
+        "Created arrangement {:?} of {} groups",
+        id,
+        arrangement.groups.len()
+    );
+    Ok(id)
+}
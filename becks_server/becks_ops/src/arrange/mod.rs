@@ -0,0 +1,97 @@
+mod acquire;
+mod close;
+mod create;
+mod list;
+
+pub use acquire::*;
+pub use close::*;
+pub use create::*;
+pub use list::*;
+
+#[cfg(test)]
+mod tests {
+    use super::create::create_arrangement_with;
+    use super::*;
+    use crate::event::{acquire_event_with, link_all, save_event};
+    use crate::prelude::*;
+    use becks_arrange::*;
+    use becks_match::{Match, Round};
+
+    fn crew(id: u32) -> Id {
+        Id::from_prim(id)
+    }
+
+    fn connect() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        becks_db::migrate(&mut conn).unwrap();
+        for id in 1..=4 {
+            conn.execute(
+                "INSERT INTO crew (id, name, score, deleted) VALUES (?1, ?2, 500, 0)",
+                (id, format!("crew {}", id)),
+            )
+            .unwrap();
+        }
+        conn
+    }
+
+    fn groups() -> Vec<Group> {
+        let mut group = Group {
+            all: vec![crew(1), crew(2), crew(3), crew(4)],
+            tables: 1,
+            schedule: Vec::new(),
+        };
+        group.arrange();
+        vec![group]
+    }
+
+    /// A match played after the arrangement is created
+    fn beat(winner: u32, loser: u32) -> Match {
+        let mut mat = Match::new(1, crew(winner), crew(loser), u64::MAX);
+        mat.rounds = vec![Round::new(true)];
+        mat
+    }
+
+    fn state(arrangement: &Arrangement) -> String {
+        serde_json::to_string(arrangement).unwrap()
+    }
+
+    #[test]
+    fn test_arrangement_round_trip() {
+        let conn = connect();
+        let (id, created) = create_arrangement_with(&conn, groups()).unwrap();
+        let loaded = acquire_event_with::<Arrangement>(&conn, id).unwrap();
+        assert_eq!(state(&loaded), state(&created));
+        assert_eq!(loaded.groups[0].schedule.len(), 6);
+        assert!(!loaded.closed);
+
+        link_all(&conn, crew(100), &beat(2, 1)).unwrap();
+        let linked = acquire_event_with::<Arrangement>(&conn, id).unwrap();
+        let done = linked.groups[0]
+            .schedule
+            .iter()
+            .filter(|short| short.is_done())
+            .collect::<Vec<_>>();
+        assert_eq!(done.len(), 1);
+        assert!(done[0].is_between(crew(1), crew(2)));
+        assert_eq!(done[0].mat, Some(crew(100)));
+
+        let mut closed = linked;
+        closed.closed = true;
+        save_event(&conn, id, &closed).unwrap();
+        link_all(&conn, crew(101), &beat(3, 4)).unwrap();
+        let loaded = acquire_event_with::<Arrangement>(&conn, id).unwrap();
+        assert!(loaded.closed);
+        assert_eq!(state(&loaded), state(&closed));
+    }
+
+    #[test]
+    fn test_arrangement_crew_not_found() {
+        let conn = connect();
+        let mut groups = groups();
+        groups[0].all.push(crew(5));
+        assert!(matches!(
+            create_arrangement_with(&conn, groups),
+            Err(CreateArrangementError::CrewNotFound(_))
+        ));
+    }
+}
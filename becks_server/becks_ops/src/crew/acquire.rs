@@ -0,0 +1,70 @@
+use super::Column;
+use crate::prelude::*;
+use std::collections::HashMap;
+
+/// Reads the column from the row, where NULL gives None
+fn column<T: Column>(row: &rusqlite::Row) -> rusqlite::Result<Option<T>> {
+    Ok(row.get::<_, Option<T::Target>>(T::name())?.map(T::acquire))
+}
+
+fn crew_from_row(row: &rusqlite::Row) -> rusqlite::Result<Option<CrewData>> {
+    let (Some(name), Some(social), Some(score), Some(score_applied)) = (
+        column::<String>(row)?,
+        column::<Social>(row)?,
+        column::<Score>(row)?,
+        column::<ScoreApplied>(row)?,
+    ) else {
+        return Ok(None);
+    };
+    Ok(Some(CrewData {
+        name,
+        social,
+        score,
+        gender: column(row)?,
+        clothes: column(row)?,
+        hand: column(row)?,
+        hold: column(row)?,
+        paddle: column(row)?,
+        red: column(row)?,
+        black: column(row)?,
+        beat: column(row)?,
+        score_applied,
+    }))
+}
+
+/// Acquires the data of all given crews with one query per batch,
+/// where crews that are not found or lack required columns are left out
+pub fn acquire_crews(login: &Login, crews: &[Id]) -> HashMap<Id, CrewData> {
+    let db = login.read();
+    let mut result = HashMap::new();
+    for batch in batches(crews) {
+        let rows = db
+            .prepare(&formatdoc! {"
+                    SELECT id, name, social, score, gender, clothes, hand, hold,
+                        paddle, red_rubber, black_rubber, beat, score_applied
+                    FROM crew
+                    WHERE id IN ({})
+                ",
+                placeholders(batch.len()),
+            })
+            .and_then(|mut stmt| {
+                stmt.query_map(rusqlite::params_from_iter(batch), |row| {
+                    Ok((Id::from_prim(row.get("id")?), crew_from_row(row)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .inspect_err(|err| {
+                error!("When acquiring crews, {}", err);
+            })
+            .unwrap_or_default();
+        for (id, crew) in rows {
+            match crew {
+                Some(crew) => {
+                    result.insert(id, crew);
+                }
+                None => warn!("Crew {:?} lacks required columns", id),
+            }
+        }
+    }
+    result
+}
@@ -2,26 +2,13 @@ use crate::prelude::*;
 
 /// Returns a new id if crew creation is successful
 pub fn create_crew(login: &Login, name: &str, social: Social) -> Option<Id> {
-    let id = loop {
-        let id = Id::rand();
-        if login
-            .db()
-            .query_row("SELECT id FROM crew WHERE id = ?1", [id.to_prim()], |row| {
-                row.get::<_, u32>(0)
-            })
-            .is_err()
-        {
-            break id;
-        }
-    };
+    let id = unused_id(&login.db(), "crew");
     if login
-        .db
-        .lock()
-        .unwrap()
+        .db()
         .execute(
             indoc! {"
-                INSERT INTO crew (id, name, social, score, deleted, score_applied)
-                VALUES ((:id), (:name), (:social), (:score), FALSE, FALSE)
+                INSERT INTO crew (id, name, social, score, initial_score, deleted, score_applied)
+                VALUES ((:id), (:name), (:social), (:score), (:score), FALSE, FALSE)
         "},
             rusqlite::named_params! {
                 ":id": id.to_prim(),
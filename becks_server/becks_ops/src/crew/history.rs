@@ -0,0 +1,66 @@
+use crate::prelude::*;
+use becks_match::ScorePoint;
+
+#[derive(Debug, Error)]
+pub enum CrewHistoryError {
+    #[error("crew {0:?} is not found")]
+    CrewNotFound(Id),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Returns the id, timestamp and earning of the crew of every match it plays that is not voided,
+/// in the order they are replayed
+pub(super) fn earns_with(conn: &Connection, crew: Id) -> rusqlite::Result<Vec<(Id, u64, i32)>> {
+    let mut stmt = conn.prepare(indoc! {"
+        SELECT id, timestamp,
+            CASE WHEN (:crew) IN (left, left_partner) THEN left_earn ELSE right_earn END AS earn
+        FROM match
+        WHERE NOT voided AND (:crew) IN (left, right, left_partner, right_partner)
+        ORDER BY timestamp, id
+    "})?;
+    let earns = stmt
+        .query_map(rusqlite::named_params! {":crew": crew.to_prim()}, |row| {
+            Ok((
+                Id::from_prim(row.get("id")?),
+                row.get::<_, u64>("timestamp")?,
+                row.get::<_, i32>("earn")?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(earns)
+}
+
+/// Rebuilds the score history of the crew from its initial score and the earnings of
+/// the matches it plays, in the order they are replayed, returning the initial score
+/// and the score after each match
+pub fn crew_history(login: &Login, crew: Id) -> Result<(i32, Vec<ScorePoint>), CrewHistoryError> {
+    let db = login.read();
+    let (score, initial) = db
+        .query_row(
+            "SELECT score, initial_score FROM crew WHERE id = (:id)",
+            rusqlite::named_params! {":id": crew.to_prim()},
+            |row| Ok((row.get::<_, i32>(0)?, row.get::<_, Option<i32>>(1)?)),
+        )
+        .map_err(|err| match err {
+            rusqlite::Error::QueryReturnedNoRows => CrewHistoryError::CrewNotFound(crew),
+            err => err.into(),
+        })?;
+    let earns = earns_with(&db, crew)?;
+    // Crews created before initial scores were kept start where their earnings end at the score
+    let initial =
+        initial.unwrap_or_else(|| score - earns.iter().map(|(_, _, earn)| earn).sum::<i32>());
+    let mut score = initial;
+    let history = earns
+        .into_iter()
+        .map(|(mat, timestamp, earn)| {
+            score += earn;
+            ScorePoint {
+                mat,
+                timestamp,
+                score,
+            }
+        })
+        .collect();
+    Ok((initial, history))
+}
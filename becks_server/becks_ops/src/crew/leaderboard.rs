@@ -0,0 +1,60 @@
+use super::Column;
+use crate::prelude::*;
+use becks_convey::crew::leaderboard::{LeaderboardFilter, LeaderboardRow};
+
+/// Ranks the crews that are not deleted and pass the filter by their scores,
+/// from the highest, where crews of the same score are sorted by name
+pub fn leaderboard(
+    login: &Login,
+    filter: &LeaderboardFilter,
+) -> rusqlite::Result<Vec<LeaderboardRow>> {
+    let db = login.read();
+    let mut stmt = db.prepare(indoc! {"
+        SELECT id, name, score, played FROM (
+            SELECT id, name, score, social, gender, hand, (
+                SELECT COUNT(*) FROM match
+                WHERE NOT voided AND crew.id IN (left, right, left_partner, right_partner)
+            ) AS played
+            FROM crew
+            WHERE NOT deleted
+        )
+        WHERE ((:social) IS NULL OR social = (:social))
+            AND ((:gender) IS NULL OR gender = (:gender))
+            AND ((:hand) IS NULL OR hand = (:hand))
+            AND played >= (:min_played)
+        ORDER BY score DESC, name
+    "})?;
+    let rows = stmt
+        .query_map(
+            rusqlite::named_params! {
+                ":social": filter.social.map(Column::convert),
+                ":gender": filter.gender.map(Column::convert),
+                ":hand": filter.hand.map(Column::convert),
+                ":min_played": filter.min_played,
+            },
+            |row| {
+                Ok((
+                    Id::from_prim(row.get("id")?),
+                    row.get::<_, String>("name")?,
+                    Score(row.get("score")?),
+                    row.get::<_, usize>("played")?,
+                ))
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let mut ranked = Vec::<LeaderboardRow>::with_capacity(rows.len());
+    for (index, (crew, name, score, played)) in rows.into_iter().enumerate() {
+        let rank = match ranked.last() {
+            Some(last) if last.score.0 == score.0 => last.rank,
+            _ => index + 1,
+        };
+        ranked.push(LeaderboardRow {
+            crew,
+            name,
+            score,
+            rank,
+            played,
+        });
+    }
+    Ok(ranked)
+}
@@ -1,9 +1,17 @@
+mod acquire;
 mod create;
 mod delete;
+mod history;
+mod leaderboard;
 mod modify;
 mod query;
+mod stats;
 
+pub use acquire::*;
 pub use create::*;
 pub use delete::*;
+pub use history::*;
+pub use leaderboard::*;
 pub use modify::*;
 pub use query::*;
+pub use stats::*;
@@ -5,44 +5,50 @@ pub trait Column: Sized {
     fn name() -> &'static str;
     fn convert(self) -> Self::Target;
     fn acquire(value: Self::Target) -> Self;
+    /// Modifies the column through the connection, which may be a transaction,
+    /// returning the number of modified rows
+    fn modify_with(self, conn: &Connection, crew: Id) -> rusqlite::Result<usize> {
+        debug!("Updating crew column {}", Self::name());
+        conn.execute(
+            &formatdoc! {"
+                    UPDATE crew
+                    SET {column} = (:value)
+                    WHERE id = (:id)
+                ",
+                column = Self::name(),
+            },
+            rusqlite::named_params! {
+                ":value": self.convert(),
+                ":id": crew.to_prim(),
+            },
+        )
+    }
+    /// Queries the column through the connection, which may be a transaction
+    fn query_with(conn: &Connection, crew: Id) -> rusqlite::Result<Self> {
+        conn.query_row(
+            &formatdoc! {"
+                SELECT {column} FROM crew
+                WHERE id = (:id)
+                ",
+                column = Self::name(),
+            },
+            rusqlite::named_params! {
+                ":id": crew.to_prim(),
+            },
+            |row| row.get::<_, Self::Target>(0),
+        )
+        .map(|value| Self::acquire(value))
+    }
     /// Returns true if modification is successful
     fn modify(self, login: &Login, crew: Id) -> bool {
-        debug!("Updating crew column {}", Self::name());
-        login
-            .db()
-            .execute(
-                &formatdoc! {"
-                        UPDATE crew
-                        SET {column} = (:value)
-                        WHERE id = (:id)
-                    ",
-                    column = Self::name(),
-                },
-                rusqlite::named_params! {
-                    ":value": self.convert(),
-                    ":id": crew.to_prim(),
-                },
-            )
+        self.modify_with(&login.db(), crew)
             .inspect_err(|err| {
                 error!("When modifying column {}, {}", Self::name(), err);
             })
             .is_ok_and(|modified| modified > 0)
     }
     fn query(login: &Login, crew: Id, required: bool) -> Option<Self> {
-        login
-            .db()
-            .query_row(
-                &formatdoc! {"
-                    SELECT {column} FROM crew
-                    WHERE id = (:id)
-                    ",
-                    column = Self::name(),
-                },
-                rusqlite::named_params! {
-                    ":id": crew.to_prim(),
-                },
-                |row| row.get::<_, Self::Target>(0),
-            )
+        Self::query_with(&login.read(), crew)
             .inspect_err(|err| {
                 if required {
                     warn!(
@@ -54,7 +60,6 @@ pub trait Column: Sized {
                 }
             })
             .ok()
-            .map(|value| Self::acquire(value))
     }
 }
 
@@ -99,6 +104,22 @@ impl Column for Score {
     fn acquire(value: Self::Target) -> Self {
         Self(value)
     }
+    /// Modifies the score, shifting the initial score by the same amount,
+    /// so that replaying the matches still arrives at the given score
+    fn modify_with(self, conn: &Connection, crew: Id) -> rusqlite::Result<usize> {
+        debug!("Updating crew column {}", Self::name());
+        conn.execute(
+            indoc! {"
+                UPDATE crew
+                SET initial_score = initial_score + (:value) - score, score = (:value)
+                WHERE id = (:id)
+            "},
+            rusqlite::named_params! {
+                ":value": self.0,
+                ":id": crew.to_prim(),
+            },
+        )
+    }
 }
 
 impl Column for Gender {
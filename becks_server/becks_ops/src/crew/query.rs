@@ -1,56 +1,21 @@
+use crate::page::{page_limit, PageQuery};
 use crate::prelude::*;
+use crate::Page;
+use becks_convey::page::PageRequest;
 
 pub trait Query {
-    fn query(self, login: &Login) -> Vec<Id>;
+    fn query(self, login: &Login) -> Page;
 }
 
 pub struct QueryBy {
     pub by: Vec<CrewLocation>,
     pub fuzzy: bool,
-}
-
-macro_rules! handle_query_by {
-    (iter $iter: ident, $login: ident) => {
-        $iter.map(|ids| {
-            ids.filter_map(|result| {
-                result
-                    .inspect_err(|err| {
-                        error!(
-                            "When querying an id(column not found?), {}",
-                            err
-                        );
-                    })
-                    .ok()
-                    .map(Id::from_prim)
-            })
-            .collect::<Vec<Id>>()
-        })
-        .unwrap_or_else(|err| {
-            error!("When querying all rows, {}",  err);
-            Vec::new()
-        })
-    };
-    ($sql: expr, $params: expr, $login: ident) => {{
-        debug!("Querying columns with sql {}", $sql);
-        match $login.db().prepare($sql) {
-            Ok(mut stmt) => {
-                let iter = stmt.query_map(
-                    $params,
-                    |row| row.get::<_, u32>(0),
-                );
-                handle_query_by!(iter iter, $login)
-            }
-            Err(err) => {
-                error!("When querying the table, {}", err);
-                Vec::new()
-            }
-        }
-    }};
+    pub page: PageRequest,
 }
 
 #[allow(dead_code)]
 fn show_id_names(login: &Login) {
-    let db = login.db();
+    let db = login.read();
     let mut stmt = db.prepare("SELECT id, name FROM crew").unwrap();
     let res = stmt
         .query_map([], |row| Ok((row.get_unwrap(0), row.get_unwrap(1))))
@@ -77,10 +42,10 @@ fn not_deleted(loc: &CrewLocation) -> bool {
 }
 
 impl Query for QueryBy {
-    fn query(mut self, login: &Login) -> Vec<Id> {
+    fn query(mut self, login: &Login) -> Page {
         use crate::crew::Column;
         use CrewLocation as Loc;
-        let mut sql = String::from("SELECT id FROM crew");
+        let mut sql = String::new();
         let mut params = Vec::new();
 
         if self.by.iter().all(not_deleted) {
@@ -130,16 +95,24 @@ impl Query for QueryBy {
                 }
             }
         }
-        #[allow(unused_assignments)]
+        let mut order = String::from(" ORDER BY id");
+        let mut order_params = Vec::new();
         if let Some(score) = fuzzy_score {
-            sql.push_str(&format!(" ORDER BY ABS({} - ?{})", Score::name(), pos));
-            params.push(box_sql(score.convert()));
-            pos += 1;
+            order = format!(" ORDER BY ABS({} - ?{}), id", Score::name(), pos);
+            order_params.push(box_sql(score.convert()));
         }
-        let params_ref = params
-            .iter()
-            .map(|boxed| boxed.as_ref())
-            .collect::<Vec<_>>();
-        handle_query_by!(&sql, params_ref.as_slice(), login)
+        let page = PageQuery {
+            table: "crew",
+            condition: sql,
+            params,
+            order,
+            order_params,
+        };
+        let limit = page_limit(self.page.limit, becks_db::CONFIG.db.crew_limit);
+        page.run(&login.read(), self.page.offset, limit)
+            .unwrap_or_else(|err| {
+                error!("When querying crew, {}", err);
+                Default::default()
+            })
     }
 }
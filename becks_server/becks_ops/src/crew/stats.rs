@@ -0,0 +1,64 @@
+use super::Column;
+use crate::prelude::*;
+use becks_match::CrewStats;
+use std::collections::HashMap;
+
+#[derive(Debug, Error)]
+pub enum CrewStatsError {
+    #[error("crew {0:?} is not found")]
+    CrewNotFound(Id),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Computes the stats of the crew from all matches it plays that are not voided,
+/// along with the head-to-head record against the opponent if given
+pub fn crew_stats(
+    login: &Login,
+    crew: Id,
+    opponent: Option<Id>,
+) -> Result<CrewStats, CrewStatsError> {
+    let ids = {
+        let db = login.read();
+        for crew in std::iter::once(crew).chain(opponent) {
+            if String::query_with(&db, crew).is_err() {
+                return Err(CrewStatsError::CrewNotFound(crew));
+            }
+        }
+        let mut stmt = db.prepare(indoc! {"
+            SELECT id FROM match
+            WHERE NOT voided AND (:crew) IN (left, right, left_partner, right_partner)
+            ORDER BY timestamp, id
+        "})?;
+        let ids = stmt
+            .query_map(rusqlite::named_params! {":crew": crew.to_prim()}, |row| {
+                Ok(Id::from_prim(row.get(0)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        ids
+    };
+    let matches = crate::mat::acquire_matches(login, &ids);
+    let matches = ids
+        .iter()
+        .filter_map(|id| matches.get(id).map(|mat| (*id, mat)))
+        .collect::<Vec<_>>();
+    let mut crews = matches
+        .iter()
+        .flat_map(|(_, mat)| mat.crews())
+        .collect::<Vec<_>>();
+    crews.sort();
+    crews.dedup();
+    // The score of a crew before a match is its current score without the earnings of
+    // the match and the matches replayed after it
+    let current = super::acquire_crews(login, &crews);
+    let db = login.read();
+    let mut scores = HashMap::new();
+    for (other, data) in current {
+        let mut score = data.score.0;
+        for (mat, _, earn) in super::history::earns_with(&db, other)?.into_iter().rev() {
+            score -= earn;
+            scores.insert((mat, other), Score(score));
+        }
+    }
+    Ok(CrewStats::new(crew, matches, &scores, opponent))
+}
@@ -0,0 +1,194 @@
+use crate::prelude::*;
+use becks_arrange::{Arrangement, Swiss};
+use becks_match::Match;
+use becks_tournament::Tournament;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// An event whose state is kept as JSON in the `state` column of its table, along with
+/// whether it is closed, and which recorded matches are linked to
+pub(crate) trait Event: Serialize + DeserializeOwned {
+    /// The table the events are kept in
+    const TABLE: &'static str;
+    /// What the event is called in logs
+    const NAME: &'static str;
+
+    fn is_closed(&self) -> bool;
+
+    fn set_closed(&mut self, closed: bool);
+
+    /// Links the recorded match, returns whether the event is changed
+    fn link(&mut self, id: Id, mat: &Match) -> bool;
+
+    /// Links the match again after it is voided or modified, where `mat` is the match as it is
+    /// now, or None if it no longer counts; Returns whether the event is changed
+    fn amend(&mut self, id: Id, mat: Option<&Match>) -> bool;
+}
+
+impl Event for Tournament {
+    const TABLE: &'static str = "tournament";
+    const NAME: &'static str = "tournament";
+
+    fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    fn set_closed(&mut self, closed: bool) {
+        self.closed = closed;
+    }
+
+    fn link(&mut self, id: Id, mat: &Match) -> bool {
+        self.record(id, mat).is_some()
+    }
+
+    fn amend(&mut self, id: Id, mat: Option<&Match>) -> bool {
+        Tournament::amend(self, id, mat).is_some()
+    }
+}
+
+impl Event for Swiss {
+    const TABLE: &'static str = "swiss";
+    const NAME: &'static str = "Swiss event";
+
+    fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    fn set_closed(&mut self, closed: bool) {
+        self.closed = closed;
+    }
+
+    fn link(&mut self, id: Id, mat: &Match) -> bool {
+        Swiss::link(self, id, mat)
+    }
+
+    fn amend(&mut self, id: Id, mat: Option<&Match>) -> bool {
+        Swiss::amend(self, id, mat)
+    }
+}
+
+impl Event for Arrangement {
+    const TABLE: &'static str = "arrangement";
+    const NAME: &'static str = "arrangement";
+
+    fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    fn set_closed(&mut self, closed: bool) {
+        self.closed = closed;
+    }
+
+    fn link(&mut self, id: Id, mat: &Match) -> bool {
+        Arrangement::link(self, id, mat)
+    }
+
+    fn amend(&mut self, id: Id, mat: Option<&Match>) -> bool {
+        Arrangement::amend(self, id, mat)
+    }
+}
+
+/// Reads the event from its state, where the closed column takes precedence
+pub(crate) fn event_from_row<E: Event>(row: &rusqlite::Row) -> rusqlite::Result<Option<E>> {
+    let state = row.get::<_, String>("state")?;
+    Ok(serde_json::from_str::<E>(&state)
+        .inspect_err(|err| {
+            error!("When parsing {} state, {}", E::NAME, err);
+        })
+        .ok()
+        .map(|mut event| {
+            event.set_closed(row.get("closed").unwrap_or(event.is_closed()));
+            event
+        }))
+}
+
+/// Acquires the event through the connection, which may be a transaction
+pub(crate) fn acquire_event_with<E: Event>(conn: &Connection, id: Id) -> Option<E> {
+    conn.query_row(
+        &format!("SELECT state, closed FROM {} WHERE id = (:id)", E::TABLE),
+        rusqlite::named_params! {":id": id.to_prim()},
+        event_from_row,
+    )
+    .inspect_err(|err| {
+        warn!("When acquiring {} {:?}, {}", E::NAME, id, err);
+    })
+    .ok()
+    .flatten()
+}
+
+/// Writes the state of the event back
+pub(crate) fn save_event<E: Event>(
+    conn: &Connection,
+    id: Id,
+    event: &E,
+) -> rusqlite::Result<usize> {
+    let state = serde_json::to_string(event)
+        .map_err(|err| rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+    conn.execute(
+        &format!(
+            "UPDATE {} SET state = (:state), closed = (:closed) WHERE id = (:id)",
+            E::TABLE
+        ),
+        rusqlite::named_params! {
+            ":state": state,
+            ":closed": event.is_closed(),
+            ":id": id.to_prim(),
+        },
+    )
+}
+
+/// Loads the events, only the open ones if `open` is true
+fn load_events<E: Event>(conn: &Connection, open: bool) -> rusqlite::Result<Vec<(Id, E)>> {
+    let filter = if open { "WHERE NOT closed" } else { "" };
+    let events = conn
+        .prepare(&format!(
+            "SELECT id, state, closed FROM {} {}",
+            E::TABLE,
+            filter
+        ))?
+        .query_map([], |row| {
+            Ok((Id::from_prim(row.get("id")?), event_from_row::<E>(row)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(events
+        .into_iter()
+        .filter_map(|(id, event)| Some((id, event?)))
+        .collect())
+}
+
+/// Links the recorded match to every open event of the kind
+fn link_events<E: Event>(conn: &Connection, id: Id, mat: &Match) -> rusqlite::Result<()> {
+    for (event_id, mut event) in load_events::<E>(conn, true)? {
+        if event.link(id, mat) {
+            debug!("Match {:?} is linked to {} {:?}", id, E::NAME, event_id);
+            save_event(conn, event_id, &event)?;
+        }
+    }
+    Ok(())
+}
+
+/// Links the match again in every event of the kind it is linked to, closed or not
+fn amend_events<E: Event>(conn: &Connection, id: Id, mat: Option<&Match>) -> rusqlite::Result<()> {
+    for (event_id, mut event) in load_events::<E>(conn, false)? {
+        if event.amend(id, mat) {
+            info!("Match {:?} is amended in {} {:?}", id, E::NAME, event_id);
+            save_event(conn, event_id, &event)?;
+        }
+    }
+    Ok(())
+}
+
+/// Links the recorded match to the open events of every kind
+pub(crate) fn link_all(conn: &Connection, id: Id, mat: &Match) -> rusqlite::Result<()> {
+    link_events::<Tournament>(conn, id, mat)?;
+    link_events::<Swiss>(conn, id, mat)?;
+    link_events::<Arrangement>(conn, id, mat)
+}
+
+/// Links the match again in the events of every kind it is linked to, after the match is voided
+/// or modified, where `mat` is the match as it is now, or None if it no longer counts
+pub(crate) fn amend_all(conn: &Connection, id: Id, mat: Option<&Match>) -> rusqlite::Result<()> {
+    amend_events::<Tournament>(conn, id, mat)?;
+    amend_events::<Swiss>(conn, id, mat)?;
+    amend_events::<Arrangement>(conn, id, mat)
+}
@@ -1,4 +1,12 @@
+pub mod arrange;
 pub mod crew;
+mod event;
 pub mod mat;
+mod page;
 pub mod poster;
 mod prelude;
+pub mod rating;
+pub mod swiss;
+pub mod tournament;
+
+pub use page::Page;
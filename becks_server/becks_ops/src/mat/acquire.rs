@@ -1,93 +1,128 @@
 use crate::prelude::*;
 use becks_match::*;
+use std::collections::HashMap;
 
 pub fn acquire_round(login: &Login, round: Id, required: bool) -> Option<becks_match::Round> {
-    login
-        .db()
+    acquire_round_with(&login.read(), round, required)
+}
+
+/// Reads a round from the row, where the points are kept only if both are stored
+fn round_from_row(row: &rusqlite::Row) -> rusqlite::Result<Round> {
+    let left = row.get::<_, Option<u32>>("left_points")?;
+    let right = row.get::<_, Option<u32>>("right_points")?;
+    Ok(Round {
+        left_win: row.get("left_win")?,
+        points: left
+            .zip(right)
+            .map(|(left, right)| GameScore { left, right }),
+    })
+}
+
+/// Acquires the round through the connection, so that rounds of a match share one connection
+pub(super) fn acquire_round_with(
+    conn: &Connection,
+    round: Id,
+    required: bool,
+) -> Option<becks_match::Round> {
+    conn.query_row(
+        indoc! {"
+                SELECT left_win, left_points, right_points
+                FROM round
+                WHERE id = (:id)
+            "},
+        rusqlite::named_params! {
+            ":id": round.to_prim()
+        },
+        round_from_row,
+    )
+    .inspect_err(|err| {
+        if required {
+            error!("When acquiring required round {:?}, {}", round, err);
+        }
+    })
+    .ok()
+}
+
+/// Reads a match from the row, along with the ids of its rounds, which are not loaded
+pub(super) fn match_from_row(row: &rusqlite::Row) -> rusqlite::Result<(Vec<Id>, Match)> {
+    let rounds_str: String = row.get("rounds")?;
+    let mut rounds = Vec::new();
+    for round in rounds_str.split_whitespace() {
+        match round.parse::<u32>() {
+            Ok(round) => {
+                rounds.push(Id::from_prim(round));
+            }
+            Err(err) => {
+                error!(
+                    "When acquiring round, value {:?} is not an id, {}",
+                    round, err
+                );
+            }
+        }
+    }
+    let total_rounds = row
+        .get::<_, Option<u32>>("total_rounds")?
+        .map_or(rounds.len(), |total| total as usize);
+    Ok((
+        rounds,
+        Match {
+            total_rounds,
+            left: Id::from_prim(row.get("left")?),
+            right: Id::from_prim(row.get("right")?),
+            left_partner: row
+                .get::<_, Option<u32>>("left_partner")?
+                .map(Id::from_prim),
+            right_partner: row
+                .get::<_, Option<u32>>("right_partner")?
+                .map(Id::from_prim),
+            round_worth: row.get("round_worth")?,
+            timestamp: row.get("timestamp")?,
+            rounds: Default::default(),
+            quit: Quit::try_from(row.get::<_, u8>("quit")?).unwrap_or_else(|err| {
+                error!("When acquiring quit field in match, {}", err);
+                Default::default()
+            }),
+            notes: row.get("notes")?,
+            left_earn: row.get("left_earn")?,
+            right_earn: row.get("right_earn")?,
+            voided: row.get("voided")?,
+        },
+    ))
+}
+
+pub fn acquire_match(login: &Login, mat: Id, required: bool) -> Option<becks_match::Match> {
+    acquire_match_with(&login.read(), mat, required)
+}
+
+/// Acquires the match along with its rounds through the connection, which may be a transaction
+pub(super) fn acquire_match_with(
+    conn: &Connection,
+    mat: Id,
+    required: bool,
+) -> Option<becks_match::Match> {
+    let data = conn
         .query_row(
             indoc! {"
-                SELECT left_win
-                FROM round
+                SELECT left, right, round_worth, timestamp, rounds, quit, notes, left_earn, right_earn,
+                    voided, total_rounds, left_partner, right_partner
+                FROM match
                 WHERE id = (:id)
             "},
             rusqlite::named_params! {
-                ":id": round.to_prim()
-            },
-            |row| {
-                Ok(Round {
-                    left_win: row.get(0)?,
-                })
+                ":id": mat.to_prim(),
             },
+            match_from_row,
         )
         .inspect_err(|err| {
             if required {
-                error!("When acquiring required round {:?}, {}", round, err);
+                error!("When acquiring match, {}", err);
             }
         })
-        .ok()
-}
-
-pub fn acquire_match(login: &Login, mat: Id, required: bool) -> Option<becks_match::Match> {
-    let data = {
-        login
-            .db()
-            .query_row(
-                indoc! {"
-                SELECT left, right, round_worth, timestamp, rounds, quit, notes, left_earn, right_earn
-                FROM match
-                WHERE id = (:id)
-            "},
-                rusqlite::named_params! {
-                    ":id": mat.to_prim(),
-                },
-                |row| {
-                    let rounds_str: String = row.get("rounds")?;
-                    let mut rounds = Vec::new();
-                    for round in rounds_str.split_whitespace() {
-                        match round.parse::<u32>() {
-                            Ok(round) => {
-                                rounds.push(Id::from_prim(round));
-                            }
-                            Err(err) => {
-                                error!(
-                                    "When acquiring round, value {:?} is not an id, {}",
-                                    round, err
-                                );
-                            }
-                        }
-                    }
-                    let total_rounds = rounds.len();
-                    Ok((
-                        rounds,
-                        Match {
-                            total_rounds,
-                            left: Id::from_prim(row.get("left")?),
-                            right: Id::from_prim(row.get("right")?),
-                            round_worth: row.get("round_worth")?,
-                            timestamp: row.get("timestamp")?,
-                            rounds: Default::default(),
-                            quit: Quit::try_from(row.get::<_, u8>("quit")?).unwrap_or_else(|err| {
-                                error!("When acquiring quit field in match, {}", err);
-                                Default::default()
-                            }),
-                            notes: row.get("notes")?,
-                            left_earn: row.get("left_earn")?,
-                            right_earn: row.get("right_earn")?
-                        },
-                    ))
-                },
-            )
-            .inspect_err(|err| {
-                if required {
-                    error!("When acquiring match, {}", err);
-                }
-            })
-            .ok()
-    };
+        .ok();
     data.map(|(rounds_id, mut mat)| {
         let mut rounds = Vec::new();
         for round in rounds_id.into_iter() {
-            if let Some(round) = acquire_round(login, round, true) {
+            if let Some(round) = acquire_round_with(conn, round, true) {
                 rounds.push(round);
             } else {
                 error!("Invalid round id: {:?}", round);
@@ -97,3 +132,89 @@ pub fn acquire_match(login: &Login, mat: Id, required: bool) -> Option<becks_mat
         mat
     })
 }
+
+/// Acquires all given rounds through the connection, which may be a transaction,
+/// with one query per batch of rounds
+pub(super) fn acquire_rounds_with(
+    conn: &Connection,
+    round_ids: &[Id],
+) -> rusqlite::Result<HashMap<Id, Round>> {
+    let mut rounds = HashMap::new();
+    for batch in batches(round_ids) {
+        let mut stmt = conn.prepare(&formatdoc! {"
+                SELECT id, left_win, left_points, right_points
+                FROM round
+                WHERE id IN ({})
+            ",
+            placeholders(batch.len()),
+        })?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(batch), |row| {
+            Ok((Id::from_prim(row.get("id")?), round_from_row(row)?))
+        })?;
+        for row in rows {
+            let (id, round) = row?;
+            rounds.insert(id, round);
+        }
+    }
+    Ok(rounds)
+}
+
+/// Picks the rounds of a match from the acquired rounds, leaving out those not found
+pub(super) fn rounds_of(rounds_id: &[Id], rounds: &HashMap<Id, Round>) -> Vec<Round> {
+    rounds_id
+        .iter()
+        .filter_map(|round| {
+            let value = rounds.get(round).copied();
+            if value.is_none() {
+                error!("Invalid round id: {:?}", round);
+            }
+            value
+        })
+        .collect()
+}
+
+/// Acquires all given matches along with their rounds, with one query per batch of matches
+/// and one per batch of rounds, where matches that are not found are left out
+pub fn acquire_matches(login: &Login, mats: &[Id]) -> HashMap<Id, Match> {
+    let db = login.read();
+    let mut found = Vec::new();
+    for batch in batches(mats) {
+        db.prepare(&formatdoc! {"
+                SELECT id, left, right, round_worth, timestamp, rounds, quit, notes, left_earn,
+                    right_earn, voided, total_rounds, left_partner, right_partner
+                FROM match
+                WHERE id IN ({})
+            ",
+            placeholders(batch.len()),
+        })
+        .and_then(|mut stmt| {
+            stmt.query_map(rusqlite::params_from_iter(batch), |row| {
+                Ok((Id::from_prim(row.get("id")?), match_from_row(row)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .map(|rows| found.extend(rows))
+        .inspect_err(|err| {
+            error!("When acquiring matches, {}", err);
+        })
+        .ok();
+    }
+
+    let round_ids = found
+        .iter()
+        .flat_map(|(_, (rounds, _))| rounds.iter().copied())
+        .collect::<Vec<_>>();
+    let rounds = acquire_rounds_with(&db, &round_ids)
+        .inspect_err(|err| {
+            error!("When acquiring rounds of matches, {}", err);
+        })
+        .unwrap_or_default();
+
+    found
+        .into_iter()
+        .map(|(id, (rounds_id, mut mat))| {
+            mat.rounds = rounds_of(&rounds_id, &rounds);
+            (id, mat)
+        })
+        .collect()
+}
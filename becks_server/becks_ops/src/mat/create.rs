@@ -1,3 +1,4 @@
+use crate::crew::Column;
 use crate::prelude::*;
 use becks_match::*;
 
@@ -5,107 +6,219 @@ use becks_match::*;
 pub enum CreateMatchError {
     #[error("match is incomplete")]
     Incomplete,
+    #[error("crew {0:?} is not found")]
+    CrewNotFound(Id),
+    #[error("a crew cannot play more than once in a match")]
+    SameCrew,
+    #[error("a partner must be given on both sides or neither")]
+    OneSided,
+    #[error("points of round {0} break the rules")]
+    InvalidPoints(usize),
+    #[error("points of round {0} do not match its winner")]
+    WinnerMismatch(usize),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
 }
 
-fn create_match_id(login: &Login) -> Id {
-    loop {
-        let id = Id::rand();
-        if login
-            .db()
-            .query_row(
-                "SELECT id FROM match WHERE id = (:id)",
-                rusqlite::named_params! {":id": id.to_prim()},
-                |row| row.get::<_, u32>(0),
-            )
-            .is_err()
-        {
-            break id;
+/// Checks the points of the rounds, which must follow the rules and agree with the winner
+/// of the round
+pub(super) fn settle_rounds(rounds: &[Round]) -> Result<(), CreateMatchError> {
+    for (index, round) in rounds.iter().enumerate() {
+        if let Some(points) = round.points {
+            let left_win = points
+                .left_win()
+                .ok_or(CreateMatchError::InvalidPoints(index + 1))?;
+            if left_win != round.left_win {
+                return Err(CreateMatchError::WinnerMismatch(index + 1));
+            }
         }
     }
+    Ok(())
 }
 
-fn create_round_id(login: &Login) -> Id {
-    loop {
-        let id = Id::rand();
-        if login
-            .db()
-            .query_row(
-                "SELECT id FROM round WHERE id = (:id)",
-                rusqlite::named_params! {":id": id.to_prim()},
-                |row| row.get::<_, u32>(0),
-            )
-            .is_err()
-        {
-            break id;
+pub fn create_round(conn: &Connection, round: &Round) -> Result<Id, CreateMatchError> {
+    let id = unused_id(conn, "round");
+    conn.execute(
+        indoc! {"
+            INSERT INTO round
+            (id, left_win, left_points, right_points)
+            VALUES ((:id), (:left_win), (:left_points), (:right_points))
+        "},
+        rusqlite::named_params! {
+            ":id": id.to_prim(),
+            ":left_win": round.left_win,
+            ":left_points": round.points.map(|points| points.left),
+            ":right_points": round.points.map(|points| points.right),
+        },
+    )
+    .inspect_err(|err| {
+        error!("When creating a round, {}", err);
+    })?;
+    Ok(id)
+}
+
+/// Creates the rounds, returning their ids as stored in the match
+pub(super) fn create_rounds(
+    conn: &Connection,
+    rounds: &[Round],
+) -> Result<String, CreateMatchError> {
+    let mut ids = String::new();
+    let len = rounds.len();
+    for (index, round) in rounds.iter().enumerate() {
+        let round = create_round(conn, round)?;
+        ids.push_str(&round.to_prim().to_string());
+        if index + 1 != len {
+            ids.push(' ');
         }
     }
+    Ok(ids)
 }
 
-pub fn create_round(login: &Login, round: &Round) -> Result<Id, CreateMatchError> {
-    let id = create_round_id(login);
-    login
-        .db()
-        .execute(
-            indoc! {"
-                INSERT INTO round
-                (id, left_win)
-                VALUES ((:id), (:left_win))
-            "},
-            rusqlite::named_params! {
-                ":id": id.to_prim(),
-                ":left_win": round.left_win,
-            },
-        )
-        .inspect_err(|err| {
-            error!("When creating a round, {}", err);
-        })
-        .ok();
+pub fn create_match(conn: &Connection, mat: &Match) -> Result<Id, CreateMatchError> {
+    if !mat.is_complete() {
+        return Err(CreateMatchError::Incomplete);
+    }
+    let id = unused_id(conn, "match");
+    let rounds = create_rounds(conn, &mat.rounds)?;
+    info!(
+        "INSERTING {:?} {:?} {:?} {:?} {:?} {:?}",
+        id, mat.left, mat.right, mat.round_worth, rounds, mat.notes
+    );
+    conn.execute(
+        indoc! {"
+            INSERT INTO match
+            (id, left, right, round_worth, timestamp, rounds, quit, notes, left_earn, right_earn,
+                total_rounds, left_partner, right_partner)
+            VALUES ((:id), (:left), (:right), (:round_worth),
+                (:timestamp), (:rounds), (:quit), (:notes), (:left_earn), (:right_earn),
+                (:total_rounds), (:left_partner), (:right_partner))
+        "},
+        rusqlite::named_params! {
+            ":id": id.to_prim(),
+            ":left": mat.left.to_prim(),
+            ":right": mat.right.to_prim(),
+            ":round_worth": mat.round_worth,
+            ":timestamp": mat.timestamp,
+            ":rounds": rounds,
+            ":quit": u8::from(mat.quit),
+            ":notes": &mat.notes,
+            ":left_earn": mat.left_earn,
+            ":right_earn": mat.right_earn,
+            ":total_rounds": mat.total_rounds,
+            ":left_partner": mat.left_partner.map(|crew| crew.to_prim()),
+            ":right_partner": mat.right_partner.map(|crew| crew.to_prim()),
+        },
+    )
+    .inspect_err(|err| {
+        error!("When creating match, {}", err);
+    })?;
     Ok(id)
 }
 
-pub fn create_match(login: &Login, mat: &Match) -> Result<Id, CreateMatchError> {
-    if mat.total_rounds != mat.rounds.len() {
+/// Records a match in a single transaction, updating the score and beat list of all crews
+/// and inserting the match with its rounds, then advances the tournaments waiting for it
+/// and links it to the open Swiss events and arrangements;
+/// Nothing is changed if any step fails
+pub fn record_match(login: &Login, mat: &Match) -> Result<Id, CreateMatchError> {
+    record_match_with(&mut login.db(), mat)
+}
+
+/// Records a match on the given connection, as [`record_match`] does
+pub(crate) fn record_match_with(
+    conn: &mut Connection,
+    mat: &Match,
+) -> Result<Id, CreateMatchError> {
+    if !mat.is_complete() {
         return Err(CreateMatchError::Incomplete);
     }
-    let id = create_match_id(login);
-    let mut rounds = String::new();
-    let len = mat.rounds.len();
-    for (index, round) in mat.rounds.iter().enumerate() {
-        let round = create_round(login, round)?;
-        rounds.push_str(&round.to_prim().to_string());
-        if index + 1 != len {
-            rounds.push(' ');
+    let mut mat = mat.clone();
+    if !mat.has_distinct_crews() {
+        return Err(CreateMatchError::SameCrew);
+    }
+    if !mat.has_balanced_sides() {
+        return Err(CreateMatchError::OneSided);
+    }
+    settle_rounds(&mat.rounds)?;
+    let tx = conn.transaction()?;
+    for crew in mat.crews() {
+        if String::query_with(&tx, crew).is_err() {
+            return Err(CreateMatchError::CrewNotFound(crew));
         }
     }
-    info!(
-        "INSERTING {:?} {:?} {:?} {:?} {:?} {:?}",
-        id, mat.left, mat.right, mat.round_worth, rounds, mat.notes
-    );
-    login
-        .db()
-        .execute(
-            indoc! {"
-                INSERT INTO match
-                (id, left, right, round_worth, timestamp, rounds, quit, notes, left_earn, right_earn)
-                VALUES ((:id), (:left), (:right), (:round_worth),
-                    (:timestamp), (:rounds), (:quit), (:notes), (:left_earn), (:right_earn))
-            "},
-            rusqlite::named_params! {
-                ":id": id.to_prim(),
-                ":left": mat.left.to_prim(),
-                ":right": mat.right.to_prim(),
-                ":round_worth": mat.round_worth,
-                ":timestamp": mat.timestamp,
-                ":rounds": rounds,
-                ":quit": u8::from(mat.quit),
-                ":notes": &mat.notes,
-                ":left_earn": mat.left_earn,
-                ":right_earn": mat.right_earn
-            },
-        )
-        .inspect_err(|err| {
-            error!("When creating match, {}", err);
-        })
-        .ok();
+    (mat.left_earn, mat.right_earn) = super::update_crew(&tx, &mat)?;
+    let id = create_match(&tx, &mat)?;
+    crate::event::link_all(&tx, id, &mat)?;
+    tx.commit()?;
     Ok(id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: Id = Id::from_prim(1);
+    const RIGHT: Id = Id::from_prim(2);
+
+    fn connect() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        becks_db::migrate(&mut conn).unwrap();
+        conn.execute_batch(indoc! {"
+            INSERT INTO crew (id, name, score, deleted) VALUES (1, 'left', 500, 0);
+            INSERT INTO crew (id, name, score, deleted) VALUES (2, 'right', 500, 0);
+        "})
+            .unwrap();
+        conn
+    }
+
+    fn with_rounds(rounds: Vec<Round>) -> Match {
+        let mut mat = Match::new(rounds.len(), LEFT, RIGHT, 0);
+        mat.rounds = rounds;
+        mat
+    }
+
+    fn count(conn: &Connection, table: &str) -> u32 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_record_points() {
+        let mut conn = connect();
+        let rounds = [(11, 9), (12, 10), (7, 11)]
+            .into_iter()
+            .map(|(left, right)| Round::with_points(GameScore::new(left, right)).unwrap())
+            .collect();
+        record_match_with(&mut conn, &with_rounds(rounds)).unwrap();
+        assert_eq!(count(&conn, "match"), 1);
+        assert_eq!(count(&conn, "round"), 3);
+    }
+
+    #[test]
+    fn test_record_rejects_points() {
+        let mut conn = connect();
+        // The points of the second round give the game to the right crew
+        let mismatch = with_rounds(vec![
+            Round::new(true),
+            Round {
+                left_win: true,
+                points: Some(GameScore::new(9, 11)),
+            },
+        ]);
+        assert!(matches!(
+            record_match_with(&mut conn, &mismatch),
+            Err(CreateMatchError::WinnerMismatch(2))
+        ));
+        let unfinished = with_rounds(vec![Round {
+            left_win: true,
+            points: Some(GameScore::new(11, 10)),
+        }]);
+        assert!(matches!(
+            record_match_with(&mut conn, &unfinished),
+            Err(CreateMatchError::InvalidPoints(1))
+        ));
+        assert_eq!(count(&conn, "match"), 0);
+        assert_eq!(count(&conn, "round"), 0);
+    }
+}
@@ -2,10 +2,14 @@ mod acquire;
 mod create;
 mod modify;
 mod query;
+mod replay;
 mod update;
+mod void;
 
 pub use acquire::*;
 pub use create::*;
 pub use modify::*;
 pub use query::*;
+pub use replay::*;
 pub use update::*;
+pub use void::*;
@@ -1,18 +1,190 @@
+use super::acquire::acquire_match_with;
+use super::create::{create_rounds, settle_rounds};
+use super::{CreateMatchError, Replayed};
+use crate::crew::Column;
 use crate::prelude::*;
+use becks_convey::mat::modify::MatchModification;
+use becks_match::*;
 
-/// Returns true if modification is successful
-pub fn modify_match_notes(login: &Login, mat: Id, notes: &str) -> bool {
-    let db = login.db();
-    db.execute(
+#[derive(Debug, Error)]
+pub enum ModifyMatchError {
+    #[error("match {0:?} is not found")]
+    MatchNotFound(Id),
+    #[error("crew {0:?} is not found")]
+    CrewNotFound(Id),
+    #[error("a match must have at least one round")]
+    NoRounds,
+    #[error("match is incomplete")]
+    Incomplete,
+    #[error("a crew cannot play more than once in a match")]
+    SameCrew,
+    #[error("a partner must be given on both sides or neither")]
+    OneSided,
+    #[error(transparent)]
+    Update(#[from] CreateMatchError),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Replaces the rounds of the match, removing the old rounds
+fn replace_rounds(conn: &Connection, mat: Id, rounds: &[Round]) -> Result<(), ModifyMatchError> {
+    let old: String = conn.query_row(
+        "SELECT rounds FROM match WHERE id = (:id)",
+        rusqlite::named_params! {":id": mat.to_prim()},
+        |row| row.get(0),
+    )?;
+    let old = old
+        .split_whitespace()
+        .filter_map(|round| round.parse::<u32>().ok())
+        .collect::<Vec<_>>();
+    conn.execute(
+        &format!(
+            "DELETE FROM round WHERE id IN ({})",
+            placeholders(old.len())
+        ),
+        rusqlite::params_from_iter(old),
+    )?;
+    conn.execute(
+        "UPDATE match SET rounds = (:rounds) WHERE id = (:id)",
+        rusqlite::named_params! {
+            ":rounds": create_rounds(conn, rounds)?,
+            ":id": mat.to_prim(),
+        },
+    )?;
+    Ok(())
+}
+
+fn audit(
+    conn: &Connection,
+    editor: &str,
+    mat: Id,
+    old: &Match,
+    new: &Match,
+) -> Result<(), ModifyMatchError> {
+    let to_json = |mat: &Match| {
+        serde_json::to_string(mat).unwrap_or_else(|err| {
+            error!("When serializing match {:?} for audit, {}", mat, err);
+            String::new()
+        })
+    };
+    conn.execute(
+        indoc! {"
+            INSERT INTO match_audit (mat, editor, timestamp, old, new)
+            VALUES ((:mat), (:editor), (:timestamp), (:old), (:new))
+        "},
+        rusqlite::named_params! {
+            ":mat": mat.to_prim(),
+            ":editor": editor,
+            ":timestamp": current_timestamp(),
+            ":old": to_json(old),
+            ":new": to_json(new),
+        },
+    )?;
+    Ok(())
+}
+
+/// Modifies the given fields of the match in a single transaction, recording the old and new
+/// values in the audit table; If the score impact may change and the match is not voided,
+/// all matches are replayed, and the tournaments, Swiss events and arrangements it decides
+/// are re-derived;
+/// Nothing is changed if any step fails
+pub fn modify_match(
+    login: &Login,
+    editor: &str,
+    mat: Id,
+    modify: &MatchModification,
+) -> Result<Replayed, ModifyMatchError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let old = acquire_match_with(&tx, mat, false).ok_or(ModifyMatchError::MatchNotFound(mat))?;
+    let mut new = old.clone();
+    if let Some(notes) = modify.notes.as_ref() {
+        new.notes = notes.clone();
+    }
+    new.quit = modify.quit.unwrap_or(new.quit);
+    if let Some(rounds) = modify.rounds.as_ref() {
+        new.rounds = rounds.clone();
+        settle_rounds(&new.rounds)?;
+    }
+    // Without a given total, a normal match is as long as its rounds
+    new.total_rounds = match modify.total_rounds {
+        Some(total) => total,
+        None if modify.rounds.is_some() && new.quit == Quit::Normal => new.rounds.len(),
+        None => new.total_rounds.max(new.rounds.len()),
+    };
+    if new.total_rounds == 0 {
+        return Err(ModifyMatchError::NoRounds);
+    }
+    if !new.is_complete() {
+        return Err(ModifyMatchError::Incomplete);
+    }
+    if new.total_rounds != old.total_rounds {
+        new.round_worth = Match::default_round_worth(new.total_rounds);
+    }
+    new.left = modify.left.unwrap_or(new.left);
+    new.right = modify.right.unwrap_or(new.right);
+    if let Some([left_partner, right_partner]) = modify.partners {
+        new.left_partner = left_partner;
+        new.right_partner = right_partner;
+    }
+    new.timestamp = modify.timestamp.unwrap_or(new.timestamp);
+    if !new.has_distinct_crews() {
+        return Err(ModifyMatchError::SameCrew);
+    }
+    if !new.has_balanced_sides() {
+        return Err(ModifyMatchError::OneSided);
+    }
+    for crew in new.crews() {
+        if String::query_with(&tx, crew).is_err() {
+            return Err(ModifyMatchError::CrewNotFound(crew));
+        }
+    }
+
+    if modify.rounds.is_some() {
+        replace_rounds(&tx, mat, &new.rounds)?;
+    }
+    tx.execute(
         indoc! {"
             UPDATE match
-            SET notes = (:notes)
+            SET left = (:left), right = (:right), left_partner = (:left_partner),
+                right_partner = (:right_partner), round_worth = (:round_worth),
+                total_rounds = (:total_rounds), timestamp = (:timestamp), quit = (:quit),
+                notes = (:notes)
             WHERE id = (:id)
         "},
         rusqlite::named_params! {
-            ":notes": notes,
+            ":left": new.left.to_prim(),
+            ":right": new.right.to_prim(),
+            ":left_partner": new.left_partner.map(|crew| crew.to_prim()),
+            ":right_partner": new.right_partner.map(|crew| crew.to_prim()),
+            ":round_worth": new.round_worth,
+            ":total_rounds": new.total_rounds,
+            ":timestamp": new.timestamp,
+            ":quit": u8::from(new.quit),
+            ":notes": &new.notes,
             ":id": mat.to_prim(),
         },
-    )
-    .is_ok_and(|modifies| modifies > 0)
+    )?;
+    audit(&tx, editor, mat, &old, &new)?;
+    let replayed = if modify.affects_score() && !old.voided {
+        crate::event::amend_all(&tx, mat, Some(&new))?;
+        super::replay_with(&tx)?
+    } else {
+        Replayed::default()
+    };
+    tx.commit()?;
+    info!(
+        "Modified match {:?} by {}, changing the score of {} crews",
+        mat,
+        editor,
+        replayed.diffs.len()
+    );
+    Ok(replayed)
 }
@@ -0,0 +1,123 @@
+use super::acquire::{acquire_rounds_with, match_from_row, rounds_of};
+use super::CreateMatchError;
+use crate::prelude::*;
+use crate::rating::{INITIAL_DEVIATION, INITIAL_VOLATILITY};
+use becks_convey::mat::replay::ScoreDiff;
+use std::collections::HashMap;
+
+/// The result of replaying all matches
+#[derive(Debug, Default)]
+pub struct Replayed {
+    pub matches: usize,
+    pub diffs: Vec<ScoreDiff>,
+}
+
+fn crew_scores(conn: &Connection) -> rusqlite::Result<HashMap<Id, (String, Score)>> {
+    let mut stmt = conn.prepare("SELECT id, name, score FROM crew")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            Id::from_prim(row.get("id")?),
+            (row.get("name")?, Score(row.get("score")?)),
+        ))
+    })?;
+    rows.collect()
+}
+
+/// Loads all matches that are not voided with their rounds in the order they are replayed
+fn matches_in_order(conn: &Connection) -> rusqlite::Result<Vec<(Id, becks_match::Match)>> {
+    let mut stmt = conn.prepare(indoc! {"
+        SELECT id, left, right, round_worth, timestamp, rounds, quit, notes, left_earn, right_earn,
+            voided, total_rounds, left_partner, right_partner
+        FROM match
+        WHERE NOT voided
+        ORDER BY timestamp, id
+    "})?;
+    let found = stmt
+        .query_map([], |row| {
+            Ok((Id::from_prim(row.get("id")?), match_from_row(row)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let round_ids = found
+        .iter()
+        .flat_map(|(_, (rounds, _))| rounds.iter().copied())
+        .collect::<Vec<_>>();
+    let rounds = acquire_rounds_with(conn, &round_ids)?;
+    Ok(found
+        .into_iter()
+        .map(|(id, (rounds_id, mut mat))| {
+            mat.rounds = rounds_of(&rounds_id, &rounds);
+            (id, mat)
+        })
+        .collect())
+}
+
+/// Replays all matches through the connection, which should be a transaction
+pub(super) fn replay_with(conn: &Connection) -> Result<Replayed, CreateMatchError> {
+    let before = crew_scores(conn)?;
+    conn.execute(
+        indoc! {"
+            UPDATE crew
+            SET score = COALESCE(initial_score, (:score)), deviation = (:deviation),
+                volatility = (:volatility), beat = ''
+        "},
+        rusqlite::named_params! {
+            ":score": Score::default().0,
+            ":deviation": INITIAL_DEVIATION,
+            ":volatility": INITIAL_VOLATILITY,
+        },
+    )?;
+    let mats = matches_in_order(conn)?;
+    for (id, mat) in mats.iter() {
+        let (left_earn, right_earn) = super::update_crew(conn, mat)?;
+        conn.execute(
+            indoc! {"
+                UPDATE match
+                SET left_earn = (:left_earn), right_earn = (:right_earn)
+                WHERE id = (:id)
+            "},
+            rusqlite::named_params! {
+                ":left_earn": left_earn,
+                ":right_earn": right_earn,
+                ":id": id.to_prim(),
+            },
+        )?;
+    }
+    let after = crew_scores(conn)?;
+    let mut diffs = before
+        .into_iter()
+        .filter_map(|(crew, (name, before))| {
+            let after = after.get(&crew)?.1;
+            (before.0 != after.0).then_some(ScoreDiff {
+                crew,
+                name,
+                before,
+                after,
+            })
+        })
+        .collect::<Vec<_>>();
+    diffs.sort_by_key(|diff| (diff.before.0 - diff.after.0, diff.crew));
+    Ok(Replayed {
+        matches: mats.len(),
+        diffs,
+    })
+}
+
+/// Recomputes the score, rating and beat list of every crew by replaying all matches
+/// from the initial scores in timestamp order, and rewrites the earnings of every match;
+/// Nothing is changed if `dry_run` is set, where the changes are only reported
+pub fn replay_matches(login: &Login, dry_run: bool) -> Result<Replayed, CreateMatchError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let replayed = replay_with(&tx)?;
+    if dry_run {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+    }
+    info!(
+        "Replayed {} matches, changing the score of {} crews",
+        replayed.matches,
+        replayed.diffs.len()
+    );
+    Ok(replayed)
+}
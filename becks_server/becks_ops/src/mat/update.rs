@@ -1,63 +1,93 @@
+use super::CreateMatchError;
 use crate::crew::Column;
 use crate::prelude::*;
+use crate::rating::{self, Outcome, Rated, Rating};
+use becks_db::ForfeitPolicy;
 use becks_match::*;
 
-fn estimated_var(login: &Login, crew: Id, other: Id, round_worth: u32) -> f32 {
-    // TODO: Better estimation
-    round_worth as f32
+fn team_of(side: &[(Id, Rated)]) -> Rated {
+    Rated::team(&side.iter().map(|(_, rated)| *rated).collect::<Vec<_>>())
 }
 
-fn calc_elo(lhs: Score, rhs: Score) -> f32 {
-    (1.0 + 10.0f32.powf((rhs.0 - lhs.0) as f32 / 400.0)).recip()
+/// Updates the crews of one side after the match against the other side,
+/// returning what each crew of the side earns;
+/// the score earned by the team is split evenly between partners,
+/// while their deviation and volatility are rated one by one
+fn update_side(
+    conn: &Connection,
+    rating: &dyn Rating,
+    side: &[(Id, Rated)],
+    oppo: Rated,
+    outcome: Outcome,
+) -> Result<i32, CreateMatchError> {
+    let team = team_of(side);
+    let team_rated = rating.rate(team, oppo, outcome);
+    let earn = ((team_rated.score - team.score) / side.len() as f64).round() as i32;
+    for (crew, rated) in side.iter() {
+        let new = Rated {
+            score: rated.score + earn as f64,
+            ..rating.rate(*rated, oppo, outcome)
+        };
+        debug!("Crew {:?} earns {}, rated {:?}", crew, earn, new);
+        if new.modify_with(conn, *crew)? == 0 {
+            return Err(CreateMatchError::CrewNotFound(*crew));
+        }
+    }
+    Ok(earn)
 }
 
 fn update_crew_option(
-    login: &Login,
-    lhs_crew: Id,
-    rhs_crew: Id,
+    conn: &Connection,
+    lhs_crews: &[Id],
+    rhs_crews: &[Id],
     lhs_wins: i32,
     rhs_wins: i32,
     round_worth: u32,
-) -> Option<(i32, i32)> {
-    let lhs = Score::query(login, lhs_crew, true)?;
-    let rhs = Score::query(login, rhs_crew, true)?;
-    debug!("Starting score: {:?} and {:?}", lhs, rhs);
+) -> Result<(i32, i32), CreateMatchError> {
+    let rating = rating::current();
+    let query = |crews: &[Id]| {
+        crews
+            .iter()
+            .map(|crew| Ok((*crew, Rated::query_with(conn, *crew)?)))
+            .collect::<rusqlite::Result<Vec<_>>>()
+    };
+    let lhs = query(lhs_crews)?;
+    let rhs = query(rhs_crews)?;
+    debug!("Starting rating: {:?} and {:?}", lhs, rhs);
     debug!(
         "Left wins {} times; Right wins {} times",
         lhs_wins, rhs_wins
     );
-    let total_round = lhs_wins + rhs_wins;
-    // For lhs:
-    let lhs_elo = calc_elo(lhs, rhs);
-    debug!("Left elo is {}", lhs_elo);
-    let lhs_diff = estimated_var(login, lhs_crew, rhs_crew, round_worth)
-        * (lhs_wins as f32 / total_round as f32 - lhs_elo)
-        * becks_db::CONFIG.user.elo_scaler;
-    let lhs_diff = lhs_diff.round() as i32;
+    let lhs_outcome = Outcome {
+        wins: lhs_wins as u32,
+        losses: rhs_wins as u32,
+        round_worth,
+    };
+    let rhs_outcome = Outcome {
+        wins: rhs_wins as u32,
+        losses: lhs_wins as u32,
+        round_worth,
+    };
+    // Both sides are rated against the states before the match
+    let (lhs_team, rhs_team) = (team_of(&lhs), team_of(&rhs));
+    let lhs_diff = update_side(conn, rating.as_ref(), &lhs, rhs_team, lhs_outcome)?;
     debug!("Left diff is {}", lhs_diff);
-    if !Score::modify(Score(lhs.0 + lhs_diff), login, lhs_crew) {
-        return None;
-    }
-    // For rhs:
-    let rhs_elo = calc_elo(rhs, lhs);
-    debug!("Right elo is {}", rhs_elo);
-    let rhs_diff = estimated_var(login, rhs_crew, lhs_crew, round_worth)
-        * (rhs_wins as f32 / total_round as f32 - rhs_elo)
-        * becks_db::CONFIG.user.elo_scaler;
-    let rhs_diff = rhs_diff.round() as i32;
+    let rhs_diff = update_side(conn, rating.as_ref(), &rhs, lhs_team, rhs_outcome)?;
     debug!("Right diff is {}", rhs_diff);
-    if !Score::modify(Score(rhs.0 + rhs_diff), login, rhs_crew) {
-        return None;
-    }
-    Some((lhs_diff, rhs_diff))
+    Ok((lhs_diff, rhs_diff))
 }
 
-fn update_beat(login: &Login, main: Id, beaten: Id, beaten_score: Score) -> Option<()> {
+fn update_beat(
+    conn: &Connection,
+    main: Id,
+    beaten: Id,
+    beaten_score: Score,
+) -> Result<(), CreateMatchError> {
     debug!(
         "{:?} beats {:?} with score {:?}, updating",
         main, beaten, beaten_score
     );
-    let mut beat = Beat::query(login, main, false).unwrap_or_default();
+    let mut beat = Beat::query_with(conn, main).unwrap_or_default();
     // If the id was previously contained
     if beat.0.iter_mut().any(|beat| {
         if beat.id == beaten {
@@ -67,12 +97,12 @@ fn update_beat(login: &Login, main: Id, beaten: Id, beaten_score: Score) -> Opti
             false
         }
     }) {
-        return Some(());
+        return Ok(());
     }
     // On continue the update
     beat.0.push(BeatItem {
         id: beaten,
-        oppo: String::query(login, beaten, true)?,
+        oppo: String::query_with(conn, beaten)?,
         score: beaten_score,
     });
     let mut index = beat.0.len() - 1;
@@ -83,38 +113,203 @@ fn update_beat(login: &Login, main: Id, beaten: Id, beaten_score: Score) -> Opti
     if beat.0.len() > becks_db::CONFIG.db.beat_limit {
         beat.0.pop();
     }
-    beat.modify(login, main);
-    Some(())
+    beat.modify_with(conn, main)?;
+    Ok(())
 }
 
-/// Updates crew score accordingly, returning left earn and right earn scores if successful
-pub fn update_crew(login: &Login, mat: &Match) -> Option<(i32, i32)> {
-    let lhs_wins = mat
-        .rounds
-        .iter()
-        .fold(0, |sum, round| if round.left_win { sum + 1 } else { sum });
-    let rhs_wins = mat.total_rounds as i32 - lhs_wins;
-    if lhs_wins > rhs_wins {
-        update_beat(
-            login,
-            mat.left,
-            mat.right,
-            Score::query(login, mat.right, true)?,
-        )?;
-    } else {
-        update_beat(
-            login,
-            mat.right,
-            mat.left,
-            Score::query(login, mat.left, true)?,
-        )?;
+/// Returns the rounds won by the left and right crews,
+/// where a match that a crew quits is settled by the policy,
+/// and never more rounds are given than the match has
+pub fn tally(mat: &Match, policy: ForfeitPolicy) -> (u32, u32) {
+    let left = mat.rounds.iter().filter(|round| round.left_win).count() as u32;
+    let right = mat.rounds.len() as u32 - left;
+    let total = (mat.total_rounds as u32).max(1);
+    let remaining = total.saturating_sub(left + right);
+    // The crew that quits keeps fewer rounds than half, so that the other crew can win
+    // one round more within the total
+    let kept = |quitter: u32| quitter.min((total - 1) / 2);
+    match (mat.quit, policy) {
+        (Quit::Normal, _) => (left, right),
+        (Quit::LeftQuit, ForfeitPolicy::Sweep) => (0, total),
+        (Quit::RightQuit, ForfeitPolicy::Sweep) => (total, 0),
+        (Quit::LeftQuit, ForfeitPolicy::Remaining) => {
+            let left = kept(left);
+            (left, (right + remaining).max(left + 1))
+        }
+        (Quit::RightQuit, ForfeitPolicy::Remaining) => {
+            let right = kept(right);
+            ((left + remaining).max(right + 1), right)
+        }
+    }
+}
+
+/// Updates crew score accordingly through the connection, which should be a transaction,
+/// returning left earn and right earn scores if successful
+pub fn update_crew(conn: &Connection, mat: &Match) -> Result<(i32, i32), CreateMatchError> {
+    update_crew_by(conn, mat, becks_db::CONFIG.user.forfeit)
+}
+
+fn update_crew_by(
+    conn: &Connection,
+    mat: &Match,
+    policy: ForfeitPolicy,
+) -> Result<(i32, i32), CreateMatchError> {
+    let (lhs_wins, rhs_wins) = tally(mat, policy);
+    let (lhs_wins, rhs_wins) = (lhs_wins as i32, rhs_wins as i32);
+    // Beat lists only keep singles matches
+    if !mat.is_doubles() {
+        if lhs_wins > rhs_wins {
+            update_beat(
+                conn,
+                mat.left,
+                mat.right,
+                Score::query_with(conn, mat.right)?,
+            )?;
+        } else {
+            update_beat(
+                conn,
+                mat.right,
+                mat.left,
+                Score::query_with(conn, mat.left)?,
+            )?;
+        }
     }
     update_crew_option(
-        login,
-        mat.left,
-        mat.right,
+        conn,
+        &mat.left_side(),
+        &mat.right_side(),
         lhs_wins,
         rhs_wins,
         mat.round_worth,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: Id = Id::from_prim(1);
+    const RIGHT: Id = Id::from_prim(2);
+
+    fn connect() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        becks_db::migrate(&mut conn).unwrap();
+        conn.execute_batch(indoc! {"
+            INSERT INTO crew (id, name, score, deleted) VALUES (1, 'left', 500, 0);
+            INSERT INTO crew (id, name, score, deleted) VALUES (2, 'right', 500, 0);
+        "})
+            .unwrap();
+        conn
+    }
+
+    /// The policy, the side that quits, the rounds played before quitting from the view of
+    /// the left crew, and the expected wins of the left and right crews
+    type ForfeitCase = (ForfeitPolicy, Quit, &'static [bool], (u32, u32));
+
+    /// A match of 5 rounds that is quit after the given rounds
+    fn quit_match(quit: Quit, rounds: &[bool]) -> Match {
+        let mut mat = Match::new(5, LEFT, RIGHT, 0);
+        mat.quit = quit;
+        mat.rounds = rounds.iter().copied().map(Round::new).collect();
+        mat
+    }
+
+    /// Returns what a crew earns against an equal opponent by the current rating
+    fn expected_earn(wins: u32, losses: u32, round_worth: u32) -> i32 {
+        let rated = Rated {
+            score: 500.0,
+            deviation: rating::INITIAL_DEVIATION,
+            volatility: rating::INITIAL_VOLATILITY,
+        };
+        let outcome = Outcome {
+            wins,
+            losses,
+            round_worth,
+        };
+        (rating::current().rate(rated, rated, outcome).score - rated.score).round() as i32
+    }
+
+    #[test]
+    fn test_forfeit_policies() {
+        use ForfeitPolicy::*;
+        use Quit::*;
+        let cases: &[ForfeitCase] = &[
+            // No round is played
+            (Sweep, LeftQuit, &[], (0, 5)),
+            (Sweep, RightQuit, &[], (5, 0)),
+            (Remaining, LeftQuit, &[], (0, 5)),
+            (Remaining, RightQuit, &[], (5, 0)),
+            // The crew that quits is behind
+            (Sweep, LeftQuit, &[false], (0, 5)),
+            (Sweep, RightQuit, &[true], (5, 0)),
+            (Remaining, LeftQuit, &[false], (0, 5)),
+            (Remaining, RightQuit, &[true], (5, 0)),
+            // The crew that quits is ahead, so the other crew wins by one more round
+            (Sweep, LeftQuit, &[true, true], (0, 5)),
+            (Sweep, RightQuit, &[false, false], (5, 0)),
+            (Remaining, LeftQuit, &[true, true], (2, 3)),
+            (Remaining, RightQuit, &[false, false], (3, 2)),
+            // The crew that quits is too far ahead to keep its rounds within the total
+            (Remaining, LeftQuit, &[true, true, true, false], (2, 3)),
+            (Remaining, RightQuit, &[false, false, false, true], (3, 2)),
+            // Every round is played before quitting
+            (Sweep, LeftQuit, &[true, true, true, false, false], (0, 5)),
+            (Sweep, RightQuit, &[false, false, false, true, true], (5, 0)),
+            (
+                Remaining,
+                LeftQuit,
+                &[true, true, true, false, false],
+                (2, 3),
+            ),
+            (
+                Remaining,
+                RightQuit,
+                &[false, false, false, true, true],
+                (3, 2),
+            ),
+        ];
+        for (policy, quit, rounds, expected) in cases.iter().copied() {
+            let mat = quit_match(quit, rounds);
+            let (left, right) = tally(&mat, policy);
+            assert_eq!(
+                (left, right),
+                expected,
+                "{:?} {:?} {:?}",
+                policy,
+                quit,
+                rounds
+            );
+            assert!(left + right <= mat.total_rounds as u32);
+
+            let conn = connect();
+            let (left_earn, right_earn) = update_crew_by(&conn, &mat, policy).unwrap();
+            assert_eq!(left_earn, expected_earn(left, right, mat.round_worth));
+            assert_eq!(right_earn, expected_earn(right, left, mat.round_worth));
+            // The crew that quits never gains
+            let (quitter, other) = match quit {
+                LeftQuit => (left_earn, right_earn),
+                _ => (right_earn, left_earn),
+            };
+            assert!(
+                quitter < 0 && other > 0,
+                "{:?} {:?} {:?}",
+                policy,
+                quit,
+                rounds
+            );
+            assert_eq!(Score::query_with(&conn, LEFT).unwrap().0, 500 + left_earn);
+            assert_eq!(Score::query_with(&conn, RIGHT).unwrap().0, 500 + right_earn);
+        }
+    }
+
+    #[test]
+    fn test_forfeit_normal() {
+        let mut mat = quit_match(Quit::Normal, &[true, false, true, true, false]);
+        for policy in [ForfeitPolicy::Sweep, ForfeitPolicy::Remaining] {
+            assert_eq!(tally(&mat, policy), (3, 2));
+        }
+        mat.rounds.truncate(0);
+        mat.total_rounds = 0;
+        assert_eq!(tally(&mat, ForfeitPolicy::Sweep), (0, 0));
+    }
+}
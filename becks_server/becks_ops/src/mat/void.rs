@@ -0,0 +1,48 @@
+use super::{CreateMatchError, Replayed};
+use crate::prelude::*;
+
+#[derive(Debug, Error)]
+pub enum VoidMatchError {
+    #[error("match {0:?} is not found")]
+    MatchNotFound(Id),
+    #[error("match {0:?} is already voided")]
+    AlreadyVoided(Id),
+    #[error("replay failed, {0}")]
+    Replay(#[from] CreateMatchError),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Voids the match and replays the rest in a single transaction, so that its effects on
+/// the scores and beat lists of crews are reversed, and takes it back from the tournaments,
+/// arrangements and Swiss events it decides; Nothing is changed if any step fails
+pub fn void_match(login: &Login, mat: Id) -> Result<Replayed, VoidMatchError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let voided = tx
+        .query_row(
+            "SELECT voided FROM match WHERE id = (:id)",
+            rusqlite::named_params! {":id": mat.to_prim()},
+            |row| row.get::<_, bool>(0),
+        )
+        .map_err(|err| match err {
+            rusqlite::Error::QueryReturnedNoRows => VoidMatchError::MatchNotFound(mat),
+            err => err.into(),
+        })?;
+    if voided {
+        return Err(VoidMatchError::AlreadyVoided(mat));
+    }
+    tx.execute(
+        "UPDATE match SET voided = TRUE WHERE id = (:id)",
+        rusqlite::named_params! {":id": mat.to_prim()},
+    )?;
+    let replayed = super::replay_with(&tx)?;
+    crate::event::amend_all(&tx, mat, None)?;
+    tx.commit()?;
+    info!(
+        "Voided match {:?}, changing the score of {} crews",
+        mat,
+        replayed.diffs.len()
+    );
+    Ok(replayed)
+}
@@ -0,0 +1,76 @@
+use crate::prelude::*;
+
+/// A page of ids matching a query
+#[derive(Debug, Default)]
+pub struct Page {
+    pub ids: Vec<Id>,
+    /// The number of all matching ids, across all pages
+    pub total: usize,
+    /// The offset of the next page, or None if this is the last page
+    pub next: Option<usize>,
+}
+
+/// Clamps the requested page size, where the default is used if no size is requested
+pub(crate) fn page_limit(limit: Option<usize>, default: usize) -> usize {
+    limit
+        .unwrap_or(default)
+        .clamp(1, becks_db::CONFIG.db.page_limit.max(1))
+}
+
+/// A query of ids in one table, which is run page by page
+pub(crate) struct PageQuery {
+    pub table: &'static str,
+    /// Either empty or starting with ` WHERE `, using the parameters in `params`
+    pub condition: String,
+    pub params: Vec<Box<dyn ToSql>>,
+    /// Either empty or starting with ` ORDER BY `, using the parameters in `order_params`,
+    /// which are numbered after those of the condition
+    pub order: String,
+    pub order_params: Vec<Box<dyn ToSql>>,
+}
+
+impl PageQuery {
+    /// Counts all matching ids, then selects one page of them in order
+    pub fn run(&self, conn: &Connection, offset: usize, limit: usize) -> rusqlite::Result<Page> {
+        let params = self
+            .params
+            .iter()
+            .map(|value| value.as_ref())
+            .collect::<Vec<&dyn ToSql>>();
+        let total = conn.query_row(
+            &format!("SELECT COUNT(*) FROM {}{}", self.table, self.condition),
+            params.as_slice(),
+            |row| row.get::<_, usize>(0),
+        )?;
+
+        let sql = format!(
+            "SELECT id FROM {}{}{} LIMIT {} OFFSET {}",
+            self.table, self.condition, self.order, limit, offset
+        );
+        debug!("Querying the database with sql {}", sql);
+        let params = self
+            .params
+            .iter()
+            .chain(self.order_params.iter())
+            .map(|value| value.as_ref())
+            .collect::<Vec<&dyn ToSql>>();
+        let ids = conn
+            .prepare(&sql)?
+            .query_map(params.as_slice(), |row| row.get::<_, u32>(0))?
+            .filter_map(|value| {
+                value
+                    .inspect_err(|err| {
+                        error!("When querying for rows in {}, {}", self.table, err);
+                    })
+                    .ok()
+                    .map(Id::from_prim)
+            })
+            .collect::<Vec<_>>();
+        let end = offset.saturating_add(ids.len());
+        Ok(Page {
+            next: (!ids.is_empty() && end < total).then_some(end),
+            ids,
+            total,
+        })
+    }
+}
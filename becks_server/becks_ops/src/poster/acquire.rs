@@ -1,8 +1,23 @@
 use crate::prelude::*;
 use becks_poster::*;
+use std::collections::HashMap;
+
+fn split_images(images: &str) -> Vec<String> {
+    images
+        .split('?')
+        .filter_map(|s| {
+            let s = s.trim();
+            if s.is_empty() {
+                None
+            } else {
+                Some(s.to_owned())
+            }
+        })
+        .collect()
+}
 
 pub fn acquire_poster(login: &Login, poster: Id) -> Option<Poster> {
-    let db = login.db();
+    let db = login.read();
     if let Ok((value, images, timestamp)) = db
         .query_row(
             indoc! {"
@@ -23,23 +38,45 @@ pub fn acquire_poster(login: &Login, poster: Id) -> Option<Poster> {
             warn!("When querying for poster {:?}, {}", poster, err);
         })
     {
-        let images = images
-            .split('?')
-            .filter_map(|s| {
-                let s = s.trim();
-                if s.is_empty() {
-                    None
-                } else {
-                    Some(s.to_owned())
-                }
-            })
-            .collect();
         Some(Poster {
             value,
-            images,
+            images: split_images(&images),
             timestamp,
         })
     } else {
         None
     }
 }
+
+/// Acquires all given posters with one query per batch, where posters that are not found are left out
+pub fn acquire_posters(login: &Login, posters: &[Id]) -> HashMap<Id, Poster> {
+    let db = login.read();
+    let mut result = HashMap::new();
+    for batch in batches(posters) {
+        db.prepare(&formatdoc! {"
+                SELECT id, value, images, timestamp FROM poster
+                WHERE id IN ({})
+            ",
+            placeholders(batch.len()),
+        })
+        .and_then(|mut stmt| {
+            stmt.query_map(rusqlite::params_from_iter(batch), |row| {
+                Ok((
+                    Id::from_prim(row.get("id")?),
+                    Poster {
+                        value: row.get("value")?,
+                        images: split_images(&row.get::<_, String>("images")?),
+                        timestamp: row.get("timestamp")?,
+                    },
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .map(|rows| result.extend(rows))
+        .inspect_err(|err| {
+            error!("When acquiring posters, {}", err);
+        })
+        .ok();
+    }
+    result
+}
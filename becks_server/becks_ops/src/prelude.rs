@@ -1,11 +1,44 @@
 pub(crate) use becks_crew::*;
 pub(crate) use becks_db::Login;
 pub(crate) use indoc::{formatdoc, indoc};
-pub(crate) use log::{debug, error, info, trace, warn};
-pub(crate) use rusqlite::ToSql;
-pub(crate) use serde::{Deserialize, Serialize};
+pub(crate) use log::{debug, error, info, warn};
+pub(crate) use rusqlite::{Connection, ToSql};
 pub(crate) use thiserror::Error;
 
 pub(crate) fn box_sql(value: impl ToSql + 'static) -> Box<dyn ToSql> {
     Box::new(value)
 }
+
+/// Generates a random id that is not used in the table yet
+pub(crate) fn unused_id(conn: &Connection, table: &str) -> Id {
+    loop {
+        let id = Id::rand();
+        if conn
+            .query_row(
+                &format!("SELECT id FROM {} WHERE id = (:id)", table),
+                rusqlite::named_params! {":id": id.to_prim()},
+                |row| row.get::<_, u32>(0),
+            )
+            .is_err()
+        {
+            break id;
+        }
+    }
+}
+
+/// The largest number of ids bound to a single `IN (...)` query,
+/// which stays well below the variable limit of SQLite
+pub(crate) const BATCH_SIZE: usize = 500;
+
+/// Returns the placeholders of an `IN (...)` list of the given length
+pub(crate) fn placeholders(len: usize) -> String {
+    vec!["?"; len].join(", ")
+}
+
+/// Sorts and removes duplicated ids, then splits them into batches for `IN (...)` queries
+pub(crate) fn batches(ids: &[Id]) -> Vec<Vec<u32>> {
+    let mut ids = ids.iter().map(|id| id.to_prim()).collect::<Vec<_>>();
+    ids.sort_unstable();
+    ids.dedup();
+    ids.chunks(BATCH_SIZE).map(|chunk| chunk.to_vec()).collect()
+}
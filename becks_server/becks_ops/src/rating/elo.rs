@@ -0,0 +1,103 @@
+use super::{Outcome, Rated, Rating};
+
+/// Elo rating, where a match moves the score by at most `round_worth * scaler`
+#[derive(Debug, Clone, Copy)]
+pub struct Elo {
+    pub scaler: f64,
+}
+
+impl Elo {
+    /// Returns the expected win rate of the score against the other
+    pub fn expected(score: f64, other: f64) -> f64 {
+        (1.0 + 10.0f64.powf((other - score) / 400.0)).recip()
+    }
+}
+
+impl Rating for Elo {
+    fn rate(&self, crew: Rated, oppo: Rated, outcome: Outcome) -> Rated {
+        if outcome.rounds() == 0 {
+            return crew;
+        }
+        let actual = outcome.wins as f64 / outcome.rounds() as f64;
+        let diff = outcome.round_worth as f64
+            * (actual - Self::expected(crew.score, oppo.score))
+            * self.scaler;
+        Rated {
+            score: crew.score + diff.round(),
+            ..crew
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rated(score: f64) -> Rated {
+        Rated {
+            score,
+            deviation: 350.0,
+            volatility: 0.06,
+        }
+    }
+
+    #[test]
+    fn test_elo_expected() {
+        assert_eq!(Elo::expected(1500.0, 1500.0), 0.5);
+        assert!((Elo::expected(1600.0, 1400.0) - 0.7597).abs() < 0.0001);
+        assert!((Elo::expected(1400.0, 1600.0) - 0.2403).abs() < 0.0001);
+        // A difference of 400 is a tenfold odds
+        assert!((Elo::expected(1900.0, 1500.0) - 10.0 / 11.0).abs() < 1e-12);
+        for (score, other) in [(500.0, 620.0), (1000.0, 0.0), (42.0, 42.0)] {
+            let sum = Elo::expected(score, other) + Elo::expected(other, score);
+            assert!((sum - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_elo_update() {
+        let elo = Elo { scaler: 2.0 };
+        let outcome = Outcome {
+            wins: 3,
+            losses: 1,
+            round_worth: 10,
+        };
+        // 10 * (0.75 - 0.5) * 2
+        let after = elo.rate(rated(500.0), rated(500.0), outcome);
+        assert_eq!(after.score, 505.0);
+        assert_eq!(after.deviation, 350.0);
+        let after = elo.rate(
+            rated(500.0),
+            rated(500.0),
+            Outcome {
+                wins: 1,
+                losses: 3,
+                round_worth: 10,
+            },
+        );
+        assert_eq!(after.score, 495.0);
+
+        // 10 * (1 - 0.7597) * 2 rounds to 5
+        let after = elo.rate(
+            rated(1600.0),
+            rated(1400.0),
+            Outcome {
+                wins: 2,
+                losses: 0,
+                round_worth: 10,
+            },
+        );
+        assert_eq!(after.score, 1605.0);
+    }
+
+    #[test]
+    fn test_elo_no_rounds() {
+        let elo = Elo { scaler: 1.0 };
+        let outcome = Outcome {
+            wins: 0,
+            losses: 0,
+            round_worth: 10,
+        };
+        assert_eq!(elo.rate(rated(500.0), rated(700.0), outcome).score, 500.0);
+    }
+}
@@ -0,0 +1,216 @@
+//! Glicko-2 as described in <http://www.glicko.net/glicko/glicko2.pdf>
+//!
+//! Every match is one rating period, where each round is one game against the opponent.
+
+use super::{Outcome, Rated, Rating};
+use becks_crew::Score;
+use std::f64::consts::PI;
+
+/// The ratio between the Glicko scale and the Glicko-2 scale
+const SCALE: f64 = 173.7178;
+/// The convergence tolerance of the volatility iteration
+const EPSILON: f64 = 0.000001;
+
+/// Glicko-2 rating, which moves uncertain scores faster than settled ones
+#[derive(Debug, Clone, Copy)]
+pub struct Glicko2 {
+    pub tau: f64,
+}
+
+/// A game against an opponent, with the result of 1 for win and 0 for loss
+#[derive(Debug, Clone, Copy)]
+pub struct Game {
+    pub oppo: Rated,
+    pub result: f64,
+}
+
+fn g(phi: f64) -> f64 {
+    (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt().recip()
+}
+
+fn expected(mu: f64, oppo_mu: f64, oppo_phi: f64) -> f64 {
+    (1.0 + (-g(oppo_phi) * (mu - oppo_mu)).exp()).recip()
+}
+
+impl Glicko2 {
+    /// Returns the new volatility by the Illinois algorithm
+    fn volatility(&self, phi: f64, sigma: f64, v: f64, delta: f64) -> f64 {
+        let a = (sigma * sigma).ln();
+        let tau = self.tau;
+        let f = |x: f64| {
+            let ex = x.exp();
+            ex * (delta * delta - phi * phi - v - ex) / (2.0 * (phi * phi + v + ex).powi(2))
+                - (x - a) / (tau * tau)
+        };
+        let mut big_a = a;
+        let mut big_b = if delta * delta > phi * phi + v {
+            (delta * delta - phi * phi - v).ln()
+        } else {
+            let mut k = 1.0;
+            while f(a - k * tau) < 0.0 {
+                k += 1.0;
+            }
+            a - k * tau
+        };
+        let mut f_a = f(big_a);
+        let mut f_b = f(big_b);
+        while (big_b - big_a).abs() > EPSILON {
+            let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+            let f_c = f(big_c);
+            if f_c * f_b <= 0.0 {
+                big_a = big_b;
+                f_a = f_b;
+            } else {
+                f_a /= 2.0;
+            }
+            big_b = big_c;
+            f_b = f_c;
+        }
+        (big_a / 2.0).exp()
+    }
+
+    /// Returns the new rating state after a rating period with the games,
+    /// where only the deviation grows if there are no games
+    pub fn update(&self, crew: Rated, games: &[Game]) -> Rated {
+        let origin = Score::default().0 as f64;
+        let mu = (crew.score - origin) / SCALE;
+        let phi = crew.deviation / SCALE;
+        let sigma = crew.volatility;
+        if games.is_empty() {
+            return Rated {
+                deviation: (phi * phi + sigma * sigma).sqrt() * SCALE,
+                ..crew
+            };
+        }
+        let mut v_inv = 0.0;
+        let mut improvement = 0.0;
+        for game in games {
+            let oppo_mu = (game.oppo.score - origin) / SCALE;
+            let oppo_phi = game.oppo.deviation / SCALE;
+            let e = expected(mu, oppo_mu, oppo_phi);
+            v_inv += g(oppo_phi).powi(2) * e * (1.0 - e);
+            improvement += g(oppo_phi) * (game.result - e);
+        }
+        let v = v_inv.recip();
+        let delta = v * improvement;
+        let sigma = self.volatility(phi, sigma, v, delta);
+        let phi_star = (phi * phi + sigma * sigma).sqrt();
+        let phi = (phi_star.powi(-2) + v_inv).sqrt().recip();
+        let mu = mu + phi * phi * improvement;
+        Rated {
+            score: mu * SCALE + origin,
+            deviation: phi * SCALE,
+            volatility: sigma,
+        }
+    }
+}
+
+impl Rating for Glicko2 {
+    fn rate(&self, crew: Rated, oppo: Rated, outcome: Outcome) -> Rated {
+        let win = Game { oppo, result: 1.0 };
+        let loss = Game { oppo, result: 0.0 };
+        let games = std::iter::repeat_n(win, outcome.wins as usize)
+            .chain(std::iter::repeat_n(loss, outcome.losses as usize))
+            .collect::<Vec<_>>();
+        self.update(crew, &games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example in the paper rates on a scale centered on 1500, which is shifted onto
+    /// the default score of crews; Glicko-2 only depends on differences in scores
+    fn paper_score(score: f64) -> f64 {
+        score - 1500.0 + Score::default().0 as f64
+    }
+
+    fn rated(score: f64, deviation: f64) -> Rated {
+        Rated {
+            score: paper_score(score),
+            deviation,
+            volatility: 0.06,
+        }
+    }
+
+    fn assert_close(value: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (value - expected).abs() < tolerance,
+            "{} is not close to {}",
+            value,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_glicko2_paper_steps() {
+        let mu = 0.0;
+        let oppos = [(-0.5756, 0.1727), (0.2878, 0.5756), (1.1513, 1.7269)];
+        let gs = [0.9955, 0.9531, 0.7242];
+        let es = [0.639, 0.432, 0.303];
+        for ((oppo_mu, oppo_phi), (g_value, e_value)) in
+            oppos.into_iter().zip(gs.into_iter().zip(es))
+        {
+            assert_close(g(oppo_phi), g_value, 0.0001);
+            assert_close(expected(mu, oppo_mu, oppo_phi), e_value, 0.001);
+        }
+    }
+
+    #[test]
+    fn test_glicko2_paper() {
+        let glicko = Glicko2 { tau: 0.5 };
+        let games = [
+            Game {
+                oppo: rated(1400.0, 30.0),
+                result: 1.0,
+            },
+            Game {
+                oppo: rated(1550.0, 100.0),
+                result: 0.0,
+            },
+            Game {
+                oppo: rated(1700.0, 300.0),
+                result: 0.0,
+            },
+        ];
+        let after = glicko.update(rated(1500.0, 200.0), &games);
+        assert_close(after.score, paper_score(1464.06), 0.01);
+        assert_close(after.deviation, 151.52, 0.01);
+        assert_close(after.volatility, 0.05999, 0.00001);
+    }
+
+    #[test]
+    fn test_glicko2_no_games() {
+        let glicko = Glicko2 { tau: 0.5 };
+        let before = rated(1500.0, 200.0);
+        let after = glicko.update(before, &[]);
+        assert_eq!(after.score, before.score);
+        assert_eq!(after.volatility, before.volatility);
+        // The deviation grows by the volatility, which is 200.27 in the paper scale
+        assert_close(after.deviation, 200.27, 0.01);
+    }
+
+    #[test]
+    fn test_glicko2_rate() {
+        let glicko = Glicko2 { tau: 0.5 };
+        let crew = rated(1500.0, 200.0);
+        let oppo = rated(1550.0, 100.0);
+        let outcome = Outcome {
+            wins: 2,
+            losses: 1,
+            round_worth: 10,
+        };
+        let games = [
+            Game { oppo, result: 1.0 },
+            Game { oppo, result: 1.0 },
+            Game { oppo, result: 0.0 },
+        ];
+        let rated = glicko.rate(crew, oppo, outcome);
+        let updated = glicko.update(crew, &games);
+        assert_eq!(rated.score, updated.score);
+        assert_eq!(rated.deviation, updated.deviation);
+        assert!(rated.score > crew.score);
+        assert!(rated.deviation < crew.deviation);
+    }
+}
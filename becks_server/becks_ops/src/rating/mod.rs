@@ -0,0 +1,142 @@
+//! Rating systems that update crew scores after matches
+//!
+//! The system in use is chosen by `rating` in the user configuration. Every system reads
+//! and writes the same [`Rated`] state, so switching systems keeps the scores of crews.
+
+mod elo;
+mod glicko2;
+
+use crate::crew::Column;
+use crate::prelude::*;
+use becks_db::{RatingKind, CONFIG};
+pub use elo::Elo;
+pub use glicko2::{Game, Glicko2};
+
+/// The deviation of a crew that has not played, which is the default of the column
+pub const INITIAL_DEVIATION: f64 = 350.0;
+/// The volatility of a crew that has not played, which is the default of the column
+pub const INITIAL_VOLATILITY: f64 = 0.06;
+
+/// The rating deviation of a crew, which is how uncertain its score is
+#[derive(Debug, Clone, Copy)]
+pub struct Deviation(pub f64);
+
+/// The rating volatility of a crew, which is how erratic its performance is
+#[derive(Debug, Clone, Copy)]
+pub struct Volatility(pub f64);
+
+impl Column for Deviation {
+    type Target = f64;
+    fn name() -> &'static str {
+        "deviation"
+    }
+    fn convert(self) -> Self::Target {
+        self.0
+    }
+    fn acquire(value: Self::Target) -> Self {
+        Self(value)
+    }
+}
+
+impl Column for Volatility {
+    type Target = f64;
+    fn name() -> &'static str {
+        "volatility"
+    }
+    fn convert(self) -> Self::Target {
+        self.0
+    }
+    fn acquire(value: Self::Target) -> Self {
+        Self(value)
+    }
+}
+
+/// The rating state of a crew
+#[derive(Debug, Clone, Copy)]
+pub struct Rated {
+    pub score: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+impl Rated {
+    /// Queries the rating state through the connection, which may be a transaction
+    pub fn query_with(conn: &Connection, crew: Id) -> rusqlite::Result<Self> {
+        Ok(Self {
+            score: Score::query_with(conn, crew)?.0 as f64,
+            deviation: Deviation::query_with(conn, crew)?.0,
+            volatility: Volatility::query_with(conn, crew)?.0,
+        })
+    }
+
+    /// Modifies the rating state through the connection, which may be a transaction,
+    /// returning the number of modified rows
+    pub fn modify_with(self, conn: &Connection, crew: Id) -> rusqlite::Result<usize> {
+        conn.execute(
+            indoc! {"
+                UPDATE crew
+                SET score = (:score), deviation = (:deviation), volatility = (:volatility)
+                WHERE id = (:id)
+            "},
+            rusqlite::named_params! {
+                ":score": self.score().0,
+                ":deviation": self.deviation,
+                ":volatility": self.volatility,
+                ":id": crew.to_prim(),
+            },
+        )
+    }
+
+    /// Returns the score as stored in the database
+    pub fn score(&self) -> Score {
+        Score(self.score.round() as i32)
+    }
+
+    /// Combines the states of partners into the state of their team,
+    /// taking the mean score and volatility and the quadratic mean deviation
+    pub fn team(members: &[Rated]) -> Rated {
+        let len = members.len().max(1) as f64;
+        Rated {
+            score: members.iter().map(|member| member.score).sum::<f64>() / len,
+            deviation: (members
+                .iter()
+                .map(|member| member.deviation.powi(2))
+                .sum::<f64>()
+                / len)
+                .sqrt(),
+            volatility: members.iter().map(|member| member.volatility).sum::<f64>() / len,
+        }
+    }
+}
+
+/// The result of a match from the view of one crew
+#[derive(Debug, Clone, Copy)]
+pub struct Outcome {
+    pub wins: u32,
+    pub losses: u32,
+    pub round_worth: u32,
+}
+
+impl Outcome {
+    pub fn rounds(&self) -> u32 {
+        self.wins + self.losses
+    }
+}
+
+pub trait Rating {
+    /// Returns the new rating state of the crew after a match against the opponent,
+    /// where both states are taken before the match
+    fn rate(&self, crew: Rated, oppo: Rated, outcome: Outcome) -> Rated;
+}
+
+/// Returns the rating system chosen by the configuration
+pub fn current() -> Box<dyn Rating> {
+    match CONFIG.user.rating {
+        RatingKind::Elo => Box::new(Elo {
+            scaler: CONFIG.user.elo_scaler as f64,
+        }),
+        RatingKind::Glicko2 => Box::new(Glicko2 {
+            tau: CONFIG.user.glicko_tau,
+        }),
+    }
+}
@@ -0,0 +1,30 @@
+use crate::event::{acquire_event_with, save_event};
+use crate::prelude::*;
+use becks_arrange::*;
+
+#[derive(Debug, Error)]
+pub enum CloseSwissError {
+    #[error("Swiss event {0:?} is not found")]
+    SwissNotFound(Id),
+    #[error("Swiss event {0:?} is already closed")]
+    AlreadyClosed(Id),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Closes the Swiss event, so that it is no longer listed as open or linked to new matches,
+/// returning its final state
+pub fn close_swiss(login: &Login, id: Id) -> Result<Swiss, CloseSwissError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let mut swiss =
+        acquire_event_with::<Swiss>(&tx, id).ok_or(CloseSwissError::SwissNotFound(id))?;
+    if swiss.closed {
+        return Err(CloseSwissError::AlreadyClosed(id));
+    }
+    swiss.closed = true;
+    save_event(&tx, id, &swiss)?;
+    tx.commit()?;
+    info!("Closed Swiss event {:?}", id);
+    Ok(swiss)
+}
@@ -0,0 +1,82 @@
+use crate::crew::Column;
+use crate::prelude::*;
+use becks_arrange::*;
+
+#[derive(Debug, Error)]
+pub enum CreateSwissError {
+    #[error("crew {0:?} is not found")]
+    CrewNotFound(Id),
+    #[error(transparent)]
+    Invalid(#[from] SwissError),
+    #[error("unable to serialize Swiss event, {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Returns the crews of the singles matches recorded since the timestamp, which are not voided
+fn met_since(conn: &Connection, since: u64) -> rusqlite::Result<Vec<(Id, Id)>> {
+    let mut stmt = conn.prepare(indoc! {"
+        SELECT left, right FROM match
+        WHERE timestamp >= (:since) AND NOT voided
+            AND left_partner IS NULL AND right_partner IS NULL
+    "})?;
+    let met = stmt
+        .query_map(rusqlite::named_params! {":since": since}, |row| {
+            Ok((Id::from_prim(row.get(0)?), Id::from_prim(row.get(1)?)))
+        })?
+        .collect();
+    met
+}
+
+/// Stores the crews as a new open Swiss event through the connection, which should be
+/// a transaction, and pairs its first round, where crews that have met since the timestamp
+/// are not paired again as long as possible
+pub(super) fn create_swiss_with(
+    conn: &Connection,
+    crews: &[Id],
+    since: u64,
+) -> Result<(Id, Swiss), CreateSwissError> {
+    let mut players = Vec::with_capacity(crews.len());
+    for crew in crews.iter().copied() {
+        let score = Score::query_with(conn, crew).map_err(|err| match err {
+            rusqlite::Error::QueryReturnedNoRows => CreateSwissError::CrewNotFound(crew),
+            err => err.into(),
+        })?;
+        players.push(SwissPlayer { crew, score });
+    }
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut swiss = Swiss::new(players, met_since(conn, since)?, timestamp)?;
+    swiss.pair_next();
+    let id = unused_id(conn, "swiss");
+    conn.execute(
+        indoc! {"
+            INSERT INTO swiss
+            (id, state, closed, timestamp)
+            VALUES ((:id), (:state), FALSE, (:timestamp))
+        "},
+        rusqlite::named_params! {
+            ":id": id.to_prim(),
+            ":state": serde_json::to_string(&swiss)?,
+            ":timestamp": timestamp,
+        },
+    )?;
+    Ok((id, swiss))
+}
+
+/// Creates a Swiss event of the crews with its first round paired
+pub fn create_swiss(login: &Login, crews: &[Id], since: u64) -> Result<Id, CreateSwissError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let (id, swiss) = create_swiss_with(&tx, crews, since)?;
+    tx.commit()?;
+    info!(
+        "Created Swiss event {:?} of {} crews",
+        id,
+        swiss.players.len()
+    );
+    Ok(id)
+}
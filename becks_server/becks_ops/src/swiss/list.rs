@@ -0,0 +1,17 @@
+use crate::prelude::*;
+
+/// Lists the Swiss events from the latest one, where closed ones are included only if asked
+pub fn list_swiss(login: &Login, closed: bool) -> rusqlite::Result<Vec<Id>> {
+    let conn = login.read();
+    let mut stmt = conn.prepare(indoc! {"
+        SELECT id FROM swiss
+        WHERE (:closed) OR NOT closed
+        ORDER BY timestamp DESC, id
+    "})?;
+    let ids = stmt
+        .query_map(rusqlite::named_params! {":closed": closed}, |row| {
+            Ok(Id::from_prim(row.get(0)?))
+        })?
+        .collect();
+    ids
+}
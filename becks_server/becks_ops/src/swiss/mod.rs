@@ -0,0 +1,112 @@
+mod acquire;
+mod close;
+mod create;
+mod list;
+mod next;
+
+pub use acquire::*;
+pub use close::*;
+pub use create::*;
+pub use list::*;
+pub use next::*;
+
+#[cfg(test)]
+mod tests {
+    use super::create::create_swiss_with;
+    use super::*;
+    use crate::event::{acquire_event_with, amend_all, link_all};
+    use crate::prelude::*;
+    use becks_arrange::*;
+    use becks_match::{Match, Round};
+
+    fn crew(id: u32) -> Id {
+        Id::from_prim(id)
+    }
+
+    fn connect() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        becks_db::migrate(&mut conn).unwrap();
+        for id in 1..=4 {
+            conn.execute(
+                "INSERT INTO crew (id, name, score, deleted) VALUES (?1, ?2, ?3, 0)",
+                (id, format!("crew {}", id), 1000 - id),
+            )
+            .unwrap();
+        }
+        conn
+    }
+
+    /// A match played after the event is created
+    fn beat(winner: u32, loser: u32) -> Match {
+        let mut mat = Match::new(1, crew(winner), crew(loser), u64::MAX);
+        mat.rounds = vec![Round::new(true)];
+        mat
+    }
+
+    fn pairs(swiss: &Swiss, round: usize) -> Vec<(Id, Option<Id>)> {
+        swiss.rounds[round]
+            .iter()
+            .map(|pairing| (pairing.left, pairing.right))
+            .collect()
+    }
+
+    #[test]
+    fn test_swiss_round_trip() {
+        let conn = connect();
+        // Only the singles match since the timestamp that is not voided counts as a meeting
+        conn.execute_batch(indoc! {"
+            INSERT INTO match (id, left, right, timestamp, voided) VALUES (1, 1, 2, 200, 0);
+            INSERT INTO match (id, left, right, timestamp, voided) VALUES (2, 3, 4, 200, 1);
+            INSERT INTO match (id, left, right, timestamp, voided) VALUES (3, 1, 4, 50, 0);
+            INSERT INTO match (id, left, right, timestamp, voided, left_partner, right_partner)
+                VALUES (4, 1, 3, 200, 0, 2, 4);
+        "})
+            .unwrap();
+        let crews = [crew(1), crew(2), crew(3), crew(4)];
+        let (id, created) = create_swiss_with(&conn, &crews, 100).unwrap();
+        assert_eq!(
+            pairs(&created, 0),
+            vec![(crew(1), Some(crew(3))), (crew(2), Some(crew(4)))]
+        );
+        let loaded = acquire_event_with::<Swiss>(&conn, id).unwrap();
+        assert_eq!(
+            serde_json::to_string(&loaded).unwrap(),
+            serde_json::to_string(&created).unwrap()
+        );
+
+        link_all(&conn, crew(100), &beat(3, 1)).unwrap();
+        link_all(&conn, crew(101), &beat(2, 4)).unwrap();
+        let linked = acquire_event_with::<Swiss>(&conn, id).unwrap();
+        assert!(linked.is_round_done());
+        assert_eq!(linked.rounds[0][0].mat, Some(crew(100)));
+        assert_eq!(linked.rounds[0][0].left_win, Some(false));
+
+        // A voided match takes its result back from the closed event as well
+        conn.execute(
+            "UPDATE swiss SET closed = TRUE WHERE id = ?1",
+            [id.to_prim()],
+        )
+        .unwrap();
+        amend_all(&conn, crew(100), None).unwrap();
+        let amended = acquire_event_with::<Swiss>(&conn, id).unwrap();
+        assert!(amended.closed);
+        assert!(!amended.is_round_done());
+        link_all(&conn, crew(102), &beat(3, 1)).unwrap();
+        assert!(!acquire_event_with::<Swiss>(&conn, id)
+            .unwrap()
+            .is_round_done());
+    }
+
+    #[test]
+    fn test_swiss_crew_not_found() {
+        let conn = connect();
+        assert!(matches!(
+            create_swiss_with(&conn, &[crew(1), crew(5)], 0),
+            Err(CreateSwissError::CrewNotFound(_))
+        ));
+        assert!(matches!(
+            create_swiss_with(&conn, &[crew(1)], 0),
+            Err(CreateSwissError::Invalid(SwissError::TooFewCrews))
+        ));
+    }
+}
@@ -0,0 +1,38 @@
+use crate::event::{acquire_event_with, save_event};
+use crate::prelude::*;
+use becks_arrange::*;
+
+#[derive(Debug, Error)]
+pub enum NextRoundError {
+    #[error("Swiss event {0:?} is not found")]
+    SwissNotFound(Id),
+    #[error("Swiss event {0:?} is closed")]
+    Closed(Id),
+    #[error("the latest round of Swiss event {0:?} is not done")]
+    RoundNotDone(Id),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Pairs the next round of the Swiss event once every result of the latest round is linked,
+/// returning the event with the round paired
+pub fn next_round(login: &Login, id: Id) -> Result<Swiss, NextRoundError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let mut swiss =
+        acquire_event_with::<Swiss>(&tx, id).ok_or(NextRoundError::SwissNotFound(id))?;
+    if swiss.closed {
+        return Err(NextRoundError::Closed(id));
+    }
+    if !swiss.pair_next() {
+        return Err(NextRoundError::RoundNotDone(id));
+    }
+    save_event(&tx, id, &swiss)?;
+    tx.commit()?;
+    info!(
+        "Paired round {} of Swiss event {:?}",
+        swiss.rounds.len(),
+        id
+    );
+    Ok(swiss)
+}
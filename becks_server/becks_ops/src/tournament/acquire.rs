@@ -0,0 +1,7 @@
+use crate::event::acquire_event_with;
+use crate::prelude::*;
+use becks_tournament::*;
+
+pub fn acquire_tournament(login: &Login, id: Id) -> Option<Tournament> {
+    acquire_event_with(&login.read(), id)
+}
@@ -0,0 +1,30 @@
+use crate::event::{acquire_event_with, save_event};
+use crate::prelude::*;
+use becks_tournament::*;
+
+#[derive(Debug, Error)]
+pub enum CloseTournamentError {
+    #[error("tournament {0:?} is not found")]
+    TournamentNotFound(Id),
+    #[error("tournament {0:?} is already closed")]
+    AlreadyClosed(Id),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Closes the tournament, so that recorded matches no longer advance it,
+/// returning its final state
+pub fn close_tournament(login: &Login, id: Id) -> Result<Tournament, CloseTournamentError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let mut tournament = acquire_event_with::<Tournament>(&tx, id)
+        .ok_or(CloseTournamentError::TournamentNotFound(id))?;
+    if tournament.closed {
+        return Err(CloseTournamentError::AlreadyClosed(id));
+    }
+    tournament.closed = true;
+    save_event(&tx, id, &tournament)?;
+    tx.commit()?;
+    info!("Closed tournament {:?}", id);
+    Ok(tournament)
+}
@@ -0,0 +1,67 @@
+use crate::crew::Column;
+use crate::prelude::*;
+use becks_tournament::*;
+
+#[derive(Debug, Error)]
+pub enum CreateTournamentError {
+    #[error("crew {0:?} is not found")]
+    CrewNotFound(Id),
+    #[error(transparent)]
+    Invalid(#[from] TournamentError),
+    #[error("unable to serialize tournament, {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("database error, {0}")]
+    Sql(#[from] rusqlite::Error),
+}
+
+/// Creates a tournament of the crews, seeded by their current scores
+/// from the highest, where crews of the same score keep the given order
+pub fn create_tournament(
+    login: &Login,
+    name: &str,
+    format: Format,
+    crews: &[Id],
+) -> Result<Id, CreateTournamentError> {
+    let mut db = login.db();
+    let tx = db.transaction()?;
+    let mut seeds = Vec::with_capacity(crews.len());
+    for crew in crews.iter().copied() {
+        let score = Score::query_with(&tx, crew).map_err(|err| match err {
+            rusqlite::Error::QueryReturnedNoRows => CreateTournamentError::CrewNotFound(crew),
+            err => err.into(),
+        })?;
+        seeds.push((crew, score.0));
+    }
+    seeds.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let tournament = Tournament::new(
+        name.to_owned(),
+        format,
+        seeds.into_iter().map(|(crew, _)| crew).collect(),
+        timestamp,
+    )?;
+    let id = unused_id(&tx, "tournament");
+    tx.execute(
+        indoc! {"
+            INSERT INTO tournament
+            (id, name, state, closed, timestamp)
+            VALUES ((:id), (:name), (:state), FALSE, (:timestamp))
+        "},
+        rusqlite::named_params! {
+            ":id": id.to_prim(),
+            ":name": name,
+            ":state": serde_json::to_string(&tournament)?,
+            ":timestamp": timestamp,
+        },
+    )?;
+    tx.commit()?;
+    info!(
+        "Created tournament {:?} of {} fixtures",
+        id,
+        tournament.fixtures.len()
+    );
+    Ok(id)
+}
@@ -0,0 +1,7 @@
+mod acquire;
+mod close;
+mod create;
+
+pub use acquire::*;
+pub use close::*;
+pub use create::*;
@@ -0,0 +1,111 @@
+use crate::fixture::*;
+use crate::prelude::*;
+
+/// Returns the seed placed at each position of the first round of a bracket of the size,
+/// so that the top seeds only meet in the latest rounds, like 1-8, 4-5, 2-7, 3-6
+fn seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < size {
+        let len = order.len() * 2;
+        order = order
+            .into_iter()
+            .flat_map(|seed| [seed, len - 1 - seed])
+            .collect();
+    }
+    order
+}
+
+/// Builds the fixtures of an elimination bracket, which is padded with byes to a power of 2
+pub(crate) struct Bracket {
+    pub fixtures: Vec<Fixture>,
+    /// The indices of fixtures in each round of the winners bracket
+    winners: Vec<Vec<usize>>,
+}
+
+impl Bracket {
+    fn push(&mut self, stage: Stage, count: usize) -> Vec<usize> {
+        let begin = self.fixtures.len();
+        self.fixtures
+            .extend(std::iter::repeat_with(|| Fixture::new(stage)).take(count));
+        (begin..begin + count).collect()
+    }
+
+    fn feed_win(&mut self, from: usize, to: usize, left: bool) {
+        self.fixtures[from].win_to = Some(Feed { fixture: to, left });
+    }
+
+    fn feed_lose(&mut self, from: usize, to: usize, left: bool) {
+        self.fixtures[from].lose_to = Some(Feed { fixture: to, left });
+    }
+
+    /// Pairs the fixtures of a round into the next round, returning the fixtures of the next round
+    fn next_round(&mut self, stage: Stage, round: &[usize]) -> Vec<usize> {
+        let next = self.push(stage, round.len() / 2);
+        for (index, from) in round.iter().enumerate() {
+            self.feed_win(*from, next[index / 2], index % 2 == 0);
+        }
+        next
+    }
+
+    pub fn single(seeds: &[Id]) -> Self {
+        let size = seeds.len().next_power_of_two();
+        let mut bracket = Self {
+            fixtures: Vec::new(),
+            winners: Vec::new(),
+        };
+        let mut round = bracket.push(Stage::Winners(0), size / 2);
+        let order = seed_order(size);
+        for (fixture, pair) in round.iter().zip(order.chunks(2)) {
+            let slot = |seed: usize| seeds.get(seed).map_or(Slot::Bye, |crew| Slot::Crew(*crew));
+            bracket.fixtures[*fixture].left = slot(pair[0]);
+            bracket.fixtures[*fixture].right = slot(pair[1]);
+        }
+        bracket.winners.push(round.clone());
+        while round.len() > 1 {
+            round = bracket.next_round(Stage::Winners(bracket.winners.len()), &round);
+            bracket.winners.push(round.clone());
+        }
+        bracket
+    }
+
+    /// Adds the losers bracket and the grand final, where the crews losing in the winners
+    /// bracket get a second chance, and the grand final is played once without a reset
+    pub fn double(seeds: &[Id]) -> Self {
+        let mut bracket = Self::single(seeds);
+        let winners = bracket.winners.clone();
+        let final_winners = winners[winners.len() - 1][0];
+        let grand_final = if winners.len() == 1 {
+            // With only two crews, the loser of the only fixture meets the winner again
+            let grand_final = bracket.push(Stage::GrandFinal, 1)[0];
+            bracket.feed_lose(final_winners, grand_final, false);
+            grand_final
+        } else {
+            let mut stage = 0;
+            let mut round = bracket.push(Stage::Losers(stage), winners[0].len() / 2);
+            for (index, from) in winners[0].iter().enumerate() {
+                bracket.feed_lose(*from, round[index / 2], index % 2 == 0);
+            }
+            for (number, dropping) in winners.iter().enumerate().skip(1) {
+                stage += 1;
+                let next = bracket.push(Stage::Losers(stage), round.len());
+                for (index, from) in round.iter().enumerate() {
+                    bracket.feed_win(*from, next[index], true);
+                }
+                // Losers drop in the reversed order, which delays rematches
+                for (index, from) in dropping.iter().rev().enumerate() {
+                    bracket.feed_lose(*from, next[index], false);
+                }
+                round = next;
+                if number + 1 < winners.len() {
+                    stage += 1;
+                    round = bracket.next_round(Stage::Losers(stage), &round);
+                }
+            }
+            let grand_final = bracket.push(Stage::GrandFinal, 1)[0];
+            bracket.feed_win(round[0], grand_final, false);
+            grand_final
+        };
+        bracket.feed_win(final_winners, grand_final, true);
+        bracket
+    }
+}
@@ -0,0 +1,99 @@
+use crate::prelude::*;
+
+/// A place in a fixture, which is filled once the fixture feeding it is decided
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    #[default]
+    Pending,
+    /// Nobody comes to this place, so the other crew advances without playing
+    Bye,
+    Crew(Id),
+}
+
+impl Slot {
+    pub fn crew(&self) -> Option<Id> {
+        match self {
+            Slot::Crew(crew) => Some(*crew),
+            _ => None,
+        }
+    }
+}
+
+/// The part of the tournament a fixture belongs to, where rounds count from 0
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Group(usize),
+    Winners(usize),
+    Losers(usize),
+    GrandFinal,
+}
+
+/// The place that the winner or the loser of a fixture goes to
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Feed {
+    pub fixture: usize,
+    pub left: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Fixture {
+    pub stage: Stage,
+    pub left: Slot,
+    pub right: Slot,
+    /// The winner, which stays pending until the fixture is decided
+    #[serde(default)]
+    pub winner: Slot,
+    /// The recorded match deciding the fixture, which is absent for byes
+    #[serde(default)]
+    pub mat: Option<Id>,
+    #[serde(default)]
+    pub win_to: Option<Feed>,
+    #[serde(default)]
+    pub lose_to: Option<Feed>,
+}
+
+impl Fixture {
+    pub(crate) fn new(stage: Stage) -> Self {
+        Self {
+            stage,
+            left: Slot::Pending,
+            right: Slot::Pending,
+            winner: Slot::Pending,
+            mat: None,
+            win_to: None,
+            lose_to: None,
+        }
+    }
+
+    pub fn is_decided(&self) -> bool {
+        self.winner != Slot::Pending
+    }
+
+    /// Returns whether both crews are known and the fixture is waiting for their match
+    pub fn is_ready(&self) -> bool {
+        !self.is_decided() && self.left.crew().is_some() && self.right.crew().is_some()
+    }
+
+    /// Returns the loser, which is only meaningful once the fixture is decided
+    pub fn loser(&self) -> Slot {
+        if self.winner == self.left {
+            self.right
+        } else {
+            self.left
+        }
+    }
+
+    /// Returns whether the fixture is played between the two crews, in either order
+    pub fn is_between(&self, lhs: Id, rhs: Id) -> bool {
+        (self.left, self.right) == (Slot::Crew(lhs), Slot::Crew(rhs))
+            || (self.left, self.right) == (Slot::Crew(rhs), Slot::Crew(lhs))
+    }
+
+    pub(crate) fn slot_mut(&mut self, left: bool) -> &mut Slot {
+        if left {
+            &mut self.left
+        } else {
+            &mut self.right
+        }
+    }
+}
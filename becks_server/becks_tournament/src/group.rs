@@ -0,0 +1,44 @@
+use crate::fixture::*;
+use crate::prelude::*;
+
+/// Splits the seeds into groups in a snake order, so that the groups are equally strong,
+/// like 1-4-5 and 2-3-6 for two groups of six crews
+pub(crate) fn split(seeds: &[Id], groups: usize) -> Vec<Vec<Id>> {
+    let mut result = vec![Vec::new(); groups];
+    for (index, crew) in seeds.iter().enumerate() {
+        let (lap, place) = (index / groups, index % groups);
+        let group = if lap % 2 == 0 {
+            place
+        } else {
+            groups - 1 - place
+        };
+        result[group].push(*crew);
+    }
+    result
+}
+
+/// Builds the fixtures where every crew meets every other crew of its group once
+pub(crate) fn fixtures(groups: &[Vec<Id>]) -> Vec<Fixture> {
+    let mut fixtures = Vec::new();
+    for (group, crews) in groups.iter().enumerate() {
+        for (index, left) in crews.iter().enumerate() {
+            for right in crews[index + 1..].iter() {
+                fixtures.push(Fixture {
+                    left: Slot::Crew(*left),
+                    right: Slot::Crew(*right),
+                    ..Fixture::new(Stage::Group(group))
+                });
+            }
+        }
+    }
+    fixtures
+}
+
+/// The record of a crew in its group
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Standing {
+    pub crew: Id,
+    pub played: usize,
+    pub wins: usize,
+    pub losses: usize,
+}
@@ -0,0 +1,10 @@
+mod bracket;
+mod fixture;
+mod group;
+mod prelude;
+mod tournament;
+
+pub use becks_crew::Id;
+pub use fixture::*;
+pub use group::Standing;
+pub use tournament::*;
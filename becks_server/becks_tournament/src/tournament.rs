@@ -0,0 +1,588 @@
+use crate::bracket::Bracket;
+use crate::fixture::*;
+use crate::group::{self, Standing};
+use crate::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Crews are split into the number of groups, and play everyone else in their group
+    RoundRobin {
+        groups: usize,
+    },
+    SingleElimination,
+    DoubleElimination,
+}
+
+#[derive(Debug, Error)]
+pub enum TournamentError {
+    #[error("a tournament needs at least 2 crews")]
+    TooFewCrews,
+    #[error("crew {0:?} enters more than once")]
+    DuplicateCrew(Id),
+    #[error("{0} groups cannot be made of {1} crews")]
+    InvalidGroups(usize, usize),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Tournament {
+    pub name: String,
+    pub format: Format,
+    /// Crews from the first seed to the last
+    pub seeds: Vec<Id>,
+    /// The crews of each group in a round robin, which is empty for brackets
+    #[serde(default)]
+    pub groups: Vec<Vec<Id>>,
+    pub fixtures: Vec<Fixture>,
+    pub timestamp: u64,
+    #[serde(default)]
+    pub closed: bool,
+}
+
+impl Tournament {
+    /// Creates the tournament of the seeds, which are sorted from the strongest crew
+    pub fn new(
+        name: String,
+        format: Format,
+        seeds: Vec<Id>,
+        timestamp: u64,
+    ) -> Result<Self, TournamentError> {
+        if seeds.len() < 2 {
+            return Err(TournamentError::TooFewCrews);
+        }
+        for (index, crew) in seeds.iter().enumerate() {
+            if seeds[index + 1..].contains(crew) {
+                return Err(TournamentError::DuplicateCrew(*crew));
+            }
+        }
+        let groups = match format {
+            Format::RoundRobin { groups } => {
+                if groups == 0 || groups * 2 > seeds.len() {
+                    return Err(TournamentError::InvalidGroups(groups, seeds.len()));
+                }
+                group::split(&seeds, groups)
+            }
+            Format::SingleElimination | Format::DoubleElimination => Vec::new(),
+        };
+        let mut tournament = Self {
+            name,
+            format,
+            seeds,
+            groups,
+            fixtures: Vec::new(),
+            timestamp,
+            closed: false,
+        };
+        tournament.fixtures = tournament.initial_fixtures();
+        for index in 0..tournament.fixtures.len() {
+            tournament.settle_byes(index);
+        }
+        Ok(tournament)
+    }
+
+    /// Decides the fixture automatically if a bye is in it, and passes the result on
+    fn settle_byes(&mut self, index: usize) {
+        let fixture = &self.fixtures[index];
+        if fixture.is_decided() || fixture.left == Slot::Pending || fixture.right == Slot::Pending {
+            return;
+        }
+        let winner = match (fixture.left, fixture.right) {
+            (Slot::Bye, other) | (other, Slot::Bye) => other,
+            _ => return,
+        };
+        self.decide(index, winner);
+    }
+
+    /// Sets the winner of the fixture, filling the places fed by it
+    fn decide(&mut self, index: usize, winner: Slot) {
+        let fixture = &mut self.fixtures[index];
+        fixture.winner = winner;
+        let loser = fixture.loser();
+        let feeds = [(fixture.win_to, winner), (fixture.lose_to, loser)];
+        for (feed, slot) in feeds {
+            if let Some(Feed { fixture, left }) = feed {
+                *self.fixtures[fixture].slot_mut(left) = slot;
+                self.settle_byes(fixture);
+            }
+        }
+    }
+
+    /// Advances the tournament by the recorded match, returning the index of the fixture
+    /// it decides; Matches out of the tournament, doubles and draws are ignored
+    pub fn record(&mut self, id: Id, mat: &Match) -> Option<usize> {
+        if self.closed || mat.is_doubles() {
+            return None;
+        }
+        let index = self
+            .fixtures
+            .iter()
+            .position(|fixture| fixture.is_ready() && fixture.is_between(mat.left, mat.right))?;
+        let winner = if mat.left_won()? { mat.left } else { mat.right };
+        self.fixtures[index].mat = Some(id);
+        self.decide(index, Slot::Crew(winner));
+        Some(index)
+    }
+
+    /// Builds the fixtures of the format as they are before any match is played
+    fn initial_fixtures(&self) -> Vec<Fixture> {
+        match self.format {
+            Format::RoundRobin { .. } => group::fixtures(&self.groups),
+            Format::SingleElimination => Bracket::single(&self.seeds).fixtures,
+            Format::DoubleElimination => Bracket::double(&self.seeds).fixtures,
+        }
+    }
+
+    /// Re-derives the fixtures after the recorded match is voided or modified, where `mat` is
+    /// the match as it is now, or None if it no longer counts; Returns the matches that no
+    /// longer decide a fixture, or None if the match decides none of the fixtures
+    ///
+    /// The fixtures are rebuilt and every other recorded match decides its fixture again
+    /// if the fixture is still played between the same crews, so that fixtures depending on
+    /// a changed result are reset; Closed tournaments are amended as well, since their
+    /// results would otherwise disagree with the scores
+    pub fn amend(&mut self, id: Id, mat: Option<&Match>) -> Option<Vec<Id>> {
+        if !self.fixtures.iter().any(|fixture| fixture.mat == Some(id)) {
+            return None;
+        }
+        let fixtures = self.initial_fixtures();
+        let old = std::mem::replace(&mut self.fixtures, fixtures);
+        for index in 0..self.fixtures.len() {
+            self.settle_byes(index);
+        }
+        let mut released = Vec::new();
+        // Fixtures only feed the ones after them, so every fixture is ready in its turn
+        for (index, fixture) in old.into_iter().enumerate() {
+            let Some(decider) = fixture.mat else {
+                continue;
+            };
+            let current = &self.fixtures[index];
+            let winner = if decider == id {
+                mat.filter(|mat| !mat.is_doubles() && current.is_between(mat.left, mat.right))
+                    .and_then(|mat| Some(if mat.left_won()? { mat.left } else { mat.right }))
+                    .map(Slot::Crew)
+            } else {
+                (current.left == fixture.left && current.right == fixture.right)
+                    .then_some(fixture.winner)
+            };
+            match winner {
+                Some(winner) if current.is_ready() => {
+                    self.fixtures[index].mat = Some(decider);
+                    self.decide(index, winner);
+                }
+                _ => released.push(decider),
+            }
+        }
+        Some(released)
+    }
+
+    /// Returns the fixtures waiting to be played
+    pub fn ready(&self) -> impl Iterator<Item = (usize, &Fixture)> {
+        self.fixtures
+            .iter()
+            .enumerate()
+            .filter(|(_, fixture)| fixture.is_ready())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.fixtures.iter().all(Fixture::is_decided)
+    }
+
+    /// Returns the winner of a finished bracket, which a round robin does not have
+    pub fn champion(&self) -> Option<Id> {
+        match self.format {
+            Format::RoundRobin { .. } => None,
+            _ => self.fixtures.last()?.winner.crew(),
+        }
+    }
+
+    /// Returns the standings of each group, sorted by wins and then by seed
+    pub fn standings(&self) -> Vec<Vec<Standing>> {
+        self.groups
+            .iter()
+            .map(|crews| {
+                let mut standings = crews
+                    .iter()
+                    .map(|crew| {
+                        let mut standing = Standing {
+                            crew: *crew,
+                            played: 0,
+                            wins: 0,
+                            losses: 0,
+                        };
+                        for fixture in self.fixtures.iter().filter(|fixture| {
+                            fixture.is_decided()
+                                && (fixture.left == Slot::Crew(*crew)
+                                    || fixture.right == Slot::Crew(*crew))
+                        }) {
+                            standing.played += 1;
+                            if fixture.winner == Slot::Crew(*crew) {
+                                standing.wins += 1;
+                            } else {
+                                standing.losses += 1;
+                            }
+                        }
+                        standing
+                    })
+                    .collect::<Vec<_>>();
+                // The sort is stable, so crews of the same wins keep their seed order
+                standings.sort_by_key(|standing| std::cmp::Reverse(standing.wins));
+                standings
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use becks_match::Round;
+
+    fn crew(seed: u32) -> Id {
+        Id::from_prim(seed)
+    }
+
+    fn slot(seed: u32) -> Slot {
+        Slot::Crew(crew(seed))
+    }
+
+    fn seeds(count: u32) -> Vec<Id> {
+        (1..=count).map(crew).collect()
+    }
+
+    /// A match of two rounds between the seeds, which the winner wins both of
+    fn beat(winner: u32, loser: u32) -> Match {
+        let mut mat = Match::new(2, crew(winner), crew(loser), 0);
+        mat.rounds = vec![Round::new(true); 2];
+        mat
+    }
+
+    fn pairs(tournament: &Tournament) -> Vec<(Slot, Slot)> {
+        tournament
+            .fixtures
+            .iter()
+            .map(|fixture| (fixture.left, fixture.right))
+            .collect()
+    }
+
+    fn ready(tournament: &Tournament) -> Vec<usize> {
+        tournament.ready().map(|(index, _)| index).collect()
+    }
+
+    #[test]
+    fn test_seed_order() {
+        let tournament =
+            Tournament::new("8".to_owned(), Format::SingleElimination, seeds(8), 0).unwrap();
+        assert_eq!(
+            pairs(&tournament)[..4],
+            [
+                (slot(1), slot(8)),
+                (slot(4), slot(5)),
+                (slot(2), slot(7)),
+                (slot(3), slot(6)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_two() {
+        let mut tournament =
+            Tournament::new("2".to_owned(), Format::SingleElimination, seeds(2), 0).unwrap();
+        assert_eq!(pairs(&tournament), [(slot(1), slot(2))]);
+        assert_eq!(tournament.record(crew(100), &beat(2, 1)), Some(0));
+        assert!(tournament.is_finished());
+        assert_eq!(tournament.champion(), Some(crew(2)));
+    }
+
+    #[test]
+    fn test_single_three() {
+        let mut tournament =
+            Tournament::new("3".to_owned(), Format::SingleElimination, seeds(3), 0).unwrap();
+        assert_eq!(tournament.fixtures.len(), 3);
+        // The top seed advances without playing
+        assert_eq!(tournament.fixtures[0].right, Slot::Bye);
+        assert_eq!(tournament.fixtures[0].winner, slot(1));
+        assert_eq!(tournament.fixtures[0].mat, None);
+        assert_eq!(tournament.fixtures[2].left, slot(1));
+        assert_eq!(tournament.fixtures[2].right, Slot::Pending);
+        assert_eq!(ready(&tournament), [1]);
+
+        assert_eq!(tournament.record(crew(100), &beat(3, 2)), Some(1));
+        assert_eq!(pairs(&tournament)[2], (slot(1), slot(3)));
+        assert_eq!(ready(&tournament), [2]);
+        assert_eq!(tournament.record(crew(101), &beat(3, 1)), Some(2));
+        assert_eq!(tournament.champion(), Some(crew(3)));
+        assert!(ready(&tournament).is_empty());
+    }
+
+    #[test]
+    fn test_single_four() {
+        let mut tournament =
+            Tournament::new("4".to_owned(), Format::SingleElimination, seeds(4), 0).unwrap();
+        assert_eq!(
+            pairs(&tournament),
+            [
+                (slot(1), slot(4)),
+                (slot(2), slot(3)),
+                (Slot::Pending, Slot::Pending),
+            ]
+        );
+        assert_eq!(ready(&tournament), [0, 1]);
+        // Matches between crews not in a ready fixture are ignored
+        assert_eq!(tournament.record(crew(99), &beat(1, 2)), None);
+        assert_eq!(tournament.record(crew(100), &beat(4, 1)), Some(0));
+        assert_eq!(pairs(&tournament)[2], (slot(4), Slot::Pending));
+        // A fixture is decided only once
+        assert_eq!(tournament.record(crew(101), &beat(1, 4)), None);
+        assert_eq!(tournament.record(crew(102), &beat(2, 3)), Some(1));
+        assert_eq!(tournament.champion(), None);
+        assert_eq!(tournament.record(crew(103), &beat(2, 4)), Some(2));
+        assert!(tournament.is_finished());
+        assert_eq!(tournament.champion(), Some(crew(2)));
+    }
+
+    #[test]
+    fn test_single_five() {
+        let mut tournament =
+            Tournament::new("5".to_owned(), Format::SingleElimination, seeds(5), 0).unwrap();
+        assert_eq!(tournament.fixtures.len(), 7);
+        assert_eq!(
+            pairs(&tournament)[..4],
+            [
+                (slot(1), Slot::Bye),
+                (slot(4), slot(5)),
+                (slot(2), Slot::Bye),
+                (slot(3), Slot::Bye),
+            ]
+        );
+        // Byes pass the top seeds on, so that seeds 2 and 3 meet at once
+        assert_eq!(pairs(&tournament)[4], (slot(1), Slot::Pending));
+        assert_eq!(pairs(&tournament)[5], (slot(2), slot(3)));
+        assert_eq!(ready(&tournament), [1, 5]);
+
+        assert_eq!(tournament.record(crew(100), &beat(5, 4)), Some(1));
+        assert_eq!(tournament.record(crew(101), &beat(2, 3)), Some(5));
+        assert_eq!(ready(&tournament), [4]);
+        assert_eq!(tournament.record(crew(102), &beat(1, 5)), Some(4));
+        assert_eq!(pairs(&tournament)[6], (slot(1), slot(2)));
+        assert_eq!(tournament.record(crew(103), &beat(1, 2)), Some(6));
+        assert_eq!(tournament.champion(), Some(crew(1)));
+    }
+
+    #[test]
+    fn test_single_eight() {
+        let mut tournament =
+            Tournament::new("8".to_owned(), Format::SingleElimination, seeds(8), 0).unwrap();
+        assert_eq!(tournament.fixtures.len(), 7);
+        assert_eq!(ready(&tournament), [0, 1, 2, 3]);
+        let mut id = 100;
+        let mut play = |tournament: &mut Tournament, winner, loser| {
+            id += 1;
+            tournament.record(crew(id), &beat(winner, loser))
+        };
+        assert_eq!(play(&mut tournament, 1, 8), Some(0));
+        assert_eq!(play(&mut tournament, 5, 4), Some(1));
+        assert_eq!(play(&mut tournament, 2, 7), Some(2));
+        assert_eq!(play(&mut tournament, 3, 6), Some(3));
+        assert_eq!(
+            pairs(&tournament)[4..6],
+            [(slot(1), slot(5)), (slot(2), slot(3))]
+        );
+        assert_eq!(play(&mut tournament, 1, 5), Some(4));
+        assert_eq!(play(&mut tournament, 3, 2), Some(5));
+        assert_eq!(pairs(&tournament)[6], (slot(1), slot(3)));
+        assert_eq!(play(&mut tournament, 3, 1), Some(6));
+        assert!(tournament.is_finished());
+        assert_eq!(tournament.champion(), Some(crew(3)));
+    }
+
+    #[test]
+    fn test_double_three() {
+        let mut tournament =
+            Tournament::new("3".to_owned(), Format::DoubleElimination, seeds(3), 0).unwrap();
+        assert_eq!(ready(&tournament), [1]);
+        assert_eq!(tournament.record(crew(100), &beat(2, 3)), Some(1));
+        // The loser of the first round meets nobody in the losers bracket, as seed 1 had a bye
+        let losers = tournament
+            .fixtures
+            .iter()
+            .position(|fixture| fixture.stage == Stage::Losers(0))
+            .unwrap();
+        assert_eq!(tournament.fixtures[losers].winner, slot(3));
+        assert_eq!(tournament.record(crew(101), &beat(2, 1)), Some(2));
+        // The loser of the winners final gets a second chance
+        let ready = tournament
+            .ready()
+            .map(|(_, fixture)| fixture)
+            .collect::<Vec<_>>();
+        assert_eq!(ready.len(), 1);
+        assert!(ready[0].is_between(crew(1), crew(3)));
+        tournament.record(crew(102), &beat(1, 3)).unwrap();
+        let grand_final = tournament.fixtures.len() - 1;
+        assert_eq!(tournament.fixtures[grand_final].stage, Stage::GrandFinal);
+        assert_eq!(pairs(&tournament)[grand_final], (slot(2), slot(1)));
+        assert_eq!(tournament.record(crew(103), &beat(1, 2)), Some(grand_final));
+        assert_eq!(tournament.champion(), Some(crew(1)));
+    }
+
+    #[test]
+    fn test_double_four() {
+        let mut tournament =
+            Tournament::new("4".to_owned(), Format::DoubleElimination, seeds(4), 0).unwrap();
+        // 3 fixtures in the winners bracket, 2 in the losers bracket and the grand final
+        assert_eq!(tournament.fixtures.len(), 6);
+        tournament.record(crew(100), &beat(1, 4)).unwrap();
+        tournament.record(crew(101), &beat(2, 3)).unwrap();
+        assert_eq!(pairs(&tournament)[3], (slot(4), slot(3)));
+        tournament.record(crew(102), &beat(3, 4)).unwrap();
+        tournament.record(crew(103), &beat(2, 1)).unwrap();
+        assert_eq!(pairs(&tournament)[4], (slot(3), slot(1)));
+        tournament.record(crew(104), &beat(1, 3)).unwrap();
+        assert_eq!(pairs(&tournament)[5], (slot(2), slot(1)));
+        assert!(!tournament.is_finished());
+        tournament.record(crew(105), &beat(2, 1)).unwrap();
+        assert_eq!(tournament.champion(), Some(crew(2)));
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let mut tournament = Tournament::new(
+            "rr".to_owned(),
+            Format::RoundRobin { groups: 2 },
+            seeds(6),
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            tournament.groups,
+            [
+                vec![crew(1), crew(4), crew(5)],
+                vec![crew(2), crew(3), crew(6)]
+            ]
+        );
+        assert_eq!(tournament.fixtures.len(), 6);
+        assert_eq!(tournament.record(crew(100), &beat(5, 1)), Some(1));
+        // Crews of different groups never meet
+        assert_eq!(tournament.record(crew(101), &beat(1, 2)), None);
+        let standings = tournament.standings();
+        assert_eq!(standings[0][0].crew, crew(5));
+        assert_eq!(standings[0][0].wins, 1);
+        // Crews of the same wins keep their seed order
+        assert_eq!(standings[0][1].crew, crew(1));
+        assert_eq!(standings[0][1].losses, 1);
+        assert_eq!(standings[0][2].crew, crew(4));
+        assert_eq!(standings[0][2].played, 0);
+        assert_eq!(tournament.champion(), None);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(matches!(
+            Tournament::new("1".to_owned(), Format::SingleElimination, seeds(1), 0),
+            Err(TournamentError::TooFewCrews)
+        ));
+        assert!(matches!(
+            Tournament::new(
+                "dup".to_owned(),
+                Format::SingleElimination,
+                vec![crew(1), crew(2), crew(1)],
+                0
+            ),
+            Err(TournamentError::DuplicateCrew(_))
+        ));
+        assert!(matches!(
+            Tournament::new(
+                "groups".to_owned(),
+                Format::RoundRobin { groups: 3 },
+                seeds(5),
+                0
+            ),
+            Err(TournamentError::InvalidGroups(3, 5))
+        ));
+    }
+
+    /// A bracket of four crews where the favourites win, decided by matches 100 to 102
+    fn played_four() -> Tournament {
+        let mut tournament =
+            Tournament::new("4".to_owned(), Format::SingleElimination, seeds(4), 0).unwrap();
+        tournament.record(crew(100), &beat(1, 4)).unwrap();
+        tournament.record(crew(101), &beat(2, 3)).unwrap();
+        tournament.record(crew(102), &beat(1, 2)).unwrap();
+        tournament
+    }
+
+    #[test]
+    fn test_amend_void() {
+        let mut tournament = played_four();
+        assert_eq!(
+            tournament.amend(crew(100), None),
+            Some(vec![crew(100), crew(102)])
+        );
+        assert_eq!(tournament.fixtures[0].winner, Slot::Pending);
+        assert_eq!(tournament.fixtures[0].mat, None);
+        assert_eq!(tournament.fixtures[1].mat, Some(crew(101)));
+        assert_eq!(pairs(&tournament)[2], (Slot::Pending, slot(2)));
+        assert_eq!(tournament.champion(), None);
+        assert_eq!(ready(&tournament), [0]);
+        // The fixture can be decided again
+        assert_eq!(tournament.record(crew(103), &beat(4, 1)), Some(0));
+        assert_eq!(pairs(&tournament)[2], (slot(4), slot(2)));
+    }
+
+    #[test]
+    fn test_amend_flip() {
+        let mut tournament = played_four();
+        assert_eq!(
+            tournament.amend(crew(100), Some(&beat(4, 1))),
+            Some(vec![crew(102)])
+        );
+        assert_eq!(tournament.fixtures[0].winner, slot(4));
+        assert_eq!(tournament.fixtures[0].mat, Some(crew(100)));
+        assert_eq!(pairs(&tournament)[2], (slot(4), slot(2)));
+        assert_eq!(tournament.fixtures[2].winner, Slot::Pending);
+        assert_eq!(ready(&tournament), [2]);
+    }
+
+    #[test]
+    fn test_amend_unchanged() {
+        let mut tournament = played_four();
+        assert_eq!(tournament.amend(crew(101), Some(&beat(2, 3))), Some(vec![]));
+        assert_eq!(tournament.champion(), Some(crew(1)));
+        assert!(tournament
+            .fixtures
+            .iter()
+            .all(|fixture| fixture.mat.is_some()));
+        // Matches not in the tournament are left alone
+        assert_eq!(tournament.amend(crew(99), None), None);
+    }
+
+    #[test]
+    fn test_amend_other_crews() {
+        let mut tournament = played_four();
+        // The match is now between other crews, so its fixture is reset
+        assert_eq!(
+            tournament.amend(crew(102), Some(&beat(3, 4))),
+            Some(vec![crew(102)])
+        );
+        assert_eq!(pairs(&tournament)[2], (slot(1), slot(2)));
+        assert_eq!(ready(&tournament), [2]);
+    }
+
+    #[test]
+    fn test_amend_byes() {
+        let mut tournament =
+            Tournament::new("3".to_owned(), Format::DoubleElimination, seeds(3), 0).unwrap();
+        tournament.record(crew(100), &beat(2, 3)).unwrap();
+        tournament.record(crew(101), &beat(2, 1)).unwrap();
+        assert_eq!(
+            tournament.amend(crew(100), Some(&beat(3, 2))),
+            Some(vec![crew(101)])
+        );
+        // Byes are settled again with the new loser
+        let losers = tournament
+            .fixtures
+            .iter()
+            .position(|fixture| fixture.stage == Stage::Losers(0))
+            .unwrap();
+        assert_eq!(tournament.fixtures[losers].winner, slot(2));
+        assert_eq!(pairs(&tournament)[2], (slot(1), slot(3)));
+    }
+}
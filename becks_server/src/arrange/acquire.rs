@@ -0,0 +1,25 @@
+use crate::prelude::*;
+use becks_convey::arrange::acquire::*;
+
+#[get("/acquire")]
+pub(super) async fn acquire_arrangement(
+    http: HttpRequest,
+    req: web::Json<AcquireRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire arrangement id {:?}", req.arrangement);
+    let login = extract_login!(db, http, req.token);
+    let id = req.arrangement;
+    if let Some(arrangement) =
+        run_blocking!(move || becks_ops::arrange::acquire_arrangement(login.as_ref(), id))
+    {
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(AcquireResponse { arrangement })
+    } else {
+        api_error(
+            ErrorCode::ArrangementNotFound,
+            "unable to acquire given arrangement id",
+        )
+    }
+}
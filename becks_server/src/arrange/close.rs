@@ -0,0 +1,34 @@
+use crate::prelude::*;
+use becks_convey::arrange::close::*;
+use becks_ops::arrange::CloseArrangementError;
+
+#[post("/close")]
+pub(super) async fn close_arrangement(
+    http: HttpRequest,
+    req: web::Json<CloseRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token, Role::Recorder);
+    info!(
+        "Closing arrangement {:?} by {}",
+        req.arrangement, login.name
+    );
+    let id = req.arrangement;
+    match run_blocking!(move || becks_ops::arrange::close_arrangement(login.as_ref(), id)) {
+        Ok(arrangement) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(CloseResponse { arrangement }),
+        Err(err @ CloseArrangementError::ArrangementNotFound(_)) => {
+            warn!("Unable to close arrangement because {}", err);
+            api_error(ErrorCode::ArrangementNotFound, err.to_string())
+        }
+        Err(err @ CloseArrangementError::AlreadyClosed(_)) => {
+            warn!("Unable to close arrangement because {}", err);
+            api_error(ErrorCode::InvalidModification, err.to_string())
+        }
+        Err(err) => {
+            error!("When closing arrangement, {}", err);
+            api_error(ErrorCode::Internal, "unable to close arrangement")
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::prelude::*;
+use becks_convey::arrange::create::*;
+use becks_ops::arrange::CreateArrangementError;
+
+#[post("/create")]
+pub(super) async fn create_arrangement(
+    http: HttpRequest,
+    req: web::Json<CreateRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token, Role::Recorder);
+    info!(
+        "Creating arrangement of {} groups by {}",
+        req.groups.len(),
+        login.name
+    );
+    let groups = req.into_inner().groups;
+    match run_blocking!(move || becks_ops::arrange::create_arrangement(login.as_ref(), groups)) {
+        Ok(arrangement) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(CreateResponse { arrangement }),
+        Err(err @ CreateArrangementError::CrewNotFound(_)) => {
+            warn!("Unable to create arrangement because {}", err);
+            api_error(ErrorCode::CrewNotFound, err.to_string())
+        }
+        Err(err @ CreateArrangementError::Invalid(_)) => {
+            warn!("Unable to create arrangement because {}", err);
+            api_error(ErrorCode::IllegalInput, err.to_string())
+        }
+        Err(err) => {
+            error!("When creating arrangement, {}", err);
+            api_error(ErrorCode::Internal, "unable to create arrangement")
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::prelude::*;
+use becks_convey::arrange::list::*;
+
+#[get("/list")]
+pub(super) async fn list_arrangements(
+    http: HttpRequest,
+    req: web::Json<ListRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token);
+    let closed = req.closed;
+    match run_blocking!(move || becks_ops::arrange::list_arrangements(login.as_ref(), closed)) {
+        Ok(arrangements) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(ListResponse { arrangements }),
+        Err(err) => {
+            error!("When listing arrangements, {}", err);
+            api_error(ErrorCode::Internal, "unable to list arrangements")
+        }
+    }
+}
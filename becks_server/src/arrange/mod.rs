@@ -0,0 +1,23 @@
+mod acquire;
+mod close;
+mod create;
+mod list;
+
+use crate::prelude::*;
+
+#[get("/test")]
+async fn test() -> impl Responder {
+    debug!("Responding /arrange/test");
+    HttpResponse::Ok().body("Arrange module is running!")
+}
+
+pub fn config_arrange(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/arrange")
+            .service(test)
+            .service(create::create_arrangement)
+            .service(acquire::acquire_arrangement)
+            .service(list::list_arrangements)
+            .service(close::close_arrangement),
+    );
+}
@@ -0,0 +1,14 @@
+/// Runs blocking database work on the thread pool of actix, so that it does not stall
+/// the worker threads, or returns 500 if the work panics
+#[macro_export]
+macro_rules! run_blocking {
+    ($work: expr) => {
+        match web::block($work).await {
+            Ok(value) => value,
+            Err(err) => {
+                error!("When running blocking database work, {}", err);
+                return $crate::error::api_error(ErrorCode::Internal, "database work failed");
+            }
+        }
+    };
+}
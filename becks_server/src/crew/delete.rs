@@ -2,16 +2,19 @@ use crate::prelude::*;
 use becks_convey::crew::delete::*;
 
 #[post("/delete")]
-pub(super) async fn delete_crew(req: web::Json<DeleteRequest>, db: DbData) -> HttpResponse {
+pub(super) async fn delete_crew(
+    http: HttpRequest,
+    req: web::Json<DeleteRequest>,
+    db: DbData,
+) -> HttpResponse {
     debug!("Attempt to delete crew with id {:?}", req.crew);
-    let login = extract_login!(db, &req.token);
-    if becks_ops::crew::delete_crew(&login, req.crew) {
+    let login = extract_login!(db, http, req.token, Role::Admin);
+    let crew = req.crew;
+    if run_blocking!(move || becks_ops::crew::delete_crew(&login, crew)) {
         HttpResponse::Ok()
             .content_type(http::header::ContentType::plaintext())
             .body("crew deleted")
     } else {
-        HttpResponse::BadRequest()
-            .content_type(http::header::ContentType::plaintext())
-            .body("unable to delete crew")
+        api_error(ErrorCode::CrewNotFound, "unable to delete crew")
     }
 }
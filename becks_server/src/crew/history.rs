@@ -0,0 +1,27 @@
+use crate::prelude::*;
+use becks_convey::crew::history::*;
+use becks_ops::crew::CrewHistoryError;
+
+#[get("/history")]
+pub(super) async fn crew_history(
+    http: HttpRequest,
+    req: web::Json<HistoryRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire score history of crew {:?}", req.crew);
+    let login = extract_login!(db, http, req.token);
+    let crew = req.crew;
+    match run_blocking!(move || becks_ops::crew::crew_history(login.as_ref(), crew)) {
+        Ok((initial, history)) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(HistoryResponse { initial, history }),
+        Err(err @ CrewHistoryError::CrewNotFound(_)) => {
+            warn!("Unable to acquire crew history because {}", err);
+            api_error(ErrorCode::CrewNotFound, err.to_string())
+        }
+        Err(err) => {
+            error!("When acquiring crew history, {}", err);
+            api_error(ErrorCode::Internal, "unable to acquire crew history")
+        }
+    }
+}
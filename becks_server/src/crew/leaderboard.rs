@@ -0,0 +1,22 @@
+use crate::prelude::*;
+use becks_convey::crew::leaderboard::*;
+
+#[get("/leaderboard")]
+pub(super) async fn crew_leaderboard(
+    http: HttpRequest,
+    req: web::Json<LeaderboardRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire leaderboard by {:?}", req.filter);
+    let login = extract_login!(db, http, req.token);
+    let filter = req.into_inner().filter;
+    match run_blocking!(move || becks_ops::crew::leaderboard(login.as_ref(), &filter)) {
+        Ok(rows) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(LeaderboardResponse { rows }),
+        Err(err) => {
+            error!("When acquiring leaderboard, {}", err);
+            api_error(ErrorCode::Internal, "unable to acquire leaderboard")
+        }
+    }
+}
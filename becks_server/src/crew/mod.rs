@@ -1,7 +1,10 @@
 mod create;
 mod delete;
+mod history;
+mod leaderboard;
 mod modify;
 mod query;
+mod stats;
 
 use crate::prelude::*;
 
@@ -18,7 +21,11 @@ pub fn config_crew(cfg: &mut web::ServiceConfig) {
             .service(create::create_crew)
             .service(modify::modify_crew)
             .service(modify::acquire_crew)
+            .service(modify::acquire_many_crew)
             .service(query::query_by_crew)
+            .service(stats::crew_stats)
+            .service(history::crew_history)
+            .service(leaderboard::crew_leaderboard)
             .service(delete::delete_crew),
     );
 }
@@ -16,32 +16,36 @@ macro_rules! modify_by {
         );
         if $column.modify($login.as_ref(), $req.crew) {
             info!("Modification of field {} is done", <$type>::name());
-            HttpResponse::Ok()
-                .content_type(http::header::ContentType::plaintext())
-                .body("modification successful")
+            Ok(())
         } else {
             warn!("Unable to modify field {}", <$type>::name());
-            HttpResponse::BadRequest()
-                .content_type(http::header::ContentType::plaintext())
-                .body("unable to modify the desired field")
+            Err(ApiError::new(
+                ErrorCode::InvalidModification,
+                "unable to modify the desired field",
+            ))
         }
     }};
 }
 
 #[post("/modify")]
-pub(super) async fn modify_crew(req: web::Json<ModifyRequest>, db: DbData) -> HttpResponse {
+pub(super) async fn modify_crew(
+    http: HttpRequest,
+    req: web::Json<ModifyRequest>,
+    db: DbData,
+) -> HttpResponse {
     use CrewLocation as Loc;
 
-    let login = extract_login!(db, &req.token);
-    match req.loc.to_owned() {
+    let login = extract_login!(db, http, req.token, Role::Admin);
+    let modify = move || match req.loc.to_owned() {
         Loc::Name(name) => modify_by!(String, name, login, req),
         Loc::Social(social) => modify_by!(Social, social, login, req),
         Loc::Score(score) => {
             let score_applied = ScoreApplied::query(&login, req.crew, false).unwrap_or_default();
             if score_applied.0 {
-                HttpResponse::BadRequest()
-                    .content_type(http::header::ContentType::plaintext())
-                    .body("unable to modify a crew's score when score applied is set to true")
+                Err(ApiError::new(
+                    ErrorCode::InvalidModification,
+                    "unable to modify a crew's score when score applied is set to true",
+                ))
             } else {
                 modify_by!(Score, score, login, req)
             }
@@ -57,19 +61,30 @@ pub(super) async fn modify_crew(req: web::Json<ModifyRequest>, db: DbData) -> Ht
         Loc::Beat(beat) => modify_by!(Beat, beat, login, req),
         Loc::ScoreApplied(score_applied) => {
             if !score_applied.0 {
-                HttpResponse::BadRequest()
-                    .content_type(http::header::ContentType::plaintext())
-                    .body("unable to modify a crew's score_applied to false")
+                Err(ApiError::new(
+                    ErrorCode::InvalidModification,
+                    "unable to modify a crew's score_applied to false",
+                ))
             } else {
                 modify_by!(ScoreApplied, score_applied, login, req)
             }
         }
+    };
+    match run_blocking!(modify) {
+        Ok(()) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::plaintext())
+            .body("modification successful"),
+        Err(err) => error_response(err),
     }
 }
 
 #[get("/acquire")]
-pub(super) async fn acquire_crew(req: web::Json<AcquireRequest>, db: DbData) -> HttpResponse {
-    let login = extract_login!(db, &req.token);
+pub(super) async fn acquire_crew(
+    http: HttpRequest,
+    req: web::Json<AcquireRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token);
     let get_crew = move || -> Option<CrewData> {
         let data = CrewData {
             name: String::query(&login, req.crew, true)?,
@@ -87,13 +102,25 @@ pub(super) async fn acquire_crew(req: web::Json<AcquireRequest>, db: DbData) ->
         };
         Some(data)
     };
-    if let Some(crew) = get_crew() {
+    if let Some(crew) = run_blocking!(get_crew) {
         HttpResponse::Ok()
             .content_type(http::header::ContentType::json())
             .json(AcquireResponse { crew })
     } else {
-        HttpResponse::BadRequest()
-            .content_type(http::header::ContentType::plaintext())
-            .body("failed to acquire desired id")
+        api_error(ErrorCode::CrewNotFound, "failed to acquire desired id")
     }
 }
+
+#[get("/acquire_many")]
+pub(super) async fn acquire_many_crew(
+    http: HttpRequest,
+    req: web::Json<AcquireManyRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire {} crews", req.crews.len());
+    let login = extract_login!(db, http, req.token);
+    let crews = run_blocking!(move || becks_ops::crew::acquire_crews(login.as_ref(), &req.crews));
+    HttpResponse::Ok()
+        .content_type(http::header::ContentType::json())
+        .json(AcquireManyResponse { crews })
+}
@@ -1,17 +1,29 @@
 use crate::prelude::*;
 use becks_convey::crew::query::*;
+use becks_convey::page::PageInfo;
 use becks_ops::crew::*;
 
 #[get("/query")]
-pub(super) async fn query_by_crew(req: web::Json<QueryByRequest>, db: DbData) -> HttpResponse {
+pub(super) async fn query_by_crew(
+    http: HttpRequest,
+    req: web::Json<QueryByRequest>,
+    db: DbData,
+) -> HttpResponse {
     debug!("Querying crew id by columns");
-    let login = extract_login!(db, &req.token);
+    let login = extract_login!(db, http, req.token);
     let query = QueryBy {
         by: req.by.clone(),
         fuzzy: req.fuzzy,
+        page: req.page,
     };
-    let ids = query.query(login.as_ref());
+    let page = run_blocking!(move || query.query(login.as_ref()));
     HttpResponse::Ok()
         .content_type(http::header::ContentType::json())
-        .json(QueryByResponse { ids })
+        .json(QueryByResponse {
+            ids: page.ids,
+            page: PageInfo {
+                total: page.total,
+                next: page.next,
+            },
+        })
 }
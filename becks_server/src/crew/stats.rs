@@ -0,0 +1,27 @@
+use crate::prelude::*;
+use becks_convey::crew::stats::*;
+use becks_ops::crew::CrewStatsError;
+
+#[get("/stats")]
+pub(super) async fn crew_stats(
+    http: HttpRequest,
+    req: web::Json<StatsRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire stats of crew {:?}", req.crew);
+    let login = extract_login!(db, http, req.token);
+    let (crew, opponent) = (req.crew, req.opponent);
+    match run_blocking!(move || becks_ops::crew::crew_stats(login.as_ref(), crew, opponent)) {
+        Ok(stats) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(StatsResponse { stats }),
+        Err(err @ CrewStatsError::CrewNotFound(_)) => {
+            warn!("Unable to acquire crew stats because {}", err);
+            api_error(ErrorCode::CrewNotFound, err.to_string())
+        }
+        Err(err) => {
+            error!("When acquiring crew stats, {}", err);
+            api_error(ErrorCode::Internal, "unable to acquire crew stats")
+        }
+    }
+}
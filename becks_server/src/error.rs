@@ -0,0 +1,49 @@
+use crate::prelude::*;
+use actix_web::http::StatusCode;
+
+fn status_of(code: ErrorCode) -> StatusCode {
+    use ErrorCode as C;
+    match code {
+        C::MalformedRequest
+        | C::IllegalInput
+        | C::UserConflict
+        | C::GrantRejected
+        | C::MatchIncomplete
+        | C::InvalidPoints
+        | C::InvalidModification => StatusCode::BAD_REQUEST,
+        C::MissingToken | C::InvalidToken | C::WrongCredentials | C::InvalidResetCode => {
+            StatusCode::UNAUTHORIZED
+        }
+        C::Forbidden => StatusCode::FORBIDDEN,
+        C::SessionNotFound
+        | C::MemberNotFound
+        | C::CrewNotFound
+        | C::MatchNotFound
+        | C::PosterNotFound
+        | C::TournamentNotFound
+        | C::SwissNotFound
+        | C::ArrangementNotFound => StatusCode::NOT_FOUND,
+        C::Internal | C::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Builds a JSON error response, whose status follows the error code
+pub(crate) fn error_response(err: ApiError) -> HttpResponse {
+    HttpResponse::build(status_of(err.code))
+        .content_type(http::header::ContentType::json())
+        .json(err)
+}
+
+/// Builds a JSON error response with the given code and message
+pub(crate) fn api_error(code: ErrorCode, message: impl Into<String>) -> HttpResponse {
+    error_response(ApiError::new(code, message))
+}
+
+/// Reports request bodies that cannot be parsed as [`ErrorCode::MalformedRequest`]
+pub(crate) fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default().error_handler(|err, http| {
+        warn!("Malformed request body of {}, {}", http.path(), err);
+        let response = api_error(ErrorCode::MalformedRequest, err.to_string());
+        actix_web::error::InternalError::from_response(err, response).into()
+    })
+}
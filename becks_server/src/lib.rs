@@ -1,7 +1,12 @@
+mod arrange;
+mod blocking;
 mod crew;
+mod error;
 mod mat;
 mod poster;
 mod prelude;
+mod swiss;
+mod tournament;
 mod user;
 
 use prelude::*;
@@ -29,12 +34,16 @@ pub async fn app() -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(db.clone()))
+            .app_data(error::json_config())
             .service(hello)
             .service(hello_name)
             .configure(user::config_user)
             .configure(crew::config_crew)
             .configure(mat::config_mat)
             .configure(poster::config_poster)
+            .configure(tournament::config_tournament)
+            .configure(swiss::config_swiss)
+            .configure(arrange::config_arrange)
     })
     .bind(becks_db::CONFIG.server.addr)?
     .run()
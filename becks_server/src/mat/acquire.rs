@@ -2,19 +2,35 @@ use crate::prelude::*;
 use becks_convey::mat::acquire::*;
 
 #[get("/acquire")]
-pub(super) async fn acquire_mat(req: web::Json<AcquireRequest>, db: DbData) -> HttpResponse {
-    debug!(
-        "Attempt to acquire with token {:?}, match id {:?}",
-        req.token, req.mat
-    );
-    let login = extract_login!(db, &req.token);
-    if let Some(mat) = becks_ops::mat::acquire_match(login.as_ref(), req.mat, true) {
+pub(super) async fn acquire_mat(
+    http: HttpRequest,
+    req: web::Json<AcquireRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire match id {:?}", req.mat);
+    let login = extract_login!(db, http, req.token);
+    let mat = req.mat;
+    if let Some(mat) =
+        run_blocking!(move || becks_ops::mat::acquire_match(login.as_ref(), mat, true))
+    {
         HttpResponse::Ok()
             .content_type(http::header::ContentType::json())
             .json(AcquireResponse { mat })
     } else {
-        HttpResponse::BadRequest()
-            .content_type(http::header::ContentType::plaintext())
-            .body("unable to acquire given match id")
+        api_error(ErrorCode::MatchNotFound, "unable to acquire given match id")
     }
 }
+
+#[get("/acquire_many")]
+pub(super) async fn acquire_many_mat(
+    http: HttpRequest,
+    req: web::Json<AcquireManyRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire {} matches", req.mats.len());
+    let login = extract_login!(db, http, req.token);
+    let mats = run_blocking!(move || becks_ops::mat::acquire_matches(login.as_ref(), &req.mats));
+    HttpResponse::Ok()
+        .content_type(http::header::ContentType::json())
+        .json(AcquireManyResponse { mats })
+}
@@ -1,33 +1,42 @@
 use crate::prelude::*;
 use becks_convey::mat::create::*;
+use becks_ops::mat::CreateMatchError;
 
 #[post("/create")]
-pub(super) async fn create_mat(req: web::Json<CreateRequest>, db: DbData) -> HttpResponse {
+pub(super) async fn create_mat(
+    http: HttpRequest,
+    req: web::Json<CreateRequest>,
+    db: DbData,
+) -> HttpResponse {
     #[cfg(debug_assertions)]
     debug!(
         "Attempt to create match {:?}",
         serde_json::to_string(&req.mat)
     );
-    let login = extract_login!(db, &req.token);
-    let mut mat = req.mat.clone();
-    if let Some((left_earn, right_earn)) = becks_ops::mat::update_crew(login.as_ref(), &mat) {
-        mat.left_earn = left_earn;
-        mat.right_earn = right_earn;
-        match becks_ops::mat::create_match(login.as_ref(), &mat) {
-            Ok(mat) => HttpResponse::Ok()
-                .content_type(http::header::ContentType::json())
-                .json(CreateResponse { mat }),
-            Err(err) => {
-                warn!("Unable to create match because {}", err);
-                HttpResponse::BadRequest()
-                    .content_type(http::header::ContentType::plaintext())
-                    .body(format!("{}", err))
-            }
+    let login = extract_login!(db, http, req.token, Role::Recorder);
+    match run_blocking!(move || becks_ops::mat::record_match(login.as_ref(), &req.mat)) {
+        Ok(mat) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(CreateResponse { mat }),
+        Err(err @ CreateMatchError::Incomplete) => {
+            warn!("Unable to create match because {}", err);
+            api_error(ErrorCode::MatchIncomplete, err.to_string())
+        }
+        Err(err @ CreateMatchError::CrewNotFound(_)) => {
+            warn!("Unable to create match because {}", err);
+            api_error(ErrorCode::CrewNotFound, err.to_string())
+        }
+        Err(err @ (CreateMatchError::SameCrew | CreateMatchError::OneSided)) => {
+            warn!("Unable to create match because {}", err);
+            api_error(ErrorCode::IllegalInput, err.to_string())
+        }
+        Err(err @ (CreateMatchError::InvalidPoints(_) | CreateMatchError::WinnerMismatch(_))) => {
+            warn!("Unable to create match because {}", err);
+            api_error(ErrorCode::InvalidPoints, err.to_string())
+        }
+        Err(err @ CreateMatchError::Sql(_)) => {
+            error!("When creating match, {}", err);
+            api_error(ErrorCode::Internal, "unable to record match")
         }
-    } else {
-        error!("When creating match, unable to update crew score");
-        HttpResponse::BadRequest()
-            .content_type(http::header::ContentType::plaintext())
-            .body("unable to update score")
     }
 }
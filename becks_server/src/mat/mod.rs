@@ -2,6 +2,8 @@ mod acquire;
 mod create;
 mod modify;
 mod query;
+mod replay;
+mod void;
 
 use crate::prelude::*;
 
@@ -17,7 +19,10 @@ pub fn config_mat(cfg: &mut web::ServiceConfig) {
             .service(test)
             .service(create::create_mat)
             .service(acquire::acquire_mat)
+            .service(acquire::acquire_many_mat)
             .service(modify::modify_mat)
-            .service(query::query_mat),
+            .service(query::query_mat)
+            .service(replay::replay_mat)
+            .service(void::void_mat),
     );
 }
@@ -1,20 +1,64 @@
 use crate::prelude::*;
 use becks_convey::mat::modify::*;
+use becks_ops::mat::{CreateMatchError, ModifyMatchError};
 
 #[post("/modify")]
-pub(super) async fn modify_mat(req: web::Json<ModifyRequest>, db: DbData) -> HttpResponse {
+pub(super) async fn modify_mat(
+    http: HttpRequest,
+    req: web::Json<ModifyRequest>,
+    db: DbData,
+) -> HttpResponse {
     debug!(
-        "Attempt to modify match {:?} notes to {}",
-        req.mat, req.notes
+        "Attempt to modify match {:?} with {:?}",
+        req.mat, req.modify
     );
-    let login = extract_login!(db, &req.token);
-    if becks_ops::mat::modify_match_notes(login.as_ref(), req.mat, &req.notes) {
-        HttpResponse::Ok()
-            .content_type(http::header::ContentType::plaintext())
-            .body("modification done")
+    // Edits that change the score impact replay every match, which is as heavy as the replay
+    // endpoint, while recorders may still edit the notes
+    let login = if req.modify.affects_score() {
+        extract_login!(db, http, req.token, Role::Admin)
     } else {
-        HttpResponse::BadRequest()
-            .content_type(http::header::ContentType::plaintext())
-            .body("unable to modify given match")
+        extract_login!(db, http, req.token, Role::Recorder)
+    };
+    match run_blocking!(move || becks_ops::mat::modify_match(
+        login.as_ref(),
+        &login.user,
+        req.mat,
+        &req.modify
+    )) {
+        Ok(replayed) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(ModifyResponse {
+                diffs: replayed.diffs,
+            }),
+        Err(err @ ModifyMatchError::MatchNotFound(_)) => {
+            warn!("Unable to modify match because {}", err);
+            api_error(ErrorCode::MatchNotFound, err.to_string())
+        }
+        Err(err @ ModifyMatchError::CrewNotFound(_)) => {
+            warn!("Unable to modify match because {}", err);
+            api_error(ErrorCode::CrewNotFound, err.to_string())
+        }
+        Err(err @ ModifyMatchError::Incomplete) => {
+            warn!("Unable to modify match because {}", err);
+            api_error(ErrorCode::MatchIncomplete, err.to_string())
+        }
+        Err(ModifyMatchError::Update(
+            err @ (CreateMatchError::InvalidPoints(_) | CreateMatchError::WinnerMismatch(_)),
+        )) => {
+            warn!("Unable to modify match because {}", err);
+            api_error(ErrorCode::InvalidPoints, err.to_string())
+        }
+        Err(
+            err @ (ModifyMatchError::NoRounds
+            | ModifyMatchError::SameCrew
+            | ModifyMatchError::OneSided),
+        ) => {
+            warn!("Unable to modify match because {}", err);
+            api_error(ErrorCode::InvalidModification, err.to_string())
+        }
+        Err(err) => {
+            error!("When modifying match, {}", err);
+            api_error(ErrorCode::Internal, "unable to modify match")
+        }
     }
 }
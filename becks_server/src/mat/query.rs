@@ -1,11 +1,22 @@
 use crate::prelude::*;
 use becks_convey::mat::query::*;
+use becks_convey::page::PageInfo;
 
 #[get("/query")]
-pub(super) async fn query_mat(req: web::Json<QueryRequest>, db: DbData) -> HttpResponse {
-    let login = extract_login!(db, &req.token);
-    let ids = becks_ops::mat::query(login.as_ref(), &req);
+pub(super) async fn query_mat(
+    http: HttpRequest,
+    req: web::Json<QueryRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token);
+    let page = run_blocking!(move || becks_ops::mat::query(login.as_ref(), &req));
     HttpResponse::Ok()
         .content_type(http::header::ContentType::json())
-        .json(QueryResponse { ids })
+        .json(QueryResponse {
+            ids: page.ids,
+            page: PageInfo {
+                total: page.total,
+                next: page.next,
+            },
+        })
 }
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+use becks_convey::mat::replay::*;
+
+#[post("/replay")]
+pub(super) async fn replay_mat(
+    http: HttpRequest,
+    req: web::Json<ReplayRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token, Role::Admin);
+    let dry_run = req.dry_run;
+    info!(
+        "Replaying all matches of {}, dry run: {}",
+        login.name, dry_run
+    );
+    match run_blocking!(move || becks_ops::mat::replay_matches(login.as_ref(), dry_run)) {
+        Ok(replayed) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(ReplayResponse {
+                applied: !dry_run,
+                matches: replayed.matches,
+                diffs: replayed.diffs,
+            }),
+        Err(err) => {
+            error!("When replaying matches, {}", err);
+            api_error(ErrorCode::Internal, "unable to replay matches")
+        }
+    }
+}
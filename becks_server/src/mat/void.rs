@@ -0,0 +1,33 @@
+use crate::prelude::*;
+use becks_convey::mat::void::*;
+use becks_ops::mat::VoidMatchError;
+
+#[post("/void")]
+pub(super) async fn void_mat(
+    http: HttpRequest,
+    req: web::Json<VoidRequest>,
+    db: DbData,
+) -> HttpResponse {
+    // Voiding replays every match, which is as heavy as the replay endpoint
+    let login = extract_login!(db, http, req.token, Role::Admin);
+    info!("Voiding match {:?} of {}", req.mat, login.name);
+    match run_blocking!(move || becks_ops::mat::void_match(login.as_ref(), req.mat)) {
+        Ok(replayed) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(VoidResponse {
+                diffs: replayed.diffs,
+            }),
+        Err(err @ VoidMatchError::MatchNotFound(_)) => {
+            warn!("Unable to void match because {}", err);
+            api_error(ErrorCode::MatchNotFound, err.to_string())
+        }
+        Err(err @ VoidMatchError::AlreadyVoided(_)) => {
+            warn!("Unable to void match because {}", err);
+            api_error(ErrorCode::InvalidModification, err.to_string())
+        }
+        Err(err) => {
+            error!("When voiding match, {}", err);
+            api_error(ErrorCode::Internal, "unable to void match")
+        }
+    }
+}
@@ -2,16 +2,39 @@ use crate::prelude::*;
 use becks_convey::poster::acquire::*;
 
 #[get("/acquire")]
-pub(super) async fn acquire_poster(req: web::Json<AcquireRequest>, db: DbData) -> HttpResponse {
+pub(super) async fn acquire_poster(
+    http: HttpRequest,
+    req: web::Json<AcquireRequest>,
+    db: DbData,
+) -> HttpResponse {
     debug!("Attempt to acquire poster {:?}", req.poster);
-    let login = extract_login!(db, &req.token);
-    if let Some(poster) = becks_ops::poster::acquire_poster(login.as_ref(), req.poster) {
+    let login = extract_login!(db, http, req.token);
+    let poster = req.poster;
+    if let Some(poster) =
+        run_blocking!(move || becks_ops::poster::acquire_poster(login.as_ref(), poster))
+    {
         HttpResponse::Ok()
             .content_type(http::header::ContentType::json())
             .json(AcquireResponse { poster })
     } else {
-        HttpResponse::BadRequest()
-            .content_type(http::header::ContentType::plaintext())
-            .body("unable to acquire desired poster")
+        api_error(
+            ErrorCode::PosterNotFound,
+            "unable to acquire desired poster",
+        )
     }
 }
+
+#[get("/acquire_many")]
+pub(super) async fn acquire_many_poster(
+    http: HttpRequest,
+    req: web::Json<AcquireManyRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire {} posters", req.posters.len());
+    let login = extract_login!(db, http, req.token);
+    let posters =
+        run_blocking!(move || becks_ops::poster::acquire_posters(login.as_ref(), &req.posters));
+    HttpResponse::Ok()
+        .content_type(http::header::ContentType::json())
+        .json(AcquireManyResponse { posters })
+}
@@ -16,6 +16,7 @@ pub fn config_poster(cfg: &mut web::ServiceConfig) {
             .service(test)
             .service(create::create_poster)
             .service(acquire::acquire_poster)
+            .service(acquire::acquire_many_poster)
             .service(query::query_poster),
     );
 }
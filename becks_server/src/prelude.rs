@@ -1,5 +1,11 @@
+pub(crate) use crate::error::{api_error, error_response};
 pub(crate) use crate::extract_login;
-pub(crate) use actix_web::{get, http, post, put, web, App, HttpResponse, HttpServer, Responder};
+pub(crate) use crate::run_blocking;
+pub(crate) use actix_web::{
+    get, http, post, web, App, HttpRequest, HttpResponse, HttpServer, Responder,
+};
+pub(crate) use becks_convey::error::{ApiError, ErrorCode};
+pub(crate) use becks_convey::user::member::Role;
 pub(crate) use becks_crew::check;
 pub(crate) use log::{debug, error, info, trace, warn};
 pub(crate) use serde::{Deserialize, Serialize};
@@ -0,0 +1,24 @@
+use crate::prelude::*;
+use becks_convey::swiss::acquire::*;
+
+#[get("/acquire")]
+pub(super) async fn acquire_swiss(
+    http: HttpRequest,
+    req: web::Json<AcquireRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire Swiss event id {:?}", req.swiss);
+    let login = extract_login!(db, http, req.token);
+    let id = req.swiss;
+    if let Some(swiss) = run_blocking!(move || becks_ops::swiss::acquire_swiss(login.as_ref(), id))
+    {
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(AcquireResponse { swiss })
+    } else {
+        api_error(
+            ErrorCode::SwissNotFound,
+            "unable to acquire given Swiss event id",
+        )
+    }
+}
@@ -0,0 +1,31 @@
+use crate::prelude::*;
+use becks_convey::swiss::close::*;
+use becks_ops::swiss::CloseSwissError;
+
+#[post("/close")]
+pub(super) async fn close_swiss(
+    http: HttpRequest,
+    req: web::Json<CloseRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token, Role::Recorder);
+    info!("Closing Swiss event {:?} by {}", req.swiss, login.name);
+    let id = req.swiss;
+    match run_blocking!(move || becks_ops::swiss::close_swiss(login.as_ref(), id)) {
+        Ok(swiss) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(CloseResponse { swiss }),
+        Err(err @ CloseSwissError::SwissNotFound(_)) => {
+            warn!("Unable to close Swiss event because {}", err);
+            api_error(ErrorCode::SwissNotFound, err.to_string())
+        }
+        Err(err @ CloseSwissError::AlreadyClosed(_)) => {
+            warn!("Unable to close Swiss event because {}", err);
+            api_error(ErrorCode::InvalidModification, err.to_string())
+        }
+        Err(err) => {
+            error!("When closing Swiss event, {}", err);
+            api_error(ErrorCode::Internal, "unable to close Swiss event")
+        }
+    }
+}
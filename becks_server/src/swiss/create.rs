@@ -0,0 +1,35 @@
+use crate::prelude::*;
+use becks_convey::swiss::create::*;
+use becks_ops::swiss::CreateSwissError;
+
+#[post("/create")]
+pub(super) async fn create_swiss(
+    http: HttpRequest,
+    req: web::Json<CreateRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token, Role::Recorder);
+    info!(
+        "Creating Swiss event of {} crews by {}",
+        req.crews.len(),
+        login.name
+    );
+    let CreateRequest { crews, since, .. } = req.into_inner();
+    match run_blocking!(move || becks_ops::swiss::create_swiss(login.as_ref(), &crews, since)) {
+        Ok(swiss) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(CreateResponse { swiss }),
+        Err(err @ CreateSwissError::CrewNotFound(_)) => {
+            warn!("Unable to create Swiss event because {}", err);
+            api_error(ErrorCode::CrewNotFound, err.to_string())
+        }
+        Err(err @ CreateSwissError::Invalid(_)) => {
+            warn!("Unable to create Swiss event because {}", err);
+            api_error(ErrorCode::IllegalInput, err.to_string())
+        }
+        Err(err) => {
+            error!("When creating Swiss event, {}", err);
+            api_error(ErrorCode::Internal, "unable to create Swiss event")
+        }
+    }
+}
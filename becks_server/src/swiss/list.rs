@@ -0,0 +1,21 @@
+use crate::prelude::*;
+use becks_convey::swiss::list::*;
+
+#[get("/list")]
+pub(super) async fn list_swiss(
+    http: HttpRequest,
+    req: web::Json<ListRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token);
+    let closed = req.closed;
+    match run_blocking!(move || becks_ops::swiss::list_swiss(login.as_ref(), closed)) {
+        Ok(swiss) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(ListResponse { swiss }),
+        Err(err) => {
+            error!("When listing Swiss events, {}", err);
+            api_error(ErrorCode::Internal, "unable to list Swiss events")
+        }
+    }
+}
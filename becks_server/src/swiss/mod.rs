@@ -0,0 +1,25 @@
+mod acquire;
+mod close;
+mod create;
+mod list;
+mod next;
+
+use crate::prelude::*;
+
+#[get("/test")]
+async fn test() -> impl Responder {
+    debug!("Responding /swiss/test");
+    HttpResponse::Ok().body("Swiss module is running!")
+}
+
+pub fn config_swiss(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/swiss")
+            .service(test)
+            .service(create::create_swiss)
+            .service(acquire::acquire_swiss)
+            .service(list::list_swiss)
+            .service(next::next_round)
+            .service(close::close_swiss),
+    );
+}
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+use becks_convey::swiss::next::*;
+use becks_ops::swiss::NextRoundError;
+
+#[post("/next")]
+pub(super) async fn next_round(
+    http: HttpRequest,
+    req: web::Json<NextRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token, Role::Recorder);
+    info!(
+        "Pairing next round of Swiss event {:?} by {}",
+        req.swiss, login.name
+    );
+    let id = req.swiss;
+    match run_blocking!(move || becks_ops::swiss::next_round(login.as_ref(), id)) {
+        Ok(swiss) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(NextResponse { swiss }),
+        Err(err @ NextRoundError::SwissNotFound(_)) => {
+            warn!("Unable to pair next round because {}", err);
+            api_error(ErrorCode::SwissNotFound, err.to_string())
+        }
+        Err(err @ (NextRoundError::Closed(_) | NextRoundError::RoundNotDone(_))) => {
+            warn!("Unable to pair next round because {}", err);
+            api_error(ErrorCode::InvalidModification, err.to_string())
+        }
+        Err(err) => {
+            error!("When pairing next round, {}", err);
+            api_error(ErrorCode::Internal, "unable to pair next round")
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+use becks_convey::tournament::acquire::*;
+
+#[get("/acquire")]
+pub(super) async fn acquire_tournament(
+    http: HttpRequest,
+    req: web::Json<AcquireRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to acquire tournament id {:?}", req.tournament);
+    let login = extract_login!(db, http, req.token);
+    let id = req.tournament;
+    if let Some(tournament) =
+        run_blocking!(move || becks_ops::tournament::acquire_tournament(login.as_ref(), id))
+    {
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(AcquireResponse {
+                standings: tournament.standings(),
+                champion: tournament.champion(),
+                tournament,
+            })
+    } else {
+        api_error(
+            ErrorCode::TournamentNotFound,
+            "unable to acquire given tournament id",
+        )
+    }
+}
@@ -0,0 +1,31 @@
+use crate::prelude::*;
+use becks_convey::tournament::close::*;
+use becks_ops::tournament::CloseTournamentError;
+
+#[post("/close")]
+pub(super) async fn close_tournament(
+    http: HttpRequest,
+    req: web::Json<CloseRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token, Role::Recorder);
+    info!("Closing tournament {:?} by {}", req.tournament, login.name);
+    let id = req.tournament;
+    match run_blocking!(move || becks_ops::tournament::close_tournament(login.as_ref(), id)) {
+        Ok(tournament) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(CloseResponse { tournament }),
+        Err(err @ CloseTournamentError::TournamentNotFound(_)) => {
+            warn!("Unable to close tournament because {}", err);
+            api_error(ErrorCode::TournamentNotFound, err.to_string())
+        }
+        Err(err @ CloseTournamentError::AlreadyClosed(_)) => {
+            warn!("Unable to close tournament because {}", err);
+            api_error(ErrorCode::InvalidModification, err.to_string())
+        }
+        Err(err) => {
+            error!("When closing tournament, {}", err);
+            api_error(ErrorCode::Internal, "unable to close tournament")
+        }
+    }
+}
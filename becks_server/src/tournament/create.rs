@@ -0,0 +1,40 @@
+use crate::prelude::*;
+use becks_convey::tournament::create::*;
+use becks_ops::tournament::CreateTournamentError;
+
+#[post("/create")]
+pub(super) async fn create_tournament(
+    http: HttpRequest,
+    req: web::Json<CreateRequest>,
+    db: DbData,
+) -> HttpResponse {
+    let login = extract_login!(db, http, req.token, Role::Recorder);
+    info!(
+        "Creating tournament {} of {} crews by {}",
+        req.name,
+        req.crews.len(),
+        login.name
+    );
+    match run_blocking!(move || becks_ops::tournament::create_tournament(
+        login.as_ref(),
+        &req.name,
+        req.format,
+        &req.crews
+    )) {
+        Ok(tournament) => HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(CreateResponse { tournament }),
+        Err(err @ CreateTournamentError::CrewNotFound(_)) => {
+            warn!("Unable to create tournament because {}", err);
+            api_error(ErrorCode::CrewNotFound, err.to_string())
+        }
+        Err(err @ CreateTournamentError::Invalid(_)) => {
+            warn!("Unable to create tournament because {}", err);
+            api_error(ErrorCode::IllegalInput, err.to_string())
+        }
+        Err(err) => {
+            error!("When creating tournament, {}", err);
+            api_error(ErrorCode::Internal, "unable to create tournament")
+        }
+    }
+}
@@ -0,0 +1,21 @@
+mod acquire;
+mod close;
+mod create;
+
+use crate::prelude::*;
+
+#[get("/test")]
+async fn test() -> impl Responder {
+    debug!("Responding /tournament/test");
+    HttpResponse::Ok().body("Tournament module is running!")
+}
+
+pub fn config_tournament(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/tournament")
+            .service(test)
+            .service(create::create_tournament)
+            .service(acquire::acquire_tournament)
+            .service(close::close_tournament),
+    );
+}
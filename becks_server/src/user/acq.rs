@@ -1,13 +1,64 @@
+use crate::prelude::*;
+use becks_convey::user::auth::Token;
+
+/// Reads the token from the `Authorization: Bearer` header,
+/// falling back to the deprecated `token` field of the request body
+pub(crate) fn request_token(http: &HttpRequest, body: Option<&Token>) -> Option<Token> {
+    let header = http
+        .headers()
+        .get(http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|value| Token::new(value.trim().to_owned()));
+    header.or_else(|| {
+        let token = body.cloned();
+        if token.is_some() {
+            debug!(
+                "Token is carried in the request body of {}, which is deprecated",
+                http.path()
+            );
+        }
+        token
+    })
+}
+
+/// Extracts the login of the request, or returns 401 if the token is not valid;
+/// If a [`Role`](becks_convey::user::member::Role) is given, returns 403 unless the user has at least that role
 #[macro_export]
 macro_rules! extract_login {
-    ($db: expr, $token: expr) => {
-        if let Some(login) = $db.get_login($token) {
+    ($db: expr, $http: expr, $token: expr) => {
+        if let Some(login) = match $crate::user::request_token(&$http, $token.as_ref()) {
+            Some(token) => {
+                let db = $db.clone();
+                $crate::run_blocking!(move || db.get_login(&token))
+            }
+            None => None,
+        } {
             login
         } else {
-            warn!("Unable to find record for log-in token {:?}", $token);
-            return HttpResponse::Unauthorized()
-                .content_type(http::header::ContentType::plaintext())
-                .body("unable to find a record for given token");
+            warn!("Unable to find record for log-in token of {}", $http.path());
+            return $crate::error::api_error(
+                ErrorCode::InvalidToken,
+                "unable to find a record for given token",
+            );
         }
     };
+    ($db: expr, $http: expr, $token: expr, $role: expr) => {{
+        let login = $crate::extract_login!($db, $http, $token);
+        if login.role < $role {
+            warn!(
+                "User {} with role {:?} on {} requires {:?} for {}",
+                login.user,
+                login.role,
+                login.name,
+                $role,
+                $http.path()
+            );
+            return $crate::error::api_error(
+                ErrorCode::Forbidden,
+                "role is not sufficient for this operation",
+            );
+        }
+        login
+    }};
 }
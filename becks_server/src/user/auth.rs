@@ -4,64 +4,71 @@ use becks_convey::user::auth::*;
 #[post("/login")]
 pub(super) async fn log_in(req: web::Json<LoginRequest>, db: DbData) -> HttpResponse {
     use becks_crew::check;
-    debug!("Log-in attempt: {} with password {}", req.name, req.pass);
-    if check!(is_alnum req.name) && check!(is_alnum req.pass) {
-        if let Some(token) = db.log_in(&req.name, &req.pass) {
+    debug!("Log-in attempt: {}", req.name);
+    if check!(is_alnum req.name)
+        && check!(is_alnum req.pass)
+        && req.club.as_ref().is_none_or(|club| check!(is_alnum club))
+    {
+        let req = req.into_inner();
+        if let Some((token, role)) =
+            run_blocking!(move || db.log_in(&req.name, &req.pass, req.club.as_deref()))
+        {
             HttpResponse::Ok()
                 .content_type(http::header::ContentType::json())
-                .json(LoginResponse { token })
+                .json(LoginResponse { token, role })
         } else {
             warn!("Log-in failed with given credentials");
-            HttpResponse::Unauthorized()
-                .content_type(http::header::ContentType::plaintext())
-                .body("unable to log-in with given credentials")
+            api_error(
+                ErrorCode::WrongCredentials,
+                "unable to log-in with given credentials",
+            )
         }
     } else {
         warn!("Given name or pass is not legal");
-        HttpResponse::BadRequest()
-            .content_type(http::header::ContentType::plaintext())
-            .body("name or pass is not legal")
+        api_error(ErrorCode::IllegalInput, "name or pass is not legal")
     }
 }
 
-pub(super) async fn log_out_work(req: LogoutRequest, db: &becks_db::Db) -> HttpResponse {
-    if db.log_out(req.token) {
+#[post("/logout")]
+pub(super) async fn log_out(
+    http: HttpRequest,
+    req: web::Json<LogoutRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Log-out attempt");
+    let Some(token) = super::request_token(&http, req.token.as_ref()) else {
+        warn!("No token is given");
+        return api_error(ErrorCode::MissingToken, "token is not given");
+    };
+    if run_blocking!(move || db.log_out(&token)) {
         HttpResponse::Ok()
             .content_type(http::header::ContentType::plaintext())
             .body("log-out done")
     } else {
         warn!("Unable to find given token");
-        HttpResponse::BadRequest()
-            .content_type(http::header::ContentType::plaintext())
-            .body("token is not available")
+        api_error(ErrorCode::InvalidToken, "token is not available")
     }
 }
 
-#[post("/logout")]
-pub(super) async fn log_out(req: web::Json<LogoutRequest>, db: DbData) -> HttpResponse {
-    debug!("Log-out attempt: token {:?}", req.token);
-    log_out_work(*req, db.as_ref()).await
-}
-
 #[post("/create")]
 pub(super) async fn create_user(req: web::Json<CreateRequest>, db: DbData) -> HttpResponse {
-    debug!("Create attempt: {} with password {}", req.name, req.pass);
+    debug!("Create attempt: {}", req.name);
     if check!(is_alnum req.name) && check!(is_alnum req.pass) {
-        if db.create(&req.name, &req.pass) {
-            info!("User {} created", req.name);
+        let name = req.name.clone();
+        if run_blocking!(move || db.create(&req.name, &req.pass)) {
+            info!("User {} created", name);
             HttpResponse::Ok()
                 .content_type(http::header::ContentType::plaintext())
                 .body("user created")
         } else {
             warn!("Create user failed with given credentials");
-            HttpResponse::BadRequest()
-                .content_type(http::header::ContentType::plaintext())
-                .body("given credentials resulted in conflicts")
+            api_error(
+                ErrorCode::UserConflict,
+                "given credentials resulted in conflicts",
+            )
         }
     } else {
         warn!("Given name or pass is not legal");
-        HttpResponse::BadRequest()
-            .content_type(http::header::ContentType::plaintext())
-            .body("name or pass is not legal")
+        api_error(ErrorCode::IllegalInput, "name or pass is not legal")
     }
 }
@@ -1,44 +1,51 @@
 use crate::prelude::*;
-use becks_convey::user::auth::*;
 use becks_convey::user::clean::UpdateRequest;
 use becks_db::{Db, CONFIG};
 use std::sync::Arc;
 
-async fn clean_up_users(db: &Db) {
-    let mut futures = Vec::new();
-    db.login_map().iter().for_each(|(token, login)| {
-        if login.duration_since_last_update() > CONFIG.user.timeout {
+/// Closes idle sessions; Their tokens stay valid until expiry
+fn clean_up_users(db: &Db) {
+    let idle: Vec<_> = db
+        .login_map()
+        .iter()
+        .filter(|(_, session)| session.duration_since_last_seen() > CONFIG.user.timeout)
+        .map(|(token, session)| {
             info!(
-                "Disconnecting user with token {:?} after {:?}",
-                token,
-                login.duration_since_last_update()
+                "Disconnecting a session of user {} after {:?}",
+                session.login.name,
+                session.duration_since_last_seen()
             );
-            futures.push(super::auth::log_out_work(
-                LogoutRequest { token: *token },
-                db,
-            ));
-        }
-    });
-    for future in futures.into_iter() {
-        future.await;
+            token.clone()
+        })
+        .collect();
+    for token in idle.iter() {
+        db.evict(token);
     }
+    db.prune_sessions();
 }
 
 pub fn start_clean_up(db: Arc<Db>) {
     tokio::spawn(async move {
         loop {
-            let future = clean_up_users(db.as_ref());
             tokio::time::sleep(CONFIG.user.timeout).await;
-            future.await;
+            let db = db.clone();
+            // Evicting sessions writes to the database, which should not hold up the runtime
+            if let Err(err) = web::block(move || clean_up_users(db.as_ref())).await {
+                error!("When cleaning up users, {}", err);
+            }
         }
     });
 }
 
 #[post("/update")]
-pub(super) async fn update_user(req: web::Json<UpdateRequest>, db: DbData) -> HttpResponse {
-    debug!("Attempt to update user with token {:?}", req.token);
-    let login = extract_login!(db, &req.token);
-    login.update_time();
+pub(super) async fn update_user(
+    http: HttpRequest,
+    req: web::Json<UpdateRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to update user");
+    // Extracting the login marks the session as seen
+    let _login = extract_login!(db, http, req.token);
     HttpResponse::Ok()
         .content_type(http::header::ContentType::plaintext())
         .body("user time updated")
@@ -0,0 +1,91 @@
+use crate::prelude::*;
+use becks_convey::user::manage::*;
+
+#[post("/password")]
+pub(super) async fn change_password(
+    http: HttpRequest,
+    req: web::Json<PasswordRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to change password");
+    let login = extract_login!(db, http, req.token);
+    if !check!(is_alnum req.new) {
+        warn!("Given pass is not legal");
+        return api_error(ErrorCode::IllegalInput, "pass is not legal");
+    }
+    let user = login.user.clone();
+    let current = super::request_token(&http, req.token.as_ref());
+    if run_blocking!(move || db.change_pass(&user, &req.old, &req.new, current.as_ref())) {
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::plaintext())
+            .body("password changed")
+    } else {
+        warn!("Unable to change password for user {}", login.user);
+        api_error(
+            ErrorCode::WrongCredentials,
+            "unable to change password with given credentials",
+        )
+    }
+}
+
+#[post("/delete")]
+pub(super) async fn delete_user(
+    http: HttpRequest,
+    req: web::Json<DeleteRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to delete user");
+    let login = extract_login!(db, http, req.token);
+    let user = login.user.clone();
+    if run_blocking!(move || db.delete(&user, &req.pass)) {
+        info!("User {} deleted", login.user);
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::plaintext())
+            .body("user deleted")
+    } else {
+        warn!("Unable to delete user {}", login.user);
+        api_error(
+            ErrorCode::WrongCredentials,
+            "unable to delete user with given credentials",
+        )
+    }
+}
+
+#[post("/reset/issue")]
+pub(super) async fn issue_reset(
+    http: HttpRequest,
+    req: web::Json<IssueResetRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to issue reset code for user {}", req.name);
+    let login = extract_login!(db, http, req.token);
+    if let Some(code) = run_blocking!(move || db.issue_reset(&login.user, &req.name)) {
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::json())
+            .json(IssueResetResponse { code })
+    } else {
+        api_error(
+            ErrorCode::Forbidden,
+            "unable to issue reset code for given user",
+        )
+    }
+}
+
+#[post("/reset")]
+pub(super) async fn reset_password(req: web::Json<ResetRequest>, db: DbData) -> HttpResponse {
+    debug!("Attempt to reset password of user {}", req.name);
+    if !check!(is_alnum req.name) || !check!(is_alnum req.pass) {
+        warn!("Given name or pass is not legal");
+        return api_error(ErrorCode::IllegalInput, "name or pass is not legal");
+    }
+    if run_blocking!(move || db.reset_pass(&req.name, &req.code, &req.pass)) {
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::plaintext())
+            .body("password reset")
+    } else {
+        api_error(
+            ErrorCode::InvalidResetCode,
+            "unable to reset password with given code",
+        )
+    }
+}
@@ -0,0 +1,71 @@
+use crate::prelude::*;
+use becks_convey::user::member::*;
+
+#[get("/members")]
+pub(super) async fn list_members(
+    http: HttpRequest,
+    req: web::Json<MembersRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to list members");
+    let login = extract_login!(db, http, req.token);
+    let members = run_blocking!(move || db.members(&login.user));
+    HttpResponse::Ok()
+        .content_type(http::header::ContentType::json())
+        .json(MembersResponse { members })
+}
+
+#[get("/clubs")]
+pub(super) async fn list_clubs(
+    http: HttpRequest,
+    req: web::Json<ClubsRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to list clubs");
+    let login = extract_login!(db, http, req.token);
+    let clubs = run_blocking!(move || db.clubs(&login.user));
+    HttpResponse::Ok()
+        .content_type(http::header::ContentType::json())
+        .json(ClubsResponse { clubs })
+}
+
+#[post("/members/grant")]
+pub(super) async fn grant_member(
+    http: HttpRequest,
+    req: web::Json<GrantRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to grant {:?} to user {}", req.role, req.member);
+    let login = extract_login!(db, http, req.token);
+    if !check!(is_alnum req.member) {
+        warn!("Given member name is not legal");
+        return api_error(ErrorCode::IllegalInput, "member name is not legal");
+    }
+    if run_blocking!(move || db.grant(&login.user, &req.member, req.role)) {
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::plaintext())
+            .body("role granted")
+    } else {
+        api_error(
+            ErrorCode::GrantRejected,
+            "unable to grant given role to given user",
+        )
+    }
+}
+
+#[post("/members/revoke")]
+pub(super) async fn revoke_member(
+    http: HttpRequest,
+    req: web::Json<RevokeMemberRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to revoke membership of user {}", req.member);
+    let login = extract_login!(db, http, req.token);
+    if run_blocking!(move || db.revoke_member(&login.user, &req.member)) {
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::plaintext())
+            .body("membership revoked")
+    } else {
+        api_error(ErrorCode::MemberNotFound, "unable to find given member")
+    }
+}
@@ -1,8 +1,12 @@
 mod acq;
 mod auth;
 mod clean;
+mod manage;
+mod member;
+mod session;
 
 use crate::prelude::*;
+pub(crate) use acq::request_token;
 pub use clean::start_clean_up;
 
 #[get("/test")]
@@ -18,6 +22,16 @@ pub fn config_user(cfg: &mut web::ServiceConfig) {
             .service(auth::log_in)
             .service(auth::log_out)
             .service(auth::create_user)
-            .service(clean::update_user),
+            .service(clean::update_user)
+            .service(manage::change_password)
+            .service(manage::delete_user)
+            .service(manage::issue_reset)
+            .service(manage::reset_password)
+            .service(session::list_sessions)
+            .service(session::revoke_session)
+            .service(member::list_members)
+            .service(member::list_clubs)
+            .service(member::grant_member)
+            .service(member::revoke_member),
     );
 }
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+use becks_convey::user::session::*;
+
+#[get("/sessions")]
+pub(super) async fn list_sessions(
+    http: HttpRequest,
+    req: web::Json<SessionsRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to list sessions");
+    let login = extract_login!(db, http, req.token);
+    let current = super::request_token(&http, req.token.as_ref());
+    let sessions = run_blocking!(move || db.sessions(&login.user, current.as_ref()));
+    HttpResponse::Ok()
+        .content_type(http::header::ContentType::json())
+        .json(SessionsResponse { sessions })
+}
+
+#[post("/sessions/revoke")]
+pub(super) async fn revoke_session(
+    http: HttpRequest,
+    req: web::Json<RevokeSessionRequest>,
+    db: DbData,
+) -> HttpResponse {
+    debug!("Attempt to revoke session {}", req.session);
+    let login = extract_login!(db, http, req.token);
+    if run_blocking!(move || db.revoke_session(&login.user, &req.session)) {
+        HttpResponse::Ok()
+            .content_type(http::header::ContentType::plaintext())
+            .body("session revoked")
+    } else {
+        api_error(ErrorCode::SessionNotFound, "unable to find given session")
+    }
+}
@@ -0,0 +1,106 @@
+# Use this file to check that concurrent reads and writes of one club do not block each other
+#
+# Start the server first, then run `python load_test.py [threads] [requests]`;
+# Readers run `crew/query` while writers run `mat/create` against the same club,
+# and the latencies of both are reported
+
+import requests
+import statistics
+import sys
+import threading
+import time
+
+ROOT = "http://127.0.0.1:1145"
+NAME = "loadtest"
+PASS = "loadtest"
+
+
+def login() -> dict:
+    requests.post(ROOT + "/user/create", json = {"name": NAME, "pass": PASS})
+    response = requests.post(ROOT + "/user/login", json = {"name": NAME, "pass": PASS})
+    response.raise_for_status()
+    return {"Authorization": f"Bearer {response.json()['token']}"}
+
+
+def create_crew(headers: dict, name: str) -> int:
+    response = requests.post(ROOT + "/crew/create", headers = headers,
+                             json = {"name": name, "social": "Student"})
+    response.raise_for_status()
+    return response.json()["crew"]
+
+
+def reader(headers: dict, count: int, latencies: list, failures: list):
+    session = requests.Session()
+    for _ in range(count):
+        start = time.perf_counter()
+        response = session.get(ROOT + "/crew/query", headers = headers,
+                               json = {"by": [{"Social": "Student"}]})
+        latencies.append(time.perf_counter() - start)
+        if not response.ok:
+            failures.append(response.status_code)
+
+
+def writer(headers: dict, left: int, right: int, count: int, latencies: list, failures: list):
+    session = requests.Session()
+    for _ in range(count):
+        mat = {
+            "total_rounds": 3,
+            "left": left,
+            "right": right,
+            "round_worth": 10,
+            "rounds": [{"left_win": True}, {"left_win": False}, {"left_win": True}],
+            "timestamp": int(time.time()),
+        }
+        start = time.perf_counter()
+        response = session.post(ROOT + "/mat/create", headers = headers, json = {"mat": mat})
+        latencies.append(time.perf_counter() - start)
+        if not response.ok:
+            failures.append(response.status_code)
+
+
+def report(kind: str, latencies: list, failures: list):
+    if not latencies:
+        return
+    latencies = sorted(latencies)
+    p95 = latencies[int(len(latencies) * 0.95) - 1]
+    print(f"{kind}: {len(latencies)} requests, {len(failures)} failed, "
+          f"mean {statistics.mean(latencies) * 1000:.1f}ms, "
+          f"p95 {p95 * 1000:.1f}ms, max {latencies[-1] * 1000:.1f}ms")
+
+
+def run(threads: int, count: int, readers: bool, writers: bool) -> float:
+    headers = login()
+    left = create_crew(headers, "LoadLeft")
+    right = create_crew(headers, "LoadRight")
+    read_latencies, read_failures = [], []
+    write_latencies, write_failures = [], []
+    workers = []
+    for _ in range(threads):
+        if readers:
+            workers.append(threading.Thread(
+                target = reader, args = (headers, count, read_latencies, read_failures)))
+        if writers:
+            workers.append(threading.Thread(
+                target = writer,
+                args = (headers, left, right, count, write_latencies, write_failures)))
+    start = time.perf_counter()
+    for worker in workers:
+        worker.start()
+    for worker in workers:
+        worker.join()
+    elapsed = time.perf_counter() - start
+    report("crew/query", read_latencies, read_failures)
+    report("mat/create", write_latencies, write_failures)
+    print(f"elapsed {elapsed:.2f}s")
+    return elapsed
+
+
+if __name__ == "__main__":
+    threads = int(sys.argv[1]) if len(sys.argv) > 1 else 8
+    count = int(sys.argv[2]) if len(sys.argv) > 2 else 50
+    print("== reads only")
+    run(threads, count, True, False)
+    print("== writes only")
+    run(threads, count, False, True)
+    print("== reads and writes")
+    run(threads, count, True, True)
@@ -4,7 +4,7 @@ import requests
 import time
 
 ROOT = "http://127.0.0.1:1145"
-token = -1
+token = ""
 
 def format_route(route: str) -> str:
     if route.startswith('/'):
@@ -22,7 +22,7 @@ def login() :
         token = response.json()["token"]
 
 def tok() -> dict:
-    return {"token": token}
+    return {"Authorization": f"Bearer {token}"}
 
 def timestamp() -> int:
     timestamp = time.time()
@@ -30,8 +30,8 @@ def timestamp() -> int:
 
 def post(route: str, **json) -> requests.Response:
     global token
-    return requests.post(format_route(route), json = tok() | json)
+    return requests.post(format_route(route), headers = tok(), json = json)
 
 def get(route: str, **json) -> requests.Response:
     global token
-    return requests.get(format_route(route), json = tok() | json)
+    return requests.get(format_route(route), headers = tok(), json = json)